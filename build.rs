@@ -1,6 +1,20 @@
 fn main() {
     let mut config = prost_build::Config::new();
 
+    // Stored session results are also served as JSON
+    config
+        .type_attribute(".f1telemetry.SessionResult", "#[derive(serde::Serialize)]")
+        .type_attribute(
+            ".f1telemetry.FinalClassificationData",
+            "#[derive(serde::Serialize)]",
+        )
+        .type_attribute(".f1telemetry.HistoryData", "#[derive(serde::Serialize)]")
+        .type_attribute(".f1telemetry.LapHistoryData", "#[derive(serde::Serialize)]")
+        .type_attribute(
+            ".f1telemetry.TyreStintsHistoryData",
+            "#[derive(serde::Serialize)]",
+        );
+
     config
         .compile_protos(&["protos/f1.proto"], &["protos/"])
         .unwrap();
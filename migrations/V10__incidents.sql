@@ -0,0 +1,37 @@
+CREATE TYPE incident_type AS ENUM ('Collision', 'Penalty');
+CREATE TYPE incident_status AS ENUM ('Open', 'PenaltyApplied', 'Dismissed');
+
+-- Tables
+CREATE TABLE incidents (
+    id SERIAL PRIMARY KEY,
+    race_id INTEGER NOT NULL REFERENCES races(id) ON DELETE CASCADE,
+    session_type SMALLINT NOT NULL,
+    incident_type incident_type NOT NULL,
+    lap SMALLINT NOT NULL,
+    session_time REAL NOT NULL,
+    steam_name VARCHAR(100) NOT NULL,
+    other_steam_name VARCHAR(100),
+    penalty_type SMALLINT,
+    infringement_type SMALLINT,
+    game_penalty_time SMALLINT,
+    status incident_status NOT NULL DEFAULT 'Open',
+    penalty_seconds SMALLINT,
+    resolved_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    resolved_at TIMESTAMPTZ
+);
+
+CREATE TABLE incident_comments (
+    id SERIAL PRIMARY KEY,
+    incident_id INTEGER NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    user_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    body TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Optimized indexes
+CREATE INDEX idx_incidents_race ON incidents (race_id, status);
+CREATE INDEX idx_incident_comments_incident ON incident_comments (incident_id, created_at);
+
+ANALYZE incidents;
+ANALYZE incident_comments;
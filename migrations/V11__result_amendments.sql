@@ -0,0 +1,25 @@
+CREATE TYPE result_amendment_type AS ENUM ('TimePenalty', 'Disqualification', 'Reorder');
+
+-- The game classification in `data` stays untouched, amendments are replayed on top of it
+ALTER TABLE results ADD COLUMN amended_data BYTEA;
+
+-- Tables
+CREATE TABLE result_amendments (
+    id SERIAL PRIMARY KEY,
+    race_id INTEGER NOT NULL,
+    session_type SMALLINT NOT NULL,
+    amendment_type result_amendment_type NOT NULL,
+    steam_name VARCHAR(100),
+    seconds SMALLINT,
+    classification_order VARCHAR(100)[],
+    reason VARCHAR(500),
+    incident_id INTEGER REFERENCES incidents(id) ON DELETE SET NULL,
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (race_id, session_type) REFERENCES results(race_id, session_type) ON DELETE CASCADE
+);
+
+-- Optimized indexes
+CREATE INDEX idx_result_amendments_result ON result_amendments (race_id, session_type, id);
+
+ANALYZE result_amendments;
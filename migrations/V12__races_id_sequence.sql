@@ -0,0 +1,4 @@
+-- Races were created without an id default, imports need the database to assign them
+CREATE SEQUENCE IF NOT EXISTS races_id_seq OWNED BY races.id;
+SELECT setval('races_id_seq', COALESCE((SELECT MAX(id) FROM races), 0) + 1, false);
+ALTER TABLE races ALTER COLUMN id SET DEFAULT nextval('races_id_seq');
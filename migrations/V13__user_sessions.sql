@@ -0,0 +1,16 @@
+-- Tables
+CREATE TABLE user_sessions (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    fingerprint VARCHAR(255) NOT NULL,
+    token_hash VARCHAR(64) NOT NULL,
+    device VARCHAR(100),
+    ip VARCHAR(45),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_used_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMPTZ NOT NULL,
+    UNIQUE (user_id, fingerprint)
+);
+
+-- Indexes
+CREATE INDEX idx_user_sessions_expires_at ON user_sessions(expires_at);
@@ -0,0 +1,9 @@
+-- Tables
+CREATE TABLE user_session_rotated_tokens (
+    token_hash VARCHAR(64) PRIMARY KEY,
+    session_id INTEGER NOT NULL REFERENCES user_sessions(id) ON DELETE CASCADE,
+    rotated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Indexes
+CREATE INDEX idx_user_session_rotated_tokens_session_id ON user_session_rotated_tokens(session_id);
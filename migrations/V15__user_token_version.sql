@@ -0,0 +1,2 @@
+-- Bumped to revoke every access token issued to the user
+ALTER TABLE users ADD COLUMN token_version INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,19 @@
+-- Types
+CREATE TYPE api_key_scope AS ENUM ('ReadResults', 'ReadLive', 'ManageRaces');
+
+-- Tables
+CREATE TABLE api_keys (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    championship_id INTEGER NOT NULL REFERENCES championships(id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    prefix VARCHAR(16) NOT NULL UNIQUE,
+    key_hash VARCHAR(255) NOT NULL,
+    scopes api_key_scope[] NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
+
+-- Indexes
+CREATE INDEX idx_api_keys_user_id ON api_keys(user_id);
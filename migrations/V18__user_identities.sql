@@ -0,0 +1,16 @@
+-- Tables
+CREATE TABLE user_identities (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    provider user_provider NOT NULL,
+    provider_user_id VARCHAR(64) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (provider, provider_user_id),
+    UNIQUE (user_id, provider)
+);
+
+-- Existing Discord accounts
+INSERT INTO user_identities (user_id, provider, provider_user_id)
+SELECT id, 'Discord', discord_id::TEXT
+FROM users
+WHERE discord_id IS NOT NULL;
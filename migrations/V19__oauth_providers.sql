@@ -0,0 +1,3 @@
+-- Types
+ALTER TYPE user_provider ADD VALUE 'Google';
+ALTER TYPE user_provider ADD VALUE 'Steam';
@@ -0,0 +1,4 @@
+-- Claims are only resolved by championship admins, the ones without a championship can't be
+UPDATE driver_claims
+SET status = 'Rejected', resolved_at = CURRENT_TIMESTAMP
+WHERE championship_id IS NULL AND status = 'Pending';
@@ -0,0 +1,22 @@
+CREATE TYPE transfer_status AS ENUM ('Pending', 'Approved', 'Rejected');
+
+-- Tables
+CREATE TABLE driver_transfers (
+    id SERIAL PRIMARY KEY,
+    steam_name VARCHAR(100) NOT NULL REFERENCES drivers(steam_name) ON DELETE CASCADE,
+    championship_id INTEGER NOT NULL REFERENCES championships(id) ON DELETE CASCADE,
+    from_team_id SMALLINT NOT NULL,
+    from_number SMALLINT NOT NULL,
+    to_team_id SMALLINT NOT NULL,
+    to_number SMALLINT NOT NULL,
+    status transfer_status NOT NULL DEFAULT 'Pending',
+    resolved_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    resolved_at TIMESTAMPTZ
+);
+
+-- Optimized indexes
+CREATE UNIQUE INDEX idx_driver_transfers_pending ON driver_transfers (championship_id, steam_name) WHERE status = 'Pending';
+CREATE INDEX idx_driver_transfers_championship ON driver_transfers (championship_id, status);
+
+ANALYZE driver_transfers;
@@ -0,0 +1,21 @@
+CREATE TYPE claim_status AS ENUM ('Pending', 'Verified', 'Approved', 'Rejected');
+
+-- Tables
+CREATE TABLE driver_claims (
+    id SERIAL PRIMARY KEY,
+    steam_name VARCHAR(100) NOT NULL,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    championship_id INTEGER REFERENCES championships(id) ON DELETE SET NULL,
+    code CHAR(6) NOT NULL,
+    status claim_status NOT NULL DEFAULT 'Pending',
+    resolved_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    resolved_at TIMESTAMPTZ
+);
+
+-- Optimized indexes
+CREATE UNIQUE INDEX idx_driver_claims_pending ON driver_claims (user_id, steam_name) WHERE status = 'Pending';
+CREATE INDEX idx_driver_claims_championship ON driver_claims (championship_id) WHERE status = 'Pending';
+CREATE INDEX idx_driver_claims_user ON driver_claims (user_id);
+
+ANALYZE driver_claims;
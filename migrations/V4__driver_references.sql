@@ -0,0 +1,10 @@
+-- Renaming a driver rewrites every reference to its steam name
+ALTER TABLE championship_drivers
+    DROP CONSTRAINT championship_drivers_steam_name_fkey,
+    ADD CONSTRAINT championship_drivers_steam_name_fkey
+        FOREIGN KEY (steam_name) REFERENCES drivers(steam_name) ON UPDATE CASCADE ON DELETE CASCADE;
+
+ALTER TABLE driver_transfers
+    DROP CONSTRAINT driver_transfers_steam_name_fkey,
+    ADD CONSTRAINT driver_transfers_steam_name_fkey
+        FOREIGN KEY (steam_name) REFERENCES drivers(steam_name) ON UPDATE CASCADE ON DELETE CASCADE;
@@ -0,0 +1,21 @@
+CREATE TYPE invitation_status AS ENUM ('Pending', 'Accepted', 'Declined');
+
+-- Tables
+CREATE TABLE championship_invitations (
+    id SERIAL PRIMARY KEY,
+    championship_id INTEGER NOT NULL REFERENCES championships(id) ON DELETE CASCADE,
+    email VARCHAR(255) NOT NULL,
+    role championship_role NOT NULL DEFAULT 'Visitor',
+    team_id SMALLINT,
+    invited_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    status invitation_status NOT NULL DEFAULT 'Pending',
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    responded_at TIMESTAMPTZ
+);
+
+-- Optimized indexes
+CREATE UNIQUE INDEX idx_championship_invitations_pending ON championship_invitations (championship_id, email) WHERE status = 'Pending';
+CREATE INDEX idx_championship_invitations_email ON championship_invitations (email) WHERE status = 'Pending';
+
+ANALYZE championship_invitations;
@@ -0,0 +1,22 @@
+CREATE TYPE championship_visibility AS ENUM ('Public', 'Unlisted', 'Private');
+CREATE TYPE join_request_status AS ENUM ('Pending', 'Approved', 'Rejected');
+
+ALTER TABLE championships ADD COLUMN visibility championship_visibility NOT NULL DEFAULT 'Private';
+
+-- Tables
+CREATE TABLE championship_join_requests (
+    id SERIAL PRIMARY KEY,
+    championship_id INTEGER NOT NULL REFERENCES championships(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    status join_request_status NOT NULL DEFAULT 'Pending',
+    resolved_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    resolved_at TIMESTAMPTZ
+);
+
+-- Optimized indexes
+CREATE INDEX idx_championships_visibility ON championships (visibility, category) WHERE visibility = 'Public';
+CREATE UNIQUE INDEX idx_championship_join_requests_pending ON championship_join_requests (championship_id, user_id) WHERE status = 'Pending';
+
+ANALYZE championships;
+ANALYZE championship_join_requests;
@@ -0,0 +1,19 @@
+CREATE TYPE owner_transfer_status AS ENUM ('Pending', 'Accepted', 'Declined', 'Cancelled', 'Forced');
+
+-- Tables
+CREATE TABLE championship_owner_transfers (
+    id SERIAL PRIMARY KEY,
+    championship_id INTEGER NOT NULL REFERENCES championships(id) ON DELETE CASCADE,
+    from_user_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    to_user_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    status owner_transfer_status NOT NULL DEFAULT 'Pending',
+    initiated_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    resolved_at TIMESTAMPTZ
+);
+
+-- Optimized indexes
+CREATE UNIQUE INDEX idx_championship_owner_transfers_pending ON championship_owner_transfers (championship_id) WHERE status = 'Pending';
+CREATE INDEX idx_championship_owner_transfers_championship ON championship_owner_transfers (championship_id, created_at);
+
+ANALYZE championship_owner_transfers;
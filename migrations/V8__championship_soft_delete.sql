@@ -0,0 +1,6 @@
+ALTER TABLE championships ADD COLUMN deleted_at TIMESTAMPTZ;
+
+-- Optimized indexes
+CREATE INDEX idx_championships_deleted ON championships (deleted_at) WHERE deleted_at IS NOT NULL;
+
+ANALYZE championships;
@@ -0,0 +1,19 @@
+ALTER TYPE championship_role ADD VALUE IF NOT EXISTS 'RaceDirector';
+
+-- Tables
+CREATE TABLE championship_engineer_teams (
+    championship_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    team_id SMALLINT NOT NULL,
+    PRIMARY KEY (championship_id, user_id, team_id),
+    FOREIGN KEY (user_id, championship_id) REFERENCES championship_users(user_id, championship_id) ON DELETE CASCADE
+);
+
+-- Engineers keep the team they had
+INSERT INTO championship_engineer_teams (championship_id, user_id, team_id)
+SELECT championship_id, user_id, team_id FROM championship_users
+WHERE role = 'Engineer' AND team_id IS NOT NULL;
+
+ALTER TABLE championship_users DROP COLUMN team_id;
+
+ANALYZE championship_engineer_teams;
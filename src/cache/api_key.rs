@@ -0,0 +1,63 @@
+use quick_cache::sync::Cache;
+use tokio::time::Instant;
+
+use crate::{
+    config::constants::{API_KEY_CACHE_TTL, API_KEY_FAILURE_DELAY},
+    entity::SharedApiKey,
+};
+
+use super::CACHE_CAPACITY;
+
+/// Keys recently verified against their hash, so a bot polling the API
+/// doesn't pay for the password hash on every request.
+///
+/// Prefixes that just failed a verification are remembered too, so wrong
+/// secrets cost at most one hash per prefix every `API_KEY_FAILURE_DELAY`.
+pub struct ApiKeyCache {
+    verified: Cache<String, (Instant, String, SharedApiKey)>,
+    failures: Cache<String, Instant>,
+}
+
+impl ApiKeyCache {
+    pub fn new() -> Self {
+        Self {
+            verified: Cache::new(CACHE_CAPACITY),
+            failures: Cache::new(CACHE_CAPACITY),
+        }
+    }
+
+    /// Retrieves a verified key by its prefix if the secret digest matches.
+    pub fn get(&self, prefix: &str, secret_digest: &str) -> Option<SharedApiKey> {
+        let (expiry, digest, key) = self.verified.get(prefix)?;
+
+        if Instant::now() >= expiry {
+            self.verified.remove(prefix);
+            return None;
+        }
+
+        (digest == secret_digest).then_some(key)
+    }
+
+    pub fn set(&self, secret_digest: String, key: SharedApiKey) {
+        let expiry = Instant::now() + API_KEY_CACHE_TTL;
+
+        self.verified
+            .insert(key.prefix.clone(), (expiry, secret_digest, key));
+    }
+
+    /// Checks if a verification failed for the prefix in the last `API_KEY_FAILURE_DELAY`.
+    pub fn recently_failed(&self, prefix: &str) -> bool {
+        self.failures
+            .get(prefix)
+            .is_some_and(|expiry| Instant::now() < expiry)
+    }
+
+    pub fn set_failure(&self, prefix: &str) {
+        let expiry = Instant::now() + API_KEY_FAILURE_DELAY;
+        self.failures.insert(prefix.to_owned(), expiry);
+    }
+
+    pub fn delete(&self, prefix: &str) {
+        self.verified.remove(prefix);
+    }
+}
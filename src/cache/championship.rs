@@ -2,13 +2,14 @@ use std::sync::Arc;
 
 use quick_cache::sync::Cache;
 
-use crate::entity::{Championship, Race};
+use crate::entity::{Championship, Race, SharedStandings};
 
 use super::{EntityCache, CACHE_CAPACITY};
 
 pub struct ChampionshipCache {
     inner: Cache<i32, Arc<Championship>>,
     races: Cache<i32, Vec<Arc<Race>>>,
+    standings: Cache<i32, SharedStandings>,
     name_to_id: Cache<String, i32>,
     user_championships: Cache<i32, Vec<Arc<Championship>>>,
 }
@@ -18,6 +19,7 @@ impl ChampionshipCache {
         Self {
             inner: Cache::new(CACHE_CAPACITY),
             races: Cache::new(CACHE_CAPACITY),
+            standings: Cache::new(CACHE_CAPACITY),
             name_to_id: Cache::new(CACHE_CAPACITY),
             user_championships: Cache::new(CACHE_CAPACITY),
         }
@@ -35,6 +37,18 @@ impl ChampionshipCache {
         self.races.remove(id);
     }
 
+    pub fn get_standings(&self, id: &i32) -> Option<SharedStandings> {
+        self.standings.get(id)
+    }
+
+    pub fn set_standings(&self, id: i32, standings: SharedStandings) {
+        self.standings.insert(id, standings)
+    }
+
+    pub fn delete_standings(&self, id: &i32) {
+        self.standings.remove(id);
+    }
+
     pub fn get_user_championships(&self, user_id: &i32) -> Option<Vec<Arc<Championship>>> {
         self.user_championships.get(user_id)
     }
@@ -57,6 +71,7 @@ impl ChampionshipCache {
             self.delete_by_user(&user);
         }
 
+        self.delete_standings(&id);
         self.delete(id);
     }
 }
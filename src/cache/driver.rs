@@ -1,17 +1,19 @@
 use quick_cache::sync::Cache;
 
-use crate::entity::SharedDriver;
+use crate::entity::{SharedDriver, SharedDriverStats};
 
 use super::CACHE_CAPACITY;
 
 pub struct DriverCache {
     inner: Cache<String, SharedDriver>,
+    stats: Cache<String, SharedDriverStats>,
 }
 
 impl DriverCache {
     pub fn new() -> Self {
         DriverCache {
             inner: Cache::new(CACHE_CAPACITY),
+            stats: Cache::new(CACHE_CAPACITY),
         }
     }
 
@@ -25,5 +27,18 @@ impl DriverCache {
 
     pub fn delete(&self, steam_name: &str) {
         self.inner.remove(steam_name);
+        self.stats.remove(steam_name);
+    }
+
+    pub fn get_stats(&self, steam_name: &str) -> Option<SharedDriverStats> {
+        self.stats.get(steam_name)
+    }
+
+    pub fn set_stats(&self, steam_name: &str, stats: SharedDriverStats) {
+        self.stats.insert(steam_name.to_owned(), stats)
+    }
+
+    pub fn delete_stats(&self, steam_name: &str) {
+        self.stats.remove(steam_name);
     }
 }
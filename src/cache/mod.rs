@@ -2,8 +2,11 @@ use std::sync::Arc;
 
 use driver::DriverCache;
 
-use self::{championship::ChampionshipCache, token::TokenCache, user::UserCache};
+use self::{
+    api_key::ApiKeyCache, championship::ChampionshipCache, token::TokenCache, user::UserCache,
+};
 
+mod api_key;
 mod championship;
 mod driver;
 mod token;
@@ -11,12 +14,13 @@ mod user;
 
 const CACHE_CAPACITY: usize = 2_000;
 
-/// Caching layer for users, championships, and tokens.
+/// Caching layer for users, championships, tokens and API keys.
 pub struct ServiceCache {
     pub user: UserCache,
     pub driver: DriverCache,
     pub championship: ChampionshipCache,
     pub token: TokenCache,
+    pub api_key: ApiKeyCache,
 }
 
 impl ServiceCache {
@@ -27,6 +31,7 @@ impl ServiceCache {
             driver: DriverCache::new(),
             championship: ChampionshipCache::new(),
             token: TokenCache::new(),
+            api_key: ApiKeyCache::new(),
         }
     }
 }
@@ -3,7 +3,12 @@ use std::time::Duration;
 use quick_cache::sync::Cache;
 use tokio::time::Instant;
 
-use crate::structs::TokenPurpose;
+use crate::{
+    config::constants::{
+        OAUTH_EXCHANGE_CODE_TTL, OAUTH_STATE_TTL, TWO_FACTOR_LOCKOUT, TWO_FACTOR_MAX_ATTEMPTS,
+    },
+    structs::{OAuthState, TokenPurpose},
+};
 
 use super::CACHE_CAPACITY;
 
@@ -11,6 +16,9 @@ use super::CACHE_CAPACITY;
 pub struct TokenCache {
     inner: Cache<(String, TokenPurpose), Instant>,
     refresh_tokens: Cache<(i32, String), (Instant, String)>,
+    oauth_states: Cache<String, (Instant, OAuthState)>,
+    exchange_codes: Cache<String, (Instant, i32)>,
+    two_factor_failures: Cache<i32, (Instant, u8)>,
 }
 
 // TODO - Parse possibles errors
@@ -19,6 +27,9 @@ impl TokenCache {
         Self {
             inner: Cache::new(CACHE_CAPACITY),
             refresh_tokens: Cache::new(CACHE_CAPACITY),
+            oauth_states: Cache::new(CACHE_CAPACITY),
+            exchange_codes: Cache::new(CACHE_CAPACITY),
+            two_factor_failures: Cache::new(CACHE_CAPACITY),
         }
     }
 
@@ -27,13 +38,71 @@ impl TokenCache {
         self.inner.insert((token, token_type), expiry)
     }
 
-    pub fn set_refresh_token(&self, user_id: i32, fingerprint: String, token: String) {
-        let expiry = Instant::now() + Duration::from_secs(86400);
+    pub fn set_refresh_token(
+        &self,
+        user_id: i32,
+        fingerprint: String,
+        token: String,
+        ttl: Duration,
+    ) {
+        let expiry = Instant::now() + ttl.min(Duration::from_secs(86400));
 
         self.refresh_tokens
             .insert((user_id, fingerprint), (expiry, token));
     }
 
+    pub fn set_oauth_state(&self, state: String, oauth_state: OAuthState) {
+        let expiry = Instant::now() + OAUTH_STATE_TTL;
+        self.oauth_states.insert(state, (expiry, oauth_state));
+    }
+
+    /// Removes and returns a pending authorization, a state can only be used once
+    /// and only by the browser holding it in its cookie
+    pub fn take_oauth_state(&self, state: &str, browser_state: Option<&str>) -> Option<OAuthState> {
+        if browser_state != Some(state) {
+            return None;
+        }
+
+        let (_, (expiry, oauth_state)) = self.oauth_states.remove(state)?;
+        (Instant::now() < expiry).then_some(oauth_state)
+    }
+
+    pub fn set_exchange_code(&self, code: String, user_id: i32) {
+        let expiry = Instant::now() + OAUTH_EXCHANGE_CODE_TTL;
+        self.exchange_codes.insert(code, (expiry, user_id));
+    }
+
+    /// Removes and returns the user of an exchange code, a code can only be used once
+    pub fn take_exchange_code(&self, code: &str) -> Option<i32> {
+        let (_, (expiry, user_id)) = self.exchange_codes.remove(code)?;
+        (Instant::now() < expiry).then_some(user_id)
+    }
+
+    /// Counts a wrong two-factor code, the window restarts with the first failure
+    pub fn set_two_factor_failure(&self, user_id: i32) {
+        let now = Instant::now();
+
+        let failure = match self.two_factor_failures.get(&user_id) {
+            Some((expiry, failures)) if now < expiry => (expiry, failures.saturating_add(1)),
+            _ => (now + TWO_FACTOR_LOCKOUT, 1),
+        };
+
+        self.two_factor_failures.insert(user_id, failure);
+    }
+
+    /// Checks if a user ran out of two-factor attempts
+    pub fn two_factor_locked(&self, user_id: i32) -> bool {
+        self.two_factor_failures
+            .get(&user_id)
+            .is_some_and(|(expiry, failures)| {
+                Instant::now() < expiry && failures >= TWO_FACTOR_MAX_ATTEMPTS
+            })
+    }
+
+    pub fn remove_two_factor_failures(&self, user_id: i32) {
+        self.two_factor_failures.remove(&user_id);
+    }
+
     pub fn get_token(&self, token: String, token_type: TokenPurpose) -> bool {
         if let Some(expiry) = self.inner.get(&(token.clone(), token_type)) {
             if Instant::now() < expiry {
@@ -66,3 +135,29 @@ impl TokenCache {
         self.refresh_tokens.remove(&(user_id, fingerprint));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::Provider;
+
+    #[test]
+    fn link_state_is_taken_with_the_browser_cookie_only() {
+        let cache = TokenCache::new();
+        cache.set_oauth_state(
+            "state".to_owned(),
+            OAuthState {
+                provider: Provider::Discord,
+                code_verifier: "verifier".to_owned(),
+                link_user_id: Some(600000000),
+            },
+        );
+
+        assert!(cache.take_oauth_state("state", None).is_none());
+        assert!(cache.take_oauth_state("state", Some("other")).is_none());
+
+        let oauth_state = cache.take_oauth_state("state", Some("state")).unwrap();
+        assert_eq!(oauth_state.link_user_id, Some(600000000));
+        assert!(cache.take_oauth_state("state", Some("state")).is_none());
+    }
+}
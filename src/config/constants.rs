@@ -11,20 +11,63 @@ use ring::pbkdf2;
 pub const BEARER_PREFIX: &str = "Bearer ";
 pub const LOGIN_RATE_LIMIT: u8 = 5;
 pub const LOGIN_RATE_LIMIT_DUR: Duration = Duration::from_secs(120);
+pub const JWT_KEYS_DIR: &str = "certs/jwt";
+pub const JWT_ACTIVE_KID_FILE: &str = "active";
+pub const JWT_LEGACY_KEY: &str = "certs/jsonwebtoken";
+pub const JWT_LEGACY_KID: &str = "default";
+pub const JWT_KEYS_RELOAD_INTERVAL: Duration = Duration::from_secs(5 * 60);
 
-// Discord auth
+// Two-factor auth
+pub const TOTP_ISSUER: &str = "Intelli Telemetry";
+pub const TOTP_PERIOD: u64 = 30;
+pub const TOTP_DIGITS: u32 = 6;
+pub const TOTP_SKEW: u64 = 1;
+pub const TOTP_SECRET_LEN: usize = 20;
+pub const RECOVERY_CODES: usize = 10;
+pub const TWO_FACTOR_MAX_ATTEMPTS: u8 = 5;
+pub const TWO_FACTOR_LOCKOUT: Duration = Duration::from_secs(15 * 60);
+
+// API keys
+pub const API_KEY_HEADER: &str = "X-Api-Key";
+pub const API_KEY_PREFIX: &str = "ik_";
+pub const API_KEY_LIMIT: usize = 20;
+pub const API_KEY_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+pub const API_KEY_FAILURE_DELAY: Duration = Duration::from_secs(2);
+
+// External auth providers
+pub const OAUTH_REDIRECT_BASE: &str = "https://intellitelemetry.live/auth";
+pub const OAUTH_STATE_TTL: Duration = Duration::from_secs(10 * 60);
+pub const OAUTH_STATE_COOKIE: &str = "oauth_state";
+pub const OAUTH_EXCHANGE_CODE_TTL: Duration = Duration::from_secs(60);
+pub const ACCOUNT_LINK_REDIRECT: &str = "https://intellitelemetry.live/user/settings";
 pub const DISCORD_API_URL: &str = "https://discord.com/api/v10";
-pub const DISCORD_REDIRECT: &str = "https://intellitelemetry.live/auth/discord/callback";
+pub const DISCORD_AUTHORIZE_URL: &str = "https://discord.com/oauth2/authorize";
+pub const DISCORD_SCOPES: &str = "identify email";
+pub const GOOGLE_AUTHORIZE_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+pub const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+pub const GOOGLE_USERINFO_URL: &str = "https://openidconnect.googleapis.com/v1/userinfo";
+pub const GOOGLE_SCOPES: &str = "openid email profile";
+pub const STEAM_OPENID_URL: &str = "https://steamcommunity.com/openid/login";
+pub const STEAM_CLAIMED_ID_PREFIX: &str = "https://steamcommunity.com/openid/id/";
+pub const OPENID_NS: &str = "http://specs.openid.net/auth/2.0";
+pub const OPENID_IDENTIFIER_SELECT: &str = "http://specs.openid.net/auth/2.0/identifier_select";
 
 // Email
 pub const MAX_CONCURRENT_EMAILS: usize = 10;
 
+// Championships
+pub const CHAMPIONSHIP_DELETION_GRACE_DAYS: i64 = 7;
+pub const CHAMPIONSHIP_PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+pub const CHAMPIONSHIP_IMPORT_LIMIT: usize = 4 * 1024 * 1024;
+
 // F1 Service
 pub const BUFFER_SIZE: usize = 1460;
 pub const SOCKET_HOST: IpAddr = IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0));
 pub const SOCKET_TIMEOUT: Duration = Duration::from_secs(15 * 60);
 pub const GENERAL_INTERVAL: Duration = Duration::from_millis(700);
 pub const TELEMETRY_INTERVAL: Duration = Duration::from_millis(100);
+pub const GAP_SAMPLE_DISTANCE: f32 = 50.0;
+pub const INCIDENT_QUEUE_SIZE: usize = 256;
 
 // Session
 pub const HISTORY_INTERVAL: Duration = Duration::from_secs(1);
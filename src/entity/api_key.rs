@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use deadpool_postgres::tokio_postgres::Row;
+use postgres_derive::{FromSql, ToSql};
+use serde::{Deserialize, Serialize};
+
+/// Shared reference to an ApiKey
+pub type SharedApiKey = Arc<ApiKey>;
+
+/// Permission granted to an API key on its championship
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, FromSql, ToSql)]
+#[postgres(name = "api_key_scope")]
+pub enum ApiKeyScope {
+    #[postgres(name = "ReadResults")]
+    ReadResults,
+    #[postgres(name = "ReadLive")]
+    ReadLive,
+    #[postgres(name = "ManageRaces")]
+    ManageRaces,
+}
+
+/// Represents a long-lived key used by bots and integrations on behalf of a user
+#[derive(Debug, Serialize)]
+pub struct ApiKey {
+    pub id: i32,
+    #[serde(skip_serializing)]
+    pub user_id: i32,
+    pub championship_id: i32,
+    pub name: String,
+    pub prefix: String,
+    #[serde(skip_serializing)]
+    pub key_hash: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    /// Creates an ApiKey from a database row
+    #[inline]
+    pub fn from_row(row: &Row) -> Self {
+        ApiKey {
+            id: row.get(0),
+            user_id: row.get(1),
+            championship_id: row.get(2),
+            name: row.get(3),
+            prefix: row.get(4),
+            key_hash: row.get(5),
+            scopes: row.get(6),
+            created_at: row.get(7),
+            last_used_at: row.get(8),
+            revoked_at: row.get(9),
+        }
+    }
+
+    /// Checks if the key grants a scope on a championship
+    #[inline]
+    pub fn allows(&self, championship_id: i32, scope: ApiKeyScope) -> bool {
+        self.championship_id == championship_id && self.scopes.contains(&scope)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_allows_its_scopes_on_its_championship() {
+        let api_key = ApiKey {
+            id: 1,
+            user_id: 600000000,
+            championship_id: 700000000,
+            name: "bot".to_owned(),
+            prefix: "0a1b2c3d".to_owned(),
+            key_hash: String::new(),
+            scopes: vec![ApiKeyScope::ReadResults],
+            created_at: Utc::now(),
+            last_used_at: None,
+            revoked_at: None,
+        };
+
+        assert!(api_key.allows(700000000, ApiKeyScope::ReadResults));
+        assert!(!api_key.allows(700000000, ApiKeyScope::ManageRaces));
+        assert!(!api_key.allows(700000001, ApiKeyScope::ReadResults));
+    }
+}
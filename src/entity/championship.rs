@@ -6,13 +6,14 @@ use postgres_derive::{FromSql, ToSql};
 use serde::{Deserialize, Serialize};
 use tokio_stream::StreamExt;
 
-use crate::error::AppResult;
+use crate::{error::AppResult, structs::protos::SessionResult};
 
 /// Shared reference to a User
 pub type SharedChampionship = Arc<Championship>;
+pub type SharedStandings = Arc<Vec<Standing>>;
 
 /// Championship roles
-#[derive(Debug, Default, Serialize, Deserialize, FromSql, ToSql, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, FromSql, ToSql, PartialEq)]
 #[postgres(name = "championship_role")]
 pub enum ChampionshipRole {
     #[default]
@@ -22,6 +23,9 @@ pub enum ChampionshipRole {
     Engineer,
     #[postgres(name = "Admin")]
     Admin,
+    /// Can follow the telemetry of every team
+    #[postgres(name = "RaceDirector")]
+    RaceDirector,
 }
 
 /// Championship categories
@@ -34,9 +38,125 @@ pub enum Category {
     F2,
 }
 
-pub struct ChampionshipRelation {
+/// Status of a championship invitation
+#[derive(Debug, Serialize, FromSql, ToSql, PartialEq)]
+#[postgres(name = "invitation_status")]
+pub enum InvitationStatus {
+    #[postgres(name = "Pending")]
+    Pending,
+    #[postgres(name = "Accepted")]
+    Accepted,
+    #[postgres(name = "Declined")]
+    Declined,
+}
+
+/// Represents an invitation to join a championship
+#[derive(Debug, Serialize)]
+pub struct ChampionshipInvitation {
+    pub id: i32,
+    pub championship_id: i32,
+    pub email: String,
     pub role: ChampionshipRole,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub team_id: Option<i16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invited_by: Option<i32>,
+    pub status: InvitationStatus,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub responded_at: Option<DateTime<Utc>>,
+    pub championship_name: String,
+}
+
+impl ChampionshipInvitation {
+    /// Creates a ChampionshipInvitation from a database row joined with the championship name
+    #[inline]
+    pub fn from_row(row: &Row) -> Self {
+        ChampionshipInvitation {
+            id: row.get(0),
+            championship_id: row.get(1),
+            email: row.get(2),
+            role: row.get(3),
+            team_id: row.get(4),
+            invited_by: row.get(5),
+            status: row.get(6),
+            expires_at: row.get(7),
+            created_at: row.get(8),
+            responded_at: row.get(9),
+            championship_name: row.get(10),
+        }
+    }
+
+    /// Checks if the invitation can still be answered
+    #[inline]
+    pub fn is_open(&self) -> bool {
+        self.status == InvitationStatus::Pending && self.expires_at > Utc::now()
+    }
+}
+
+/// Championship visibility
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, FromSql, ToSql, PartialEq)]
+#[postgres(name = "championship_visibility")]
+pub enum Visibility {
+    /// Listed in the search and open to join requests
+    #[postgres(name = "Public")]
+    Public,
+    /// Reachable by id and open to join requests, but not listed
+    #[postgres(name = "Unlisted")]
+    Unlisted,
+    /// Only visible to its members
+    #[default]
+    #[postgres(name = "Private")]
+    Private,
+}
+
+/// Status of a request to join a championship
+#[derive(Debug, Serialize, FromSql, ToSql, PartialEq)]
+#[postgres(name = "join_request_status")]
+pub enum JoinRequestStatus {
+    #[postgres(name = "Pending")]
+    Pending,
+    #[postgres(name = "Approved")]
+    Approved,
+    #[postgres(name = "Rejected")]
+    Rejected,
+}
+
+/// Represents a request of a user to join a championship
+#[derive(Debug, Serialize)]
+pub struct JoinRequest {
+    pub id: i32,
+    pub championship_id: i32,
+    pub user_id: i32,
+    pub status: JoinRequestStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl JoinRequest {
+    /// Creates a JoinRequest from a database row
+    #[inline]
+    pub fn from_row(row: &Row) -> Self {
+        JoinRequest {
+            id: row.get(0),
+            championship_id: row.get(1),
+            user_id: row.get(2),
+            status: row.get(3),
+            resolved_by: row.get(4),
+            created_at: row.get(5),
+            resolved_at: row.get(6),
+        }
+    }
+}
+
+pub struct ChampionshipRelation {
+    pub role: ChampionshipRole,
+    /// Teams assigned to an engineer
+    pub team_ids: Vec<i16>,
 }
 
 /// Represents a championship
@@ -51,6 +171,10 @@ pub struct Championship {
     pub created_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<DateTime<Utc>>,
+    pub visibility: Visibility,
+    /// Set while the championship waits to be purged
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl Championship {
@@ -65,6 +189,8 @@ impl Championship {
             category: row.get(4),
             created_at: row.get(5),
             updated_at: row.get(6),
+            visibility: row.get(7),
+            deleted_at: row.get(8),
         }
     }
 
@@ -87,3 +213,113 @@ impl Championship {
         Ok(championships)
     }
 }
+
+/// Status of a championship ownership transfer
+#[derive(Debug, Serialize, FromSql, ToSql, PartialEq)]
+#[postgres(name = "owner_transfer_status")]
+pub enum OwnerTransferStatus {
+    #[postgres(name = "Pending")]
+    Pending,
+    #[postgres(name = "Accepted")]
+    Accepted,
+    #[postgres(name = "Declined")]
+    Declined,
+    /// Replaced by an admin override before being answered
+    #[postgres(name = "Cancelled")]
+    Cancelled,
+    /// Applied by an admin without confirmation
+    #[postgres(name = "Forced")]
+    Forced,
+}
+
+/// Represents a change of owner of a championship, kept as audit record
+#[derive(Debug, Serialize)]
+pub struct OwnerTransfer {
+    pub id: i32,
+    pub championship_id: i32,
+    pub from_user_id: Option<i32>,
+    pub to_user_id: Option<i32>,
+    pub status: OwnerTransferStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initiated_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl OwnerTransfer {
+    /// Creates an OwnerTransfer from a database row
+    #[inline]
+    pub fn from_row(row: &Row) -> Self {
+        OwnerTransfer {
+            id: row.get(0),
+            championship_id: row.get(1),
+            from_user_id: row.get(2),
+            to_user_id: row.get(3),
+            status: row.get(4),
+            initiated_by: row.get(5),
+            created_at: row.get(6),
+            resolved_at: row.get(7),
+        }
+    }
+}
+
+/// Drivers standing of a championship computed from the stored race results
+#[derive(Debug, Serialize)]
+pub struct Standing {
+    pub steam_name: String,
+    pub points: u32,
+    pub races: u32,
+    pub wins: u32,
+    pub podiums: u32,
+}
+
+impl Standing {
+    /// Builds the sorted standings from the results of the race sessions.
+    pub fn from_results<'a>(results: impl Iterator<Item = &'a SessionResult>) -> Vec<Standing> {
+        let mut standings: Vec<Standing> = Vec::new();
+
+        for result in results {
+            for (steam_name, classification) in &result.classification {
+                // 0 = invalid, 1 = inactive
+                if classification.result_status.unwrap_or_default() < 2 {
+                    continue;
+                }
+
+                let index = match standings.iter().position(|s| &s.steam_name == steam_name) {
+                    Some(index) => index,
+                    None => {
+                        standings.push(Standing {
+                            steam_name: steam_name.clone(),
+                            points: 0,
+                            races: 0,
+                            wins: 0,
+                            podiums: 0,
+                        });
+
+                        standings.len() - 1
+                    }
+                };
+
+                let standing = &mut standings[index];
+                let position = classification.position.unwrap_or_default();
+
+                standing.races += 1;
+                standing.points += classification.points.unwrap_or_default();
+
+                if classification.result_status == Some(3) {
+                    if position == 1 {
+                        standing.wins += 1;
+                    }
+
+                    if (1..=3).contains(&position) {
+                        standing.podiums += 1;
+                    }
+                }
+            }
+        }
+
+        standings.sort_by(|a, b| b.points.cmp(&a.points).then(b.wins.cmp(&a.wins)));
+        standings
+    }
+}
@@ -2,16 +2,23 @@ use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
 use deadpool_postgres::tokio_postgres::Row;
+use postgres_derive::{FromSql, ToSql};
+use serde::Serialize;
+
+use crate::structs::protos::SessionResult;
 
 pub type SharedDriver = Arc<Driver>;
+pub type SharedDriverStats = Arc<DriverStats>;
 
 /// Represents a driver in the championship
-#[allow(unused)]
+#[derive(Debug, Serialize)]
 pub struct Driver {
     pub steam_name: String,
     pub nationality: i16,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub user_id: Option<i32>,
     pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<DateTime<Utc>>,
 }
 
@@ -34,3 +41,190 @@ impl Driver {
         Arc::new(Driver::from_row(row))
     }
 }
+
+/// Career statistics of a driver computed from the stored race results
+#[derive(Debug, Default, Serialize)]
+pub struct DriverStats {
+    pub starts: u32,
+    pub wins: u32,
+    pub podiums: u32,
+    pub poles: u32,
+    pub fastest_laps: u32,
+    pub dnfs: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub average_finish: Option<f32>,
+    pub penalties: u32,
+    pub penalties_time: u32,
+    #[serde(skip_serializing)]
+    finishes: u32,
+    #[serde(skip_serializing)]
+    finish_positions: u32,
+}
+
+impl DriverStats {
+    /// Adds the result of a race session to the statistics of `steam_name`.
+    pub fn add_result(&mut self, steam_name: &str, result: &SessionResult) {
+        let Some(classification) = result.classification.get(steam_name) else {
+            return;
+        };
+
+        // 0 = invalid, 1 = inactive
+        let result_status = classification.result_status.unwrap_or_default();
+        if result_status < 2 {
+            return;
+        }
+
+        let position = classification.position.unwrap_or_default();
+
+        self.starts += 1;
+        self.penalties += classification.num_penalties.unwrap_or_default();
+        self.penalties_time += classification.penalties_time.unwrap_or_default();
+
+        if classification.grid_position == Some(1) {
+            self.poles += 1;
+        }
+
+        match result_status {
+            // Finished
+            3 => {
+                self.finishes += 1;
+                self.finish_positions += position;
+
+                if position == 1 {
+                    self.wins += 1;
+                }
+
+                if (1..=3).contains(&position) {
+                    self.podiums += 1;
+                }
+
+                self.average_finish = Some(self.finish_positions as f32 / self.finishes as f32);
+            }
+
+            // Did not finish or retired
+            4 | 7 => self.dnfs += 1,
+
+            _ => {}
+        }
+
+        let best_lap_time = classification.best_lap_time.unwrap_or_default();
+
+        let fastest_lap = result
+            .classification
+            .values()
+            .filter_map(|other| other.best_lap_time)
+            .filter(|&lap_time| lap_time > 0)
+            .min();
+
+        if best_lap_time > 0 && fastest_lap == Some(best_lap_time) {
+            self.fastest_laps += 1;
+        }
+    }
+}
+
+/// Status of a driver transfer request
+#[derive(Debug, Serialize, FromSql, ToSql, PartialEq)]
+#[postgres(name = "transfer_status")]
+pub enum TransferStatus {
+    #[postgres(name = "Pending")]
+    Pending,
+    #[postgres(name = "Approved")]
+    Approved,
+    #[postgres(name = "Rejected")]
+    Rejected,
+}
+
+/// Team and number a driver is registered with in a championship
+#[derive(Debug, Serialize)]
+pub struct ChampionshipDriver {
+    pub steam_name: String,
+    pub team_id: i16,
+    pub number: i16,
+}
+
+/// Represents a team or number change of a driver within a championship
+#[derive(Debug, Serialize)]
+pub struct DriverTransfer {
+    pub id: i32,
+    pub steam_name: String,
+    pub championship_id: i32,
+    pub from_team_id: i16,
+    pub from_number: i16,
+    pub to_team_id: i16,
+    pub to_number: i16,
+    pub status: TransferStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl DriverTransfer {
+    /// Creates a DriverTransfer from a database row
+    #[inline]
+    pub fn from_row(row: &Row) -> Self {
+        DriverTransfer {
+            id: row.get(0),
+            steam_name: row.get(1),
+            championship_id: row.get(2),
+            from_team_id: row.get(3),
+            from_number: row.get(4),
+            to_team_id: row.get(5),
+            to_number: row.get(6),
+            status: row.get(7),
+            resolved_by: row.get(8),
+            created_at: row.get(9),
+            resolved_at: row.get(10),
+        }
+    }
+}
+
+/// Status of a steam name claim
+#[derive(Debug, Serialize, FromSql, ToSql, PartialEq)]
+#[postgres(name = "claim_status")]
+pub enum ClaimStatus {
+    #[postgres(name = "Pending")]
+    Pending,
+    #[postgres(name = "Verified")]
+    Verified,
+    #[postgres(name = "Approved")]
+    Approved,
+    #[postgres(name = "Rejected")]
+    Rejected,
+}
+
+/// Represents a request of a user to link a steam name to their account
+#[derive(Debug, Serialize)]
+pub struct DriverClaim {
+    pub id: i32,
+    pub steam_name: String,
+    pub user_id: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub championship_id: Option<i32>,
+    pub code: String,
+    pub status: ClaimStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl DriverClaim {
+    /// Creates a DriverClaim from a database row
+    #[inline]
+    pub fn from_row(row: &Row) -> Self {
+        DriverClaim {
+            id: row.get(0),
+            steam_name: row.get(1),
+            user_id: row.get(2),
+            championship_id: row.get(3),
+            code: row.get(4),
+            status: row.get(5),
+            resolved_by: row.get(6),
+            created_at: row.get(7),
+            resolved_at: row.get(8),
+        }
+    }
+}
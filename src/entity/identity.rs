@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use deadpool_postgres::tokio_postgres::Row;
+use serde::Serialize;
+
+use super::Provider;
+
+/// External login method linked to a user, a user has at most one per provider
+#[derive(Debug, Serialize)]
+pub struct UserIdentity {
+    pub provider: Provider,
+    pub provider_user_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl UserIdentity {
+    /// Creates a UserIdentity from a database row
+    #[inline]
+    pub fn from_row(row: &Row) -> Self {
+        UserIdentity {
+            provider: row.get(0),
+            provider_user_id: row.get(1),
+            created_at: row.get(2),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use chrono::{DateTime, Utc};
+use deadpool_postgres::tokio_postgres::Row;
+use postgres_derive::{FromSql, ToSql};
+use serde::Serialize;
+
+/// Kind of event an incident was recorded from
+#[derive(Debug, Clone, Copy, Serialize, FromSql, ToSql, PartialEq)]
+#[postgres(name = "incident_type")]
+pub enum IncidentType {
+    #[postgres(name = "Collision")]
+    Collision,
+    #[postgres(name = "Penalty")]
+    Penalty,
+}
+
+/// Status of the steward review of an incident
+#[derive(Debug, Serialize, FromSql, ToSql, PartialEq)]
+#[postgres(name = "incident_status")]
+pub enum IncidentStatus {
+    #[postgres(name = "Open")]
+    Open,
+    #[postgres(name = "PenaltyApplied")]
+    PenaltyApplied,
+    #[postgres(name = "Dismissed")]
+    Dismissed,
+}
+
+/// Represents a collision or penalty recorded during a race session
+#[derive(Debug, Serialize)]
+pub struct Incident {
+    pub id: i32,
+    pub race_id: i32,
+    pub session_type: i16,
+    pub incident_type: IncidentType,
+    pub lap: i16,
+    pub session_time: f32,
+    pub steam_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub other_steam_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub penalty_type: Option<i16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub infringement_type: Option<i16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub game_penalty_time: Option<i16>,
+    pub status: IncidentStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub penalty_seconds: Option<i16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl Incident {
+    /// Creates an Incident from a database row
+    #[inline]
+    pub fn from_row(row: &Row) -> Self {
+        Incident {
+            id: row.get(0),
+            race_id: row.get(1),
+            session_type: row.get(2),
+            incident_type: row.get(3),
+            lap: row.get(4),
+            session_time: row.get(5),
+            steam_name: row.get(6),
+            other_steam_name: row.get(7),
+            penalty_type: row.get(8),
+            infringement_type: row.get(9),
+            game_penalty_time: row.get(10),
+            status: row.get(11),
+            penalty_seconds: row.get(12),
+            resolved_by: row.get(13),
+            created_at: row.get(14),
+            resolved_at: row.get(15),
+        }
+    }
+
+    /// Checks if the incident still waits for a steward decision
+    #[inline]
+    pub fn is_open(&self) -> bool {
+        self.status == IncidentStatus::Open
+    }
+}
+
+/// Represents a steward comment on an incident
+#[derive(Debug, Serialize)]
+pub struct IncidentComment {
+    pub id: i32,
+    pub incident_id: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<i32>,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl IncidentComment {
+    /// Creates an IncidentComment from a database row
+    #[inline]
+    pub fn from_row(row: &Row) -> Self {
+        IncidentComment {
+            id: row.get(0),
+            incident_id: row.get(1),
+            user_id: row.get(2),
+            body: row.get(3),
+            created_at: row.get(4),
+        }
+    }
+}
@@ -1,13 +1,23 @@
+pub use api_key::*;
 pub use championship::*;
 pub use driver::*;
+pub use identity::*;
+pub use incident::*;
 #[allow(unused)]
 pub use race::*;
 #[allow(unused)]
 pub use result::*;
+pub use session::*;
+pub use two_factor::*;
 pub use user::*;
 
+mod api_key;
 mod championship;
 mod driver;
+mod identity;
+mod incident;
 mod race;
 mod result;
+mod session;
+mod two_factor;
 mod user;
@@ -10,13 +10,13 @@ pub type SharedRace = Arc<Race>;
 /// Represents a race in a championship
 #[derive(Debug, Serialize)]
 pub struct Race {
-    id: i32,
-    championship_id: i32,
-    track_id: i16,
-    date: DateTime<Utc>,
-    created_at: DateTime<Utc>,
+    pub id: i32,
+    pub championship_id: i32,
+    pub track_id: i16,
+    pub date: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    updated_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl Race {
@@ -1,5 +1,7 @@
 use chrono::{DateTime, Utc};
 use deadpool_postgres::tokio_postgres::Row;
+use postgres_derive::{FromSql, ToSql};
+use serde::Serialize;
 
 /// Represents a result for a race session
 #[allow(unused)]
@@ -23,3 +25,57 @@ impl Result {
         }
     }
 }
+
+/// Kind of correction made to a stored classification
+#[derive(Debug, Clone, Copy, Serialize, FromSql, ToSql, PartialEq)]
+#[postgres(name = "result_amendment_type")]
+pub enum AmendmentType {
+    #[postgres(name = "TimePenalty")]
+    TimePenalty,
+    #[postgres(name = "Disqualification")]
+    Disqualification,
+    #[postgres(name = "Reorder")]
+    Reorder,
+}
+
+/// Represents a correction made to a race session result after it was stored
+#[derive(Debug, Serialize)]
+pub struct ResultAmendment {
+    pub id: i32,
+    pub race_id: i32,
+    pub session_type: i16,
+    pub amendment_type: AmendmentType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub steam_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seconds: Option<i16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub classification_order: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub incident_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ResultAmendment {
+    /// Creates a ResultAmendment from a database row
+    #[inline]
+    pub fn from_row(row: &Row) -> Self {
+        ResultAmendment {
+            id: row.get(0),
+            race_id: row.get(1),
+            session_type: row.get(2),
+            amendment_type: row.get(3),
+            steam_name: row.get(4),
+            seconds: row.get(5),
+            classification_order: row.get(6),
+            reason: row.get(7),
+            incident_id: row.get(8),
+            created_by: row.get(9),
+            created_at: row.get(10),
+        }
+    }
+}
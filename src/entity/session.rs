@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use deadpool_postgres::tokio_postgres::Row;
+use serde::Serialize;
+
+/// Represents a device holding a refresh token of a user
+#[derive(Debug, Serialize)]
+pub struct UserSession {
+    pub id: i32,
+    #[serde(skip_serializing)]
+    pub user_id: i32,
+    #[serde(skip_serializing)]
+    pub fingerprint: String,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl UserSession {
+    /// Creates a UserSession from a database row
+    #[inline]
+    pub fn from_row(row: &Row) -> Self {
+        UserSession {
+            id: row.get(0),
+            user_id: row.get(1),
+            fingerprint: row.get(2),
+            token_hash: row.get(3),
+            device: row.get(4),
+            ip: row.get(5),
+            created_at: row.get(6),
+            last_used_at: row.get(7),
+            expires_at: row.get(8),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use deadpool_postgres::tokio_postgres::Row;
+
+/// TOTP enrolment of a user, pending until `enabled_at` is set
+#[derive(Debug)]
+pub struct UserTwoFactor {
+    pub user_id: i32,
+    pub secret: String,
+    pub last_step: Option<i64>,
+    pub enabled_at: Option<DateTime<Utc>>,
+}
+
+/// Hashed one-time recovery code of a user
+#[derive(Debug)]
+pub struct RecoveryCode {
+    pub id: i32,
+    pub code_hash: String,
+}
+
+impl UserTwoFactor {
+    /// Creates a UserTwoFactor from a database row
+    #[inline]
+    pub fn from_row(row: &Row) -> Self {
+        UserTwoFactor {
+            user_id: row.get(0),
+            secret: row.get(1),
+            last_step: row.get(2),
+            enabled_at: row.get(3),
+        }
+    }
+
+    #[inline]
+    pub fn enabled(&self) -> bool {
+        self.enabled_at.is_some()
+    }
+}
+
+impl RecoveryCode {
+    /// Creates a RecoveryCode from a database row
+    #[inline]
+    pub fn from_row(row: &Row) -> Self {
+        RecoveryCode {
+            id: row.get(0),
+            code_hash: row.get(1),
+        }
+    }
+}
@@ -12,13 +12,30 @@ use crate::error::{AppError, AppResult, CommonError};
 pub type SharedUser = Arc<User>;
 
 /// User authentication provider
-#[derive(Debug, Serialize, Deserialize, PartialEq, FromSql, ToSql)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, FromSql, ToSql)]
 #[postgres(name = "user_provider")]
+#[serde(rename_all = "lowercase")]
 pub enum Provider {
     #[postgres(name = "Local")]
     Local,
     #[postgres(name = "Discord")]
     Discord,
+    #[postgres(name = "Google")]
+    Google,
+    #[postgres(name = "Steam")]
+    Steam,
+}
+
+impl Provider {
+    /// Name of the provider in URLs
+    pub const fn slug(&self) -> &'static str {
+        match self {
+            Provider::Local => "local",
+            Provider::Discord => "discord",
+            Provider::Google => "google",
+            Provider::Steam => "steam",
+        }
+    }
 }
 
 /// User role in the system
@@ -52,6 +69,8 @@ pub struct User {
     pub created_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing)]
+    pub token_version: i32,
 }
 
 impl User {
@@ -70,6 +89,7 @@ impl User {
             active: row.get(8),
             created_at: row.get(9),
             updated_at: row.get(10),
+            token_version: row.get(11),
         }
     }
 
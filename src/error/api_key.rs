@@ -0,0 +1,67 @@
+use ntex::{
+    http::{
+        header::{HeaderValue, CONTENT_TYPE},
+        StatusCode,
+    },
+    web::{error::WebResponseError, HttpRequest, HttpResponse},
+};
+
+use super::AppError;
+
+#[derive(Debug)]
+pub enum ApiKeyError {
+    InvalidKey,
+    NotFound,
+    MissingScope,
+    NotMember,
+    LimitReached,
+}
+
+impl ApiKeyError {
+    pub const fn status_code(&self) -> StatusCode {
+        match self {
+            ApiKeyError::InvalidKey => StatusCode::UNAUTHORIZED,
+            ApiKeyError::NotFound => StatusCode::NOT_FOUND,
+            ApiKeyError::MissingScope => StatusCode::FORBIDDEN,
+            ApiKeyError::NotMember => StatusCode::FORBIDDEN,
+            ApiKeyError::LimitReached => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    pub const fn error_message(&self) -> &'static str {
+        match self {
+            ApiKeyError::InvalidKey => "Invalid API key",
+            ApiKeyError::NotFound => "API key not found",
+            ApiKeyError::MissingScope => "API key not allowed for this request",
+            ApiKeyError::NotMember => "Not allowed to create this key for the championship",
+            ApiKeyError::LimitReached => "API key limit reached",
+        }
+    }
+}
+
+impl std::error::Error for ApiKeyError {}
+
+impl std::fmt::Display for ApiKeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error_message())
+    }
+}
+
+impl From<ApiKeyError> for AppError {
+    #[inline]
+    fn from(e: ApiKeyError) -> Self {
+        AppError::ApiKey(e)
+    }
+}
+
+// Added for middlewares
+impl WebResponseError for ApiKeyError {
+    fn error_response(&self, _: &HttpRequest) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .header(
+                CONTENT_TYPE,
+                HeaderValue::from_static("text/plain; charset=utf-8"),
+            )
+            .body(self.error_message())
+    }
+}
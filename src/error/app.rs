@@ -10,8 +10,9 @@ use ntex::{
 use tracing::error;
 
 use super::{
-    driver::DriverError, user::UserError, ChampionshipError, CommonError, F1ServiceError,
-    FirewallError, TokenError,
+    driver::DriverError, user::UserError, ApiKeyError, ChampionshipError, CommonError,
+    F1ServiceError, FirewallError, IncidentError, OAuthError, ResultError, TokenError,
+    TwoFactorError,
 };
 
 pub type AppResult<T> = Result<T, AppError>;
@@ -22,6 +23,11 @@ pub enum AppError {
     User(UserError),
     Championship(ChampionshipError),
     Driver(DriverError),
+    Incident(IncidentError),
+    Result(ResultError),
+    ApiKey(ApiKeyError),
+    TwoFactor(TwoFactorError),
+    OAuth(OAuthError),
     Token(TokenError),
     Common(CommonError),
     F1(F1ServiceError),
@@ -60,6 +66,13 @@ impl From<reqwest::Error> for AppError {
     }
 }
 
+impl From<serde_json::Error> for AppError {
+    fn from(value: serde_json::Error) -> Self {
+        error!("Serde Json Error: {}", value);
+        AppError::Common(CommonError::InternalServerError)
+    }
+}
+
 impl From<sailfish::RenderError> for AppError {
     fn from(value: sailfish::RenderError) -> Self {
         error!("Sailfish Error: {:?}", value);
@@ -73,6 +86,11 @@ impl AppError {
             AppError::User(e) => e.status_code(),
             AppError::Championship(e) => e.status_code(),
             AppError::Driver(e) => e.status_code(),
+            AppError::Incident(e) => e.status_code(),
+            AppError::Result(e) => e.status_code(),
+            AppError::ApiKey(e) => e.status_code(),
+            AppError::TwoFactor(e) => e.status_code(),
+            AppError::OAuth(e) => e.status_code(),
             AppError::Token(e) => e.status_code(),
             AppError::Common(e) => e.status_code(),
             AppError::F1(e) => e.status_code(),
@@ -89,6 +107,11 @@ impl AppError {
             AppError::User(e) => e.error_message(),
             AppError::Championship(e) => e.error_message(),
             AppError::Driver(e) => e.error_message(),
+            AppError::Incident(e) => e.error_message(),
+            AppError::Result(e) => e.error_message(),
+            AppError::ApiKey(e) => e.error_message(),
+            AppError::TwoFactor(e) => e.error_message(),
+            AppError::OAuth(e) => e.error_message(),
             AppError::Token(e) => e.error_message(),
             AppError::Common(e) => e.error_message(),
             AppError::F1(e) => e.error_message(),
@@ -14,10 +14,22 @@ pub enum ChampionshipError {
     LimitReached,
     NotOwner,
     CannotRemoveOwner,
+    CannotModifyOwner,
     IntervalNotReached,
     NoPortsAvailable,
     InvalidTeamId,
     NotEngineer,
+    NotAdmin,
+    TransferNotFound,
+    AlreadyMember,
+    InvitationNotFound,
+    InvitationAlreadyPending,
+    InvitationExpired,
+    InvitationNotForUser,
+    JoinRequestNotFound,
+    JoinRequestAlreadyPending,
+    AlreadyOwner,
+    OwnerTransferNotFound,
 }
 
 impl std::error::Error for ChampionshipError {}
@@ -36,10 +48,22 @@ impl ChampionshipError {
             ChampionshipError::LimitReached => StatusCode::BAD_REQUEST,
             ChampionshipError::NotOwner => StatusCode::UNAUTHORIZED,
             ChampionshipError::CannotRemoveOwner => StatusCode::BAD_REQUEST,
+            ChampionshipError::CannotModifyOwner => StatusCode::BAD_REQUEST,
             ChampionshipError::IntervalNotReached => StatusCode::BAD_REQUEST,
             ChampionshipError::NoPortsAvailable => StatusCode::SERVICE_UNAVAILABLE,
             ChampionshipError::InvalidTeamId => StatusCode::BAD_REQUEST,
             ChampionshipError::NotEngineer => StatusCode::UNAUTHORIZED,
+            ChampionshipError::NotAdmin => StatusCode::UNAUTHORIZED,
+            ChampionshipError::TransferNotFound => StatusCode::NOT_FOUND,
+            ChampionshipError::AlreadyMember => StatusCode::CONFLICT,
+            ChampionshipError::InvitationNotFound => StatusCode::NOT_FOUND,
+            ChampionshipError::InvitationAlreadyPending => StatusCode::CONFLICT,
+            ChampionshipError::InvitationExpired => StatusCode::GONE,
+            ChampionshipError::InvitationNotForUser => StatusCode::FORBIDDEN,
+            ChampionshipError::JoinRequestNotFound => StatusCode::NOT_FOUND,
+            ChampionshipError::JoinRequestAlreadyPending => StatusCode::CONFLICT,
+            ChampionshipError::AlreadyOwner => StatusCode::BAD_REQUEST,
+            ChampionshipError::OwnerTransferNotFound => StatusCode::NOT_FOUND,
         }
     }
 
@@ -50,10 +74,22 @@ impl ChampionshipError {
             ChampionshipError::LimitReached => "Championship limit reached",
             ChampionshipError::NotOwner => "Not Owner of Championship",
             ChampionshipError::CannotRemoveOwner => "Cannot remove owner of Championship",
+            ChampionshipError::CannotModifyOwner => "Cannot modify owner of Championship",
             ChampionshipError::IntervalNotReached => "Interval update time not reached",
             ChampionshipError::NoPortsAvailable => "No ports available",
             ChampionshipError::InvalidTeamId => "Invalid Team Id",
             ChampionshipError::NotEngineer => "Not an engineer",
+            ChampionshipError::NotAdmin => "Not an admin of Championship",
+            ChampionshipError::TransferNotFound => "Transfer not found",
+            ChampionshipError::AlreadyMember => "User already in Championship",
+            ChampionshipError::InvitationNotFound => "Invitation not found",
+            ChampionshipError::InvitationAlreadyPending => "Invitation already pending",
+            ChampionshipError::InvitationExpired => "Invitation expired",
+            ChampionshipError::InvitationNotForUser => "Invitation was sent to another email",
+            ChampionshipError::JoinRequestNotFound => "Join request not found",
+            ChampionshipError::JoinRequestAlreadyPending => "Join request already pending",
+            ChampionshipError::AlreadyOwner => "User already owns the Championship",
+            ChampionshipError::OwnerTransferNotFound => "Ownership transfer not found",
         }
     }
 }
@@ -11,18 +11,33 @@ use super::AppError;
 #[derive(Debug)]
 pub enum DriverError {
     AlreadyExists,
+    NotFound,
+    AlreadyLinked,
+    UserAlreadyLinked,
+    ClaimNotFound,
+    ClaimAlreadyPending,
 }
 
 impl DriverError {
     pub const fn status_code(&self) -> StatusCode {
         match self {
             DriverError::AlreadyExists => StatusCode::CONFLICT,
+            DriverError::NotFound => StatusCode::NOT_FOUND,
+            DriverError::AlreadyLinked => StatusCode::CONFLICT,
+            DriverError::UserAlreadyLinked => StatusCode::CONFLICT,
+            DriverError::ClaimNotFound => StatusCode::NOT_FOUND,
+            DriverError::ClaimAlreadyPending => StatusCode::CONFLICT,
         }
     }
 
     pub const fn error_message(&self) -> &'static str {
         match self {
             DriverError::AlreadyExists => "Driver already exists",
+            DriverError::NotFound => "Driver not found",
+            DriverError::AlreadyLinked => "Driver already linked to a user",
+            DriverError::UserAlreadyLinked => "User already has a linked driver",
+            DriverError::ClaimNotFound => "Claim not found",
+            DriverError::ClaimAlreadyPending => "Claim already pending",
         }
     }
 }
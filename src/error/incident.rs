@@ -0,0 +1,61 @@
+use ntex::{
+    http::{
+        header::{HeaderValue, CONTENT_TYPE},
+        StatusCode,
+    },
+    web::{error::WebResponseError, HttpRequest, HttpResponse},
+};
+
+use super::AppError;
+
+#[derive(Debug)]
+pub enum IncidentError {
+    NotFound,
+    AlreadyResolved,
+    NotSteward,
+}
+
+impl IncidentError {
+    pub const fn status_code(&self) -> StatusCode {
+        match self {
+            IncidentError::NotFound => StatusCode::NOT_FOUND,
+            IncidentError::AlreadyResolved => StatusCode::CONFLICT,
+            IncidentError::NotSteward => StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    pub const fn error_message(&self) -> &'static str {
+        match self {
+            IncidentError::NotFound => "Incident not found",
+            IncidentError::AlreadyResolved => "Incident already resolved",
+            IncidentError::NotSteward => "Not a steward of Championship",
+        }
+    }
+}
+
+impl std::error::Error for IncidentError {}
+
+impl std::fmt::Display for IncidentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error_message())
+    }
+}
+
+impl From<IncidentError> for AppError {
+    #[inline]
+    fn from(e: IncidentError) -> Self {
+        AppError::Incident(e)
+    }
+}
+
+// Added for middlewares
+impl WebResponseError for IncidentError {
+    fn error_response(&self, _: &HttpRequest) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .header(
+                CONTENT_TYPE,
+                HeaderValue::from_static("text/plain; charset=utf-8"),
+            )
+            .body(self.error_message())
+    }
+}
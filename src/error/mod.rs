@@ -1,17 +1,27 @@
+pub(crate) use api_key::*;
 pub(crate) use app::*;
 pub(crate) use championship::*;
 pub(crate) use common::*;
 pub(crate) use driver::*;
 pub(crate) use f1::*;
 pub(crate) use firewall::*;
+pub(crate) use incident::*;
+pub(crate) use oauth::*;
+pub(crate) use result::*;
 pub(crate) use token::*;
+pub(crate) use two_factor::*;
 pub(crate) use user::*;
 
+mod api_key;
 mod app;
 mod championship;
 mod common;
 mod driver;
 mod f1;
 mod firewall;
+mod incident;
+mod oauth;
+mod result;
 mod token;
+mod two_factor;
 mod user;
@@ -0,0 +1,67 @@
+use ntex::{
+    http::{
+        header::{HeaderValue, CONTENT_TYPE},
+        StatusCode,
+    },
+    web::{error::WebResponseError, HttpRequest, HttpResponse},
+};
+
+use super::AppError;
+
+#[derive(Debug)]
+pub enum OAuthError {
+    ProviderDisabled,
+    InvalidState,
+    ExchangeFailed,
+    AccountNotLinked,
+    InvalidExchangeCode,
+}
+
+impl OAuthError {
+    pub const fn status_code(&self) -> StatusCode {
+        match self {
+            OAuthError::ProviderDisabled => StatusCode::NOT_FOUND,
+            OAuthError::InvalidState => StatusCode::BAD_REQUEST,
+            OAuthError::ExchangeFailed => StatusCode::BAD_GATEWAY,
+            OAuthError::AccountNotLinked => StatusCode::NOT_FOUND,
+            OAuthError::InvalidExchangeCode => StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    pub const fn error_message(&self) -> &'static str {
+        match self {
+            OAuthError::ProviderDisabled => "Login provider not available",
+            OAuthError::InvalidState => "Invalid or expired login attempt",
+            OAuthError::ExchangeFailed => "Login provider rejected the authorization",
+            OAuthError::AccountNotLinked => "Account not linked to any user",
+            OAuthError::InvalidExchangeCode => "Invalid or expired login code",
+        }
+    }
+}
+
+impl std::error::Error for OAuthError {}
+
+impl std::fmt::Display for OAuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error_message())
+    }
+}
+
+impl From<OAuthError> for AppError {
+    #[inline]
+    fn from(e: OAuthError) -> Self {
+        AppError::OAuth(e)
+    }
+}
+
+// Added for middlewares
+impl WebResponseError for OAuthError {
+    fn error_response(&self, _: &HttpRequest) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .header(
+                CONTENT_TYPE,
+                HeaderValue::from_static("text/plain; charset=utf-8"),
+            )
+            .body(self.error_message())
+    }
+}
@@ -0,0 +1,61 @@
+use ntex::{
+    http::{
+        header::{HeaderValue, CONTENT_TYPE},
+        StatusCode,
+    },
+    web::{error::WebResponseError, HttpRequest, HttpResponse},
+};
+
+use super::AppError;
+
+#[derive(Debug)]
+pub enum ResultError {
+    NotFound,
+    DriverNotClassified,
+    InvalidOrder,
+}
+
+impl ResultError {
+    pub const fn status_code(&self) -> StatusCode {
+        match self {
+            ResultError::NotFound => StatusCode::NOT_FOUND,
+            ResultError::DriverNotClassified => StatusCode::BAD_REQUEST,
+            ResultError::InvalidOrder => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    pub const fn error_message(&self) -> &'static str {
+        match self {
+            ResultError::NotFound => "Session result not found",
+            ResultError::DriverNotClassified => "Driver not classified in session",
+            ResultError::InvalidOrder => "Order must contain every classified driver once",
+        }
+    }
+}
+
+impl std::error::Error for ResultError {}
+
+impl std::fmt::Display for ResultError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error_message())
+    }
+}
+
+impl From<ResultError> for AppError {
+    #[inline]
+    fn from(e: ResultError) -> Self {
+        AppError::Result(e)
+    }
+}
+
+// Added for middlewares
+impl WebResponseError for ResultError {
+    fn error_response(&self, _: &HttpRequest) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .header(
+                CONTENT_TYPE,
+                HeaderValue::from_static("text/plain; charset=utf-8"),
+            )
+            .body(self.error_message())
+    }
+}
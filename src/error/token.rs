@@ -14,6 +14,9 @@ pub enum TokenError {
     MissingToken,
     TokenCreationError,
     InvalidTokenPurpose,
+    SessionNotFound,
+    TokenReused,
+    RevokedToken,
 }
 
 impl std::error::Error for TokenError {}
@@ -31,6 +34,9 @@ impl TokenError {
             TokenError::MissingToken => StatusCode::BAD_REQUEST,
             TokenError::TokenCreationError => StatusCode::INTERNAL_SERVER_ERROR,
             TokenError::InvalidTokenPurpose => StatusCode::BAD_REQUEST,
+            TokenError::SessionNotFound => StatusCode::NOT_FOUND,
+            TokenError::TokenReused => StatusCode::UNAUTHORIZED,
+            TokenError::RevokedToken => StatusCode::UNAUTHORIZED,
         }
     }
 
@@ -40,6 +46,9 @@ impl TokenError {
             TokenError::MissingToken => "Missing Bearer token",
             TokenError::TokenCreationError => "Token Validation Error",
             TokenError::InvalidTokenPurpose => "Invalid token type",
+            TokenError::SessionNotFound => "Session not found",
+            TokenError::TokenReused => "Refresh token reuse detected, session revoked",
+            TokenError::RevokedToken => "Token has been revoked",
         }
     }
 }
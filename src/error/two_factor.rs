@@ -0,0 +1,73 @@
+use ntex::{
+    http::{
+        header::{HeaderValue, CONTENT_TYPE},
+        StatusCode,
+    },
+    web::{error::WebResponseError, HttpRequest, HttpResponse},
+};
+
+use super::AppError;
+
+#[derive(Debug)]
+pub enum TwoFactorError {
+    AlreadyEnabled,
+    NotEnabled,
+    NotSetUp,
+    InvalidCode,
+    LocalOnly,
+    TooManyAttempts,
+    SelfReset,
+}
+
+impl TwoFactorError {
+    pub const fn status_code(&self) -> StatusCode {
+        match self {
+            TwoFactorError::AlreadyEnabled => StatusCode::CONFLICT,
+            TwoFactorError::NotEnabled => StatusCode::NOT_FOUND,
+            TwoFactorError::NotSetUp => StatusCode::BAD_REQUEST,
+            TwoFactorError::InvalidCode => StatusCode::UNAUTHORIZED,
+            TwoFactorError::LocalOnly => StatusCode::BAD_REQUEST,
+            TwoFactorError::TooManyAttempts => StatusCode::TOO_MANY_REQUESTS,
+            TwoFactorError::SelfReset => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    pub const fn error_message(&self) -> &'static str {
+        match self {
+            TwoFactorError::AlreadyEnabled => "Two-factor authentication already enabled",
+            TwoFactorError::NotEnabled => "Two-factor authentication not enabled",
+            TwoFactorError::NotSetUp => "Two-factor authentication not set up",
+            TwoFactorError::InvalidCode => "Invalid two-factor code",
+            TwoFactorError::LocalOnly => "Two-factor authentication is only for local accounts",
+            TwoFactorError::TooManyAttempts => "Too many two-factor attempts, try again later",
+            TwoFactorError::SelfReset => "Cannot reset your own two-factor authentication",
+        }
+    }
+}
+
+impl std::error::Error for TwoFactorError {}
+
+impl std::fmt::Display for TwoFactorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error_message())
+    }
+}
+
+impl From<TwoFactorError> for AppError {
+    #[inline]
+    fn from(e: TwoFactorError) -> Self {
+        AppError::TwoFactor(e)
+    }
+}
+
+// Added for middlewares
+impl WebResponseError for TwoFactorError {
+    fn error_response(&self, _: &HttpRequest) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .header(
+                CONTENT_TYPE,
+                HeaderValue::from_static("text/plain; charset=utf-8"),
+            )
+            .body(self.error_message())
+    }
+}
@@ -14,7 +14,7 @@ pub enum UserError {
     NotFound,
     InvalidCredentials,
     NotVerified,
-    DiscordAuth,
+    ExternalAuth,
     Unauthorized,
     AutoDelete,
     AlreadyActive,
@@ -22,6 +22,10 @@ pub enum UserError {
     WrongProvider,
     InvalidUpdate,
     UpdateLimitExceeded,
+    IdentityInUse,
+    IdentityAlreadyLinked,
+    IdentityNotLinked,
+    LastLoginMethod,
 }
 
 impl UserError {
@@ -31,7 +35,7 @@ impl UserError {
             UserError::NotFound => StatusCode::NOT_FOUND,
             UserError::InvalidCredentials => StatusCode::UNAUTHORIZED,
             UserError::NotVerified => StatusCode::UNAUTHORIZED,
-            UserError::DiscordAuth => StatusCode::BAD_REQUEST,
+            UserError::ExternalAuth => StatusCode::BAD_REQUEST,
             UserError::Unauthorized => StatusCode::UNAUTHORIZED,
             UserError::AutoDelete => StatusCode::BAD_REQUEST,
             UserError::AlreadyActive => StatusCode::BAD_REQUEST,
@@ -39,6 +43,10 @@ impl UserError {
             UserError::WrongProvider => StatusCode::BAD_REQUEST,
             UserError::InvalidUpdate => StatusCode::BAD_REQUEST,
             UserError::UpdateLimitExceeded => StatusCode::UNAUTHORIZED,
+            UserError::IdentityInUse => StatusCode::CONFLICT,
+            UserError::IdentityAlreadyLinked => StatusCode::CONFLICT,
+            UserError::IdentityNotLinked => StatusCode::NOT_FOUND,
+            UserError::LastLoginMethod => StatusCode::BAD_REQUEST,
         }
     }
 
@@ -48,7 +56,7 @@ impl UserError {
             UserError::NotFound => "User not found",
             UserError::InvalidCredentials => "Invalid credentials",
             UserError::NotVerified => "Not verified user",
-            UserError::DiscordAuth => "Use discord auth",
+            UserError::ExternalAuth => "Use the provider linked to the account",
             UserError::Unauthorized => "Unauthorized user",
             UserError::AutoDelete => "Cannot Delete Yourself",
             UserError::AlreadyActive => "User Already Active",
@@ -56,6 +64,10 @@ impl UserError {
             UserError::WrongProvider => "Using wrong provider",
             UserError::InvalidUpdate => "Invalid Update",
             UserError::UpdateLimitExceeded => "Update Limit Exceeded",
+            UserError::IdentityInUse => "Account already linked to another user",
+            UserError::IdentityAlreadyLinked => "Provider already linked",
+            UserError::IdentityNotLinked => "Provider not linked",
+            UserError::LastLoginMethod => "Cannot remove the last login method",
         }
     }
 }
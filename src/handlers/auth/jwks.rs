@@ -0,0 +1,23 @@
+use ntex::{
+    http::header::{HeaderValue, CACHE_CONTROL},
+    web::{types::State, HttpResponse},
+};
+
+use crate::{error::AppResult, states::AppState};
+
+/// Public keys other services can use to verify access tokens.
+#[inline]
+pub(crate) async fn jwks(state: State<AppState>) -> HttpResponse {
+    HttpResponse::Ok()
+        .header(
+            CACHE_CONTROL,
+            HeaderValue::from_static("public, max-age=300"),
+        )
+        .json(&state.token_svc.jwks())
+}
+
+#[inline]
+pub(crate) async fn reload_jwt_keys(state: State<AppState>) -> AppResult<HttpResponse> {
+    state.token_svc.reload_keys()?;
+    Ok(HttpResponse::Ok().finish())
+}
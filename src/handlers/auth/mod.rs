@@ -1,7 +1,9 @@
-pub(crate) use discord::*;
+pub(crate) use jwks::*;
+pub(crate) use oauth::*;
 pub(crate) use user::*;
 pub(crate) use verify::*;
 
-mod discord;
+mod jwks;
+mod oauth;
 mod user;
 mod verify;
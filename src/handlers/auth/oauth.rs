@@ -0,0 +1,132 @@
+use garde::Validate;
+use ntex::{
+    http::header::{COOKIE, SET_COOKIE},
+    web::{
+        types::{Json, Path, Query, State},
+        HttpRequest, HttpResponse,
+    },
+};
+
+use crate::{
+    config::constants::*,
+    error::{AppResult, CommonError, UserError},
+    services::OAuthOutcome,
+    states::AppState,
+    structs::{ClientFingerprint, OAuthCallback, OAuthCodeExchange, ProviderPath},
+};
+
+use super::user::complete_login;
+
+/// Cookie binding the state of an authorization to the browser that started it.
+#[inline]
+pub(crate) fn oauth_state_cookie(oauth_state: &str) -> String {
+    format!(
+        "{OAUTH_STATE_COOKIE}={oauth_state}; Max-Age={}; Path=/; HttpOnly; Secure; SameSite=Lax",
+        OAUTH_STATE_TTL.as_secs()
+    )
+}
+
+/// Reads the state cookie set when the authorization started.
+#[inline]
+fn browser_oauth_state(req: &HttpRequest) -> Option<&str> {
+    req.headers()
+        .get_all(COOKIE)
+        .filter_map(|header| header.to_str().ok())
+        .flat_map(|cookies| cookies.split(';'))
+        .find_map(|cookie| {
+            cookie
+                .trim()
+                .strip_prefix(OAUTH_STATE_COOKIE)
+                .and_then(|cookie| cookie.strip_prefix('='))
+        })
+}
+
+#[inline]
+pub async fn oauth_authorize(
+    state: State<AppState>,
+    path: Path<ProviderPath>,
+) -> AppResult<HttpResponse> {
+    let (authorize_url, oauth_state) = state.oauth_svc.authorize_url(path.0, None)?;
+
+    Ok(HttpResponse::Found()
+        .set_header("Location", authorize_url)
+        .set_header(SET_COOKIE, oauth_state_cookie(&oauth_state))
+        .finish())
+}
+
+pub async fn oauth_callback(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ProviderPath>,
+    Query(callback): Query<OAuthCallback>,
+) -> AppResult<HttpResponse> {
+    let outcome = state
+        .oauth_svc
+        .callback(path.0, &callback, browser_oauth_state(&req))
+        .await?;
+
+    let redirect_url = match outcome {
+        OAuthOutcome::SignedIn(user) => {
+            let code = state.token_svc.generate_exchange_code(user.id)?;
+            format!(
+                "{OAUTH_REDIRECT_BASE}/{}/callback?code={}",
+                path.0.slug(),
+                code
+            )
+        }
+
+        OAuthOutcome::Linked => format!("{ACCOUNT_LINK_REDIRECT}?linked={}", path.0.slug()),
+    };
+
+    Ok(HttpResponse::Found()
+        .set_header("Location", redirect_url)
+        .set_header(
+            SET_COOKIE,
+            format!("{OAUTH_STATE_COOKIE}=; Max-Age=0; Path=/; HttpOnly; Secure; SameSite=Lax"),
+        )
+        .finish())
+}
+
+#[inline]
+pub async fn oauth_exchange(
+    req: HttpRequest,
+    state: State<AppState>,
+    Query(query): Query<ClientFingerprint>,
+    Json(exchange): Json<OAuthCodeExchange>,
+) -> AppResult<HttpResponse> {
+    if exchange.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = state.token_svc.redeem_exchange_code(&exchange.code)?;
+
+    let Some(user) = state.user_repo.find(user_id).await? else {
+        Err(UserError::NotFound)?
+    };
+
+    if !user.active {
+        Err(UserError::NotVerified)?
+    }
+
+    complete_login(&req, &state, &user, query.fingerprint).await
+}
+
+#[cfg(test)]
+mod tests {
+    use ntex::web::test::TestRequest;
+
+    use super::*;
+
+    #[test]
+    fn state_is_read_from_the_cookie_header() {
+        let req = TestRequest::default()
+            .header(COOKIE, "theme=dark; oauth_state=c3RhdGU; other=1")
+            .to_http_request();
+        assert_eq!(browser_oauth_state(&req), Some("c3RhdGU"));
+
+        let req = TestRequest::default()
+            .header(COOKIE, "oauth_state_old=c3RhdGU")
+            .to_http_request();
+        assert_eq!(browser_oauth_state(&req), None);
+    }
+}
@@ -6,15 +6,15 @@ use ntex::web::{
 };
 
 use crate::{
-    entity::{Provider, UserExtension},
+    entity::{Provider, User, UserExtension},
     error::{AppResult, CommonError, UserError},
-    services::UserServiceOperations,
+    services::{TwoFactorServiceOperations, UserServiceOperations},
     states::AppState,
     structs::{
-        AuthTokens, ClientFingerprint, EmailVerificationTemplate, LoginCredentials, NewAccessToken,
+        ClientDevice, ClientFingerprint, EmailVerificationTemplate, LoginCredentials,
         PasswordChangeConfirmationTemplate, PasswordResetRequest, PasswordResetTemplate,
         PasswordUpdateData, RefreshTokenRequest, TokenPurpose, TokenVerification,
-        UserRegistrationData,
+        TwoFactorChallenge, TwoFactorLogin, UserRegistrationData,
     },
 };
 
@@ -56,6 +56,7 @@ pub(crate) async fn register(
 
 #[inline]
 pub(crate) async fn login(
+    req: HttpRequest,
     state: State<AppState>,
     Query(query): Query<ClientFingerprint>,
     Json(login_credentials): Json<LoginCredentials>,
@@ -77,7 +78,7 @@ pub(crate) async fn login(
     }
 
     if user.provider != Provider::Local {
-        return Err(UserError::DiscordAuth)?;
+        return Err(UserError::ExternalAuth)?;
     }
 
     if !state
@@ -88,20 +89,67 @@ pub(crate) async fn login(
         return Err(UserError::InvalidCredentials)?;
     }
 
-    let access_token = state
-        .token_svc
-        .generate_token(user.id, TokenPurpose::Authentication)?;
+    complete_login(&req, &state, &user, query.fingerprint).await
+}
+
+/// Issues the tokens of an authenticated user, or a two-factor challenge
+/// if the user has TOTP enabled.
+pub(super) async fn complete_login(
+    req: &HttpRequest,
+    state: &AppState,
+    user: &User,
+    fingerprint: String,
+) -> AppResult<HttpResponse> {
+    if state.two_factor_repo.enabled(user.id).await? {
+        let two_factor_token = state
+            .token_svc
+            .generate_token(user.id, TokenPurpose::TwoFactorPending)?;
 
-    let refresh_token = state
+        state
+            .token_svc
+            .save_two_factor_token(two_factor_token.clone());
+
+        return Ok(HttpResponse::Accepted().json(&TwoFactorChallenge { two_factor_token }));
+    }
+
+    let auth_tokens = state
         .token_svc
-        .generate_refresh_token(user.id, query.fingerprint)?;
+        .generate_auth_tokens(user, fingerprint, &ClientDevice::from_request(req))
+        .await?;
 
-    let auth_response = AuthTokens {
-        access_token,
-        refresh_token,
+    Ok(HttpResponse::Ok().json(&auth_tokens))
+}
+
+#[inline]
+pub(crate) async fn login_two_factor(
+    req: HttpRequest,
+    state: State<AppState>,
+    Query(query): Query<ClientFingerprint>,
+    Json(two_factor_login): Json<TwoFactorLogin>,
+) -> AppResult<HttpResponse> {
+    if two_factor_login.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = state
+        .two_factor_svc
+        .login(two_factor_login.token, &two_factor_login.code)
+        .await?;
+
+    let Some(user) = state.user_repo.find(user_id).await? else {
+        Err(UserError::NotFound)?
     };
 
-    Ok(HttpResponse::Ok().json(&auth_response))
+    if !user.active {
+        Err(UserError::NotVerified)?
+    }
+
+    let auth_tokens = state
+        .token_svc
+        .generate_auth_tokens(&user, query.fingerprint, &ClientDevice::from_request(&req))
+        .await?;
+
+    Ok(HttpResponse::Ok().json(&auth_tokens))
 }
 
 #[inline]
@@ -109,13 +157,12 @@ pub(crate) async fn refresh_token(
     state: State<AppState>,
     Query(query): Query<RefreshTokenRequest>,
 ) -> AppResult<HttpResponse> {
-    let access_token = state
+    let auth_tokens = state
         .token_svc
-        .refresh_access_token(&query.refresh_token, query.fingerprint)?;
-
-    let refresh_response = NewAccessToken { access_token };
+        .rotate_refresh_token(&query.refresh_token, query.fingerprint)
+        .await?;
 
-    Ok(HttpResponse::Ok().json(&refresh_response))
+    Ok(HttpResponse::Ok().json(&auth_tokens))
 }
 
 #[inline]
@@ -128,7 +175,10 @@ pub(crate) async fn logout(
 
     state
         .token_svc
-        .remove_refresh_token(user_id, query.fingerprint);
+        .remove_refresh_token(user_id, query.fingerprint)
+        .await?;
+
+    state.token_svc.revoke_access_tokens(user_id).await?;
 
     Ok(HttpResponse::Ok().finish())
 }
@@ -1,13 +1,15 @@
+use garde::Validate;
 use ntex::web::{
-    types::{Path, State},
-    HttpResponse,
+    types::{Json, Path, State},
+    HttpRequest, HttpResponse,
 };
 
 use crate::{
-    error::{AppResult, ChampionshipError},
+    entity::UserExtension,
+    error::{AppResult, ChampionshipError, CommonError},
     services::ChampionshipAdminServiceOperations,
     states::AppState,
-    structs::ChampionshipId,
+    structs::{ChampionshipId, ChampionshipOwnerForm},
 };
 
 // TODO: implement a method to update championship info
@@ -35,3 +37,23 @@ pub async fn delete_championship(
 
     Ok(HttpResponse::Ok().finish())
 }
+
+#[inline]
+pub async fn transfer_owner(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipId>,
+    form: Json<ChampionshipOwnerForm>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() || form.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let admin_id = req.user_id()?;
+    state
+        .championship_svc
+        .admin_transfer_ownership(path.0, admin_id, form.user_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
@@ -0,0 +1,69 @@
+use garde::Validate;
+use ntex::web::{
+    types::{Path, State},
+    HttpRequest, HttpResponse,
+};
+
+use crate::{
+    entity::UserExtension,
+    error::{AppResult, CommonError},
+    services::DriverServiceOperations,
+    states::AppState,
+    structs::{ChampionshipAndClaimId, ChampionshipId},
+};
+
+use super::ensure_admin;
+
+#[inline]
+pub async fn pending(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    ensure_admin(&req, &state, path.0).await?;
+
+    let claims = state.driver_repo.championship_claims(path.0).await?;
+    Ok(HttpResponse::Ok().json(&claims))
+}
+
+#[inline]
+pub async fn approve(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipAndClaimId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    state
+        .driver_svc
+        .approve_claim(path.championship_id, user_id, path.claim_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[inline]
+pub async fn reject(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipAndClaimId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    state
+        .driver_svc
+        .reject_claim(path.championship_id, user_id, path.claim_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
@@ -0,0 +1,92 @@
+use garde::Validate;
+use ntex::{
+    http::header::{HeaderValue, CONTENT_DISPOSITION},
+    util::Bytes,
+    web::{
+        types::{Path, Query, State},
+        HttpRequest, HttpResponse,
+    },
+};
+use tokio_stream::StreamExt;
+use tracing::error;
+
+use crate::{
+    error::{AppError, AppResult, ChampionshipError, CommonError},
+    states::AppState,
+    structs::{ChampionshipExportQuery, ChampionshipId},
+    utils::{ChampionshipExportHead, ChampionshipExporter},
+};
+
+use super::ensure_visible;
+
+#[inline]
+pub async fn export(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipId>,
+    Query(query): Query<ChampionshipExportQuery>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() || query.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let Some(championship) = state.championship_repo.find(path.0).await? else {
+        Err(ChampionshipError::NotFound)?
+    };
+
+    ensure_visible(&req, &state, &championship).await?;
+
+    let (drivers, standings, races, results) = tokio::try_join!(
+        state.championship_repo.drivers_linked(path.0),
+        state.championship_repo.standings(path.0),
+        state.championship_repo.races(path.0),
+        state.championship_repo.results_stream(path.0)
+    )?;
+
+    let mut exporter = ChampionshipExporter::new(query.format);
+
+    let head = exporter.head(&ChampionshipExportHead {
+        championship: &championship,
+        drivers: &drivers,
+        standings: &standings,
+        races: &races,
+    })?;
+
+    let tail = exporter.tail();
+    let interrupted = exporter.interrupted();
+    let mut failed = false;
+
+    // Only the results are streamed from the database, the rest is small enough.
+    // A failure ends the export with an explicit error instead of truncating it.
+    let body = tokio_stream::once(Ok(head))
+        .chain(results.map(move |entry| exporter.result(&entry?)))
+        .chain(tokio_stream::once(Ok(tail)))
+        .map_while(move |chunk: AppResult<Bytes>| {
+            if failed {
+                return None;
+            }
+
+            match chunk {
+                Ok(chunk) => Some(Ok::<_, AppError>(chunk)),
+                Err(e) => {
+                    error!("Error exporting championship: {}", e);
+                    failed = true;
+                    Some(Ok(interrupted.clone()))
+                }
+            }
+        });
+
+    let disposition = format!(
+        "attachment; filename=\"championship-{}.{}\"",
+        championship.id,
+        query.format.extension()
+    );
+
+    Ok(HttpResponse::Ok()
+        .content_type(query.format.content_type())
+        .header(
+            CONTENT_DISPOSITION,
+            HeaderValue::from_str(&disposition).map_err(|_| CommonError::InternalServerError)?,
+        )
+        .streaming(Box::pin(body)))
+}
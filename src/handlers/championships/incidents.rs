@@ -0,0 +1,123 @@
+use garde::Validate;
+use ntex::web::{
+    types::{Json, Path, State},
+    HttpRequest, HttpResponse,
+};
+
+use crate::{
+    entity::UserExtension,
+    error::{AppResult, CommonError, IncidentError},
+    services::IncidentServiceOperations,
+    states::AppState,
+    structs::{
+        ChampionshipAndIncidentId, ChampionshipId, IncidentCommentForm, IncidentData,
+        IncidentPenaltyForm,
+    },
+};
+
+use super::ensure_steward;
+
+#[inline]
+pub async fn list(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    ensure_steward(&req, &state, path.0).await?;
+
+    let incidents = state.incident_repo.championship_incidents(path.0).await?;
+
+    Ok(HttpResponse::Ok().json(&incidents))
+}
+
+#[inline]
+pub async fn get(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipAndIncidentId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    ensure_steward(&req, &state, path.championship_id).await?;
+
+    let (incident, comments) = tokio::try_join!(
+        state
+            .incident_repo
+            .find(path.championship_id, path.incident_id),
+        state.incident_repo.comments(path.incident_id)
+    )?;
+
+    let incident = incident.ok_or(IncidentError::NotFound)?;
+
+    Ok(HttpResponse::Ok().json(&IncidentData { incident, comments }))
+}
+
+#[inline]
+pub async fn comment(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipAndIncidentId>,
+    form: Json<IncidentCommentForm>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() || form.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    state
+        .incident_svc
+        .comment(path.championship_id, user_id, path.incident_id, &form.body)
+        .await?;
+
+    Ok(HttpResponse::Created().finish())
+}
+
+#[inline]
+pub async fn penalty(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipAndIncidentId>,
+    form: Json<IncidentPenaltyForm>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() || form.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    state
+        .incident_svc
+        .apply_penalty(
+            path.championship_id,
+            user_id,
+            path.incident_id,
+            form.seconds,
+        )
+        .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[inline]
+pub async fn dismiss(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipAndIncidentId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    state
+        .incident_svc
+        .dismiss(path.championship_id, user_id, path.incident_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
@@ -0,0 +1,92 @@
+use garde::Validate;
+use ntex::web::{
+    types::{Path, Query, State},
+    HttpRequest, HttpResponse,
+};
+
+use crate::{
+    entity::UserExtension,
+    error::{AppResult, ChampionshipError, CommonError},
+    services::ChampionshipServiceOperations,
+    states::AppState,
+    structs::{InvitationId, TokenVerification},
+};
+
+#[inline]
+pub async fn get(
+    state: State<AppState>,
+    Query(query): Query<TokenVerification>,
+) -> AppResult<HttpResponse> {
+    let invitation_id = state.token_svc.invitation_id(&query.token)?;
+
+    let Some(invitation) = state
+        .championship_repo
+        .find_invitation(invitation_id)
+        .await?
+    else {
+        Err(ChampionshipError::InvitationNotFound)?
+    };
+
+    Ok(HttpResponse::Ok().json(&invitation))
+}
+
+#[inline]
+pub async fn decline_by_token(
+    state: State<AppState>,
+    Query(query): Query<TokenVerification>,
+) -> AppResult<HttpResponse> {
+    let invitation_id = state.token_svc.invitation_id(&query.token)?;
+
+    state
+        .championship_svc
+        .decline_invitation(invitation_id, None)
+        .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[inline]
+pub async fn user_invitations(req: HttpRequest, state: State<AppState>) -> AppResult<HttpResponse> {
+    let user = req.user()?;
+    let invitations = state.championship_repo.invitations(&user.email).await?;
+
+    Ok(HttpResponse::Ok().json(&invitations))
+}
+
+#[inline]
+pub async fn accept(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<InvitationId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user = req.user()?;
+    state
+        .championship_svc
+        .accept_invitation(path.0, &user)
+        .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[inline]
+pub async fn decline(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<InvitationId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user = req.user()?;
+    state
+        .championship_svc
+        .decline_invitation(path.0, Some(&user.email))
+        .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
@@ -0,0 +1,85 @@
+use garde::Validate;
+use ntex::web::{
+    types::{Path, State},
+    HttpRequest, HttpResponse,
+};
+
+use crate::{
+    entity::UserExtension,
+    error::{AppResult, CommonError},
+    services::ChampionshipServiceOperations,
+    states::AppState,
+    structs::{ChampionshipAndRequestId, ChampionshipId},
+};
+
+use super::ensure_admin;
+
+#[inline]
+pub async fn request(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    state.championship_svc.request_join(path.0, user_id).await?;
+
+    Ok(HttpResponse::Created().finish())
+}
+
+#[inline]
+pub async fn pending(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    ensure_admin(&req, &state, path.0).await?;
+
+    let requests = state.championship_repo.join_requests(path.0).await?;
+    Ok(HttpResponse::Ok().json(&requests))
+}
+
+#[inline]
+pub async fn approve(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipAndRequestId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    state
+        .championship_svc
+        .approve_join_request(path.championship_id, user_id, path.request_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[inline]
+pub async fn reject(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipAndRequestId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    state
+        .championship_svc
+        .reject_join_request(path.championship_id, user_id, path.request_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
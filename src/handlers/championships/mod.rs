@@ -1,22 +1,90 @@
+use ntex::web::{types::State, HttpRequest};
+
+use crate::{
+    entity::{Championship, UserExtension, Visibility},
+    error::{AppResult, ChampionshipError, IncidentError},
+    states::AppState,
+};
+
 pub(crate) mod admin;
+pub(crate) mod claims;
+pub(crate) mod export;
+pub(crate) mod incidents;
+pub(crate) mod invitations;
+pub(crate) mod join_requests;
+pub(crate) mod owner;
+pub(crate) mod results;
 pub(crate) mod service;
 pub(crate) mod stream;
+pub(crate) mod transfers;
+
+/// Checks that the requesting user is an admin of the championship.
+#[inline]
+async fn ensure_admin(req: &HttpRequest, state: &State<AppState>, id: i32) -> AppResult<()> {
+    let user_id = req.user_id()?;
+
+    if !state.championship_repo.is_admin(id, user_id).await? {
+        Err(ChampionshipError::NotAdmin)?
+    }
+
+    Ok(())
+}
+
+/// Checks that the requesting user can review the incidents of the championship.
+#[inline]
+async fn ensure_steward(req: &HttpRequest, state: &State<AppState>, id: i32) -> AppResult<()> {
+    let user_id = req.user_id()?;
+
+    if !state.championship_repo.is_steward(id, user_id).await? {
+        Err(IncidentError::NotSteward)?
+    }
+
+    Ok(())
+}
+
+/// Checks that the requesting user can see the championship.
+///
+/// Private championships are hidden from anyone outside them.
+#[inline]
+async fn ensure_visible(
+    req: &HttpRequest,
+    state: &State<AppState>,
+    championship: &Championship,
+) -> AppResult<()> {
+    if championship.visibility == Visibility::Private {
+        let user_id = req.user_id()?;
+
+        if state
+            .championship_repo
+            .user_relation(championship.id, user_id)
+            .await?
+            .is_none()
+        {
+            Err(ChampionshipError::NotFound)?
+        }
+    }
+
+    Ok(())
+}
 
 pub(crate) mod core {
     use garde::Validate;
     use ntex::web::{
-        types::{Json, Path, State},
+        types::{Json, Path, Query, State},
         HttpRequest, HttpResponse,
     };
 
+    use super::ensure_visible;
     use crate::{
         entity::{Role, UserExtension},
         error::{AppResult, ChampionshipError, CommonError},
-        services::ChampionshipServiceOperations,
+        services::{ChampionshipServiceOperations, TeamAccess},
         states::AppState,
         structs::{
             ChampionshipAndUserId, ChampionshipCreationData, ChampionshipData, ChampionshipId,
-            ChampionshipUpdateData, ChampionshipUserAddForm,
+            ChampionshipImportForm, ChampionshipInvitationTemplate, ChampionshipSearchData,
+            ChampionshipSearchQuery, ChampionshipUpdateData, ChampionshipUserAddForm,
+            ChampionshipUserUpdateForm, TokenPurpose,
         },
     };
 
@@ -77,22 +145,75 @@ pub(crate) mod core {
     }
 
     #[inline]
-    pub async fn add_user(
+    pub async fn invite_user(
         req: HttpRequest,
         state: State<AppState>,
-        Json(add_user): Json<ChampionshipUserAddForm>,
+        Json(invite_user): Json<ChampionshipUserAddForm>,
         path: Path<ChampionshipId>,
     ) -> AppResult<HttpResponse> {
-        if add_user.validate().is_err() || path.validate().is_err() {
+        if invite_user.validate().is_err() || path.validate().is_err() {
             Err(CommonError::ValidationFailed)?
         }
 
-        let user_id = req.user_id()?;
+        let user = req.user()?;
+        let invitation_id = state
+            .championship_svc
+            .invite_user(path.0, user.id, &invite_user)
+            .await?;
+
+        let Some(championship) = state.championship_repo.find(path.0).await? else {
+            Err(ChampionshipError::NotFound)?
+        };
+
+        let token = state
+            .token_svc
+            .generate_token(invitation_id, TokenPurpose::ChampionshipInvitation)?;
+
+        let template = ChampionshipInvitationTemplate {
+            championship_name: championship.name.clone(),
+            invited_by: user.username.clone(),
+            invitation_link: format!(
+                "https://intellitelemetry.live/championships/invitation?token={}",
+                token
+            ),
+        };
+
         state
+            .email_svc
+            .send_mail_to(
+                &invite_user.email,
+                None,
+                "Championship Invitation",
+                template,
+            )
+            .await?;
+
+        Ok(HttpResponse::Created().finish())
+    }
+
+    #[inline]
+    pub async fn update_user(
+        req: HttpRequest,
+        state: State<AppState>,
+        path: Path<ChampionshipAndUserId>,
+        form: Json<ChampionshipUserUpdateForm>,
+    ) -> AppResult<HttpResponse> {
+        if path.validate().is_err() || form.validate().is_err() {
+            Err(CommonError::ValidationFailed)?
+        }
+
+        let user_id = req.user_id()?;
+        let relation = state
             .championship_svc
-            .add_user(path.0, user_id, add_user)
+            .update_user(path.championship_id, user_id, path.user_id, &form)
             .await?;
 
+        state.f1_svc.update_engineer(
+            &path.championship_id,
+            path.user_id,
+            TeamAccess::from(&relation),
+        );
+
         Ok(HttpResponse::Ok().finish())
     }
 
@@ -115,8 +236,46 @@ pub(crate) mod core {
         Ok(HttpResponse::Ok().finish())
     }
 
+    #[inline]
+    pub async fn delete(
+        req: HttpRequest,
+        state: State<AppState>,
+        path: Path<ChampionshipId>,
+    ) -> AppResult<HttpResponse> {
+        if path.validate().is_err() {
+            Err(CommonError::ValidationFailed)?
+        }
+
+        let user_id = req.user_id()?;
+        state.championship_svc.delete(path.0, user_id).await?;
+
+        // A hidden championship can't keep receiving telemetry
+        if state.f1_svc.service_status(&path.0).active {
+            state.f1_svc.stop(&path.0).await?;
+        }
+
+        Ok(HttpResponse::Ok().finish())
+    }
+
+    #[inline]
+    pub async fn restore(
+        req: HttpRequest,
+        state: State<AppState>,
+        path: Path<ChampionshipId>,
+    ) -> AppResult<HttpResponse> {
+        if path.validate().is_err() {
+            Err(CommonError::ValidationFailed)?
+        }
+
+        let user_id = req.user_id()?;
+        state.championship_svc.restore(path.0, user_id).await?;
+
+        Ok(HttpResponse::Ok().finish())
+    }
+
     #[inline]
     pub async fn get(
+        req: HttpRequest,
         state: State<AppState>,
         path: Path<ChampionshipId>,
     ) -> AppResult<HttpResponse> {
@@ -127,9 +286,74 @@ pub(crate) mod core {
             state.championship_repo.races(path.0)
         )?;
 
+        let championship = championship.ok_or(ChampionshipError::NotFound)?;
+        ensure_visible(&req, &state, &championship).await?;
+
         Ok(HttpResponse::Ok().json(&ChampionshipData {
-            championship: championship.ok_or(ChampionshipError::NotFound)?,
+            championship,
             races,
         }))
     }
+
+    #[inline]
+    pub async fn standings(
+        req: HttpRequest,
+        state: State<AppState>,
+        path: Path<ChampionshipId>,
+    ) -> AppResult<HttpResponse> {
+        path.validate().map_err(|_| CommonError::ValidationFailed)?;
+
+        let Some(championship) = state.championship_repo.find(path.0).await? else {
+            Err(ChampionshipError::NotFound)?
+        };
+
+        ensure_visible(&req, &state, &championship).await?;
+
+        let standings = state.championship_repo.standings(path.0).await?;
+        Ok(HttpResponse::Ok().json(&*standings))
+    }
+
+    #[inline]
+    pub async fn import(
+        req: HttpRequest,
+        state: State<AppState>,
+        path: Path<ChampionshipId>,
+        form: Json<ChampionshipImportForm>,
+    ) -> AppResult<HttpResponse> {
+        if path.validate().is_err() {
+            Err(CommonError::ValidationFailed)?
+        }
+
+        // Rows are validated one by one to report every failing row
+        let user_id = req.user_id()?;
+        let report = state
+            .championship_svc
+            .import(path.0, user_id, &form)
+            .await?;
+
+        if report.has_errors() {
+            return Ok(HttpResponse::UnprocessableEntity().json(&report));
+        }
+
+        Ok(HttpResponse::Created().json(&report))
+    }
+
+    #[inline]
+    pub async fn search(
+        state: State<AppState>,
+        Query(query): Query<ChampionshipSearchQuery>,
+    ) -> AppResult<HttpResponse> {
+        if query.validate().is_err() {
+            Err(CommonError::ValidationFailed)?
+        }
+
+        let (championships, total) = state.championship_repo.search(&query).await?;
+
+        Ok(HttpResponse::Ok().json(&ChampionshipSearchData {
+            championships,
+            total,
+            page: query.page,
+            per_page: query.per_page,
+        }))
+    }
 }
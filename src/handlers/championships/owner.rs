@@ -0,0 +1,89 @@
+use garde::Validate;
+use ntex::web::{
+    types::{Json, Path, State},
+    HttpRequest, HttpResponse,
+};
+
+use crate::{
+    entity::UserExtension,
+    error::{AppResult, CommonError},
+    services::ChampionshipServiceOperations,
+    states::AppState,
+    structs::{ChampionshipId, ChampionshipOwnerForm},
+};
+
+use super::ensure_admin;
+
+#[inline]
+pub async fn transfer(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipId>,
+    form: Json<ChampionshipOwnerForm>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() || form.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    state
+        .championship_svc
+        .transfer_ownership(path.0, user_id, form.user_id)
+        .await?;
+
+    Ok(HttpResponse::Created().finish())
+}
+
+#[inline]
+pub async fn history(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    ensure_admin(&req, &state, path.0).await?;
+
+    let transfers = state.championship_repo.owner_transfers(path.0).await?;
+    Ok(HttpResponse::Ok().json(&transfers))
+}
+
+#[inline]
+pub async fn accept(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    state
+        .championship_svc
+        .accept_ownership(path.0, user_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[inline]
+pub async fn decline(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    state
+        .championship_svc
+        .decline_ownership(path.0, user_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
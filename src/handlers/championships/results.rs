@@ -0,0 +1,76 @@
+use garde::Validate;
+use ntex::web::{
+    types::{Json, Path, State},
+    HttpRequest, HttpResponse,
+};
+
+use crate::{
+    entity::UserExtension,
+    error::{AppResult, ChampionshipError, CommonError, ResultError},
+    services::ResultServiceOperations,
+    states::AppState,
+    structs::{RaceResultData, RaceResultPath, ResultAmendmentForm},
+};
+
+use super::ensure_visible;
+
+#[inline]
+pub async fn get(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<RaceResultPath>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let Some(championship) = state.championship_repo.find(path.championship_id).await? else {
+        Err(ChampionshipError::NotFound)?
+    };
+
+    ensure_visible(&req, &state, &championship).await?;
+
+    let (result, amendments) = tokio::try_join!(
+        state
+            .result_repo
+            .find(path.championship_id, path.race_id, path.session_type),
+        state
+            .result_repo
+            .amendments(path.race_id, path.session_type)
+    )?;
+
+    let result = result.ok_or(ResultError::NotFound)?;
+
+    Ok(HttpResponse::Ok().json(&RaceResultData {
+        race_id: path.race_id,
+        session_type: path.session_type,
+        result,
+        amendments,
+    }))
+}
+
+#[inline]
+pub async fn amend(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<RaceResultPath>,
+    form: Json<ResultAmendmentForm>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() || form.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    state
+        .result_svc
+        .amend(
+            path.championship_id,
+            user_id,
+            path.race_id,
+            path.session_type,
+            &form,
+        )
+        .await?;
+
+    Ok(HttpResponse::Created().finish())
+}
@@ -2,25 +2,101 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use garde::Validate;
+use ntex::util::Bytes;
 use ntex::web::HttpRequest;
 use ntex::web::{
     types::{Path, State},
     HttpResponse,
 };
 use reqwest::header::HeaderValue;
-use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
+    Stream, StreamExt, StreamMap,
+};
 
-use crate::entity::{ChampionshipRole, UserExtension};
+use crate::entity::UserExtension;
 use crate::error::ChampionshipError;
 use crate::{
     error::{AppResult, CommonError, F1ServiceError},
+    services::{EngineerUpdate, TeamAccess},
     states::AppState,
     structs::ChampionshipId,
 };
 
 enum StreamType {
     Normal,
-    Engineer(u8),
+    Engineer {
+        user_id: i32,
+        access: TeamAccess,
+        updates: BroadcastStream<EngineerUpdate>,
+    },
+}
+
+/// Merged channels of the teams a member follows, joining the teams that
+/// show up in the session after the stream opened.
+struct TeamStreams {
+    streams: StreamMap<u8, BroadcastStream<Bytes>>,
+    new_teams: Option<BroadcastStream<u8>>,
+    team_ids: Vec<u8>,
+    access: TeamAccess,
+    state: State<AppState>,
+    championship_id: i32,
+}
+
+impl Stream for TeamStreams {
+    type Item = Result<Bytes, BroadcastStreamRecvError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        if let Some(new_teams) = &mut this.new_teams {
+            loop {
+                match Pin::new(&mut *new_teams).poll_next(cx) {
+                    Poll::Ready(Some(Ok(team_id))) => {
+                        if this.streams.contains_key(&team_id) {
+                            continue;
+                        }
+
+                        if let Some((team_id, rx)) = this.state.f1_svc.join_team(
+                            &this.championship_id,
+                            &this.access,
+                            team_id,
+                        ) {
+                            this.streams.insert(team_id, BroadcastStream::new(rx));
+                            this.team_ids.push(team_id);
+                        }
+                    }
+
+                    Poll::Ready(Some(Err(_))) => continue,
+
+                    // The session ended, no more teams can show up
+                    Poll::Ready(None) => {
+                        this.new_teams = None;
+                        break;
+                    }
+
+                    Poll::Pending => break,
+                }
+            }
+        }
+
+        match Pin::new(&mut this.streams).poll_next(cx) {
+            Poll::Ready(Some((_, data))) => Poll::Ready(Some(data)),
+
+            // No team channel yet, keep waiting while teams can still show up
+            Poll::Ready(None) if this.new_teams.is_some() => Poll::Pending,
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl Drop for TeamStreams {
+    fn drop(&mut self) {
+        self.state
+            .f1_svc
+            .unsubscribe_teams(&self.championship_id, &self.team_ids);
+    }
 }
 
 struct CleanupStream<S> {
@@ -34,22 +110,34 @@ impl<S: Stream + Unpin> Stream for CleanupStream<S> {
     type Item = S::Item;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Engineer streams end as soon as the teams of the member change
+        if let StreamType::Engineer {
+            user_id,
+            access,
+            updates,
+            ..
+        } = &mut self.stream_type
+        {
+            while let Poll::Ready(Some(update)) = Pin::new(&mut *updates).poll_next(cx) {
+                let Ok(update) = update else {
+                    continue;
+                };
+
+                if update.user_id == *user_id && update.access != *access {
+                    return Poll::Ready(None);
+                }
+            }
+        }
+
         Pin::new(&mut self.inner).poll_next(cx)
     }
 }
 
 impl<S> Drop for CleanupStream<S> {
     fn drop(&mut self) {
-        match &self.stream_type {
-            StreamType::Normal => {
-                self.state.f1_svc.unsubscribe(&self.championship_id);
-            }
-
-            StreamType::Engineer(team_id) => {
-                self.state
-                    .f1_svc
-                    .unsubscribe_team(&self.championship_id, *team_id);
-            }
+        // Team channels are released by their own stream
+        if let StreamType::Normal = self.stream_type {
+            self.state.f1_svc.unsubscribe(&self.championship_id);
         }
     }
 }
@@ -107,21 +195,45 @@ pub async fn stream_telemetry_session(
 
     match relation {
         Some(relation) => {
-            if relation.role != ChampionshipRole::Engineer || relation.team_id.is_none() {
+            let access = TeamAccess::from(&relation);
+
+            if access == TeamAccess::None {
                 Err(ChampionshipError::NotEngineer)?
             }
 
-            let team_id = relation.team_id.unwrap() as u8;
-
-            let Some(rx) = state.f1_svc.subscribe_team(&path.0, team_id) else {
+            let Some((receivers, new_teams_rx, updates_rx)) =
+                state.f1_svc.subscribe_teams(&path.0, &access)
+            else {
                 Err(F1ServiceError::NotActive)?
             };
 
+            // Race directors and engineers of several teams get every channel merged
+            let mut streams = StreamMap::with_capacity(receivers.len());
+            let mut team_ids = Vec::with_capacity(receivers.len());
+
+            for (team_id, rx) in receivers {
+                streams.insert(team_id, BroadcastStream::new(rx));
+                team_ids.push(team_id);
+            }
+
+            let team_streams = TeamStreams {
+                streams,
+                new_teams: Some(BroadcastStream::new(new_teams_rx)),
+                team_ids,
+                access: access.clone(),
+                state: state.clone(),
+                championship_id: path.0,
+            };
+
             let stream = CleanupStream {
-                inner: BroadcastStream::new(rx),
+                inner: team_streams,
                 state: state.clone(),
                 championship_id: path.0,
-                stream_type: StreamType::Engineer(team_id),
+                stream_type: StreamType::Engineer {
+                    user_id,
+                    access,
+                    updates: BroadcastStream::new(updates_rx),
+                },
             };
 
             let mut response = HttpResponse::Ok();
@@ -0,0 +1,85 @@
+use garde::Validate;
+use ntex::web::{
+    types::{Path, State},
+    HttpRequest, HttpResponse,
+};
+
+use crate::{
+    entity::UserExtension,
+    error::{AppResult, CommonError},
+    services::ChampionshipServiceOperations,
+    states::AppState,
+    structs::{ChampionshipAndTransferId, ChampionshipId},
+};
+
+use super::ensure_admin;
+
+#[inline]
+pub async fn pending(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    ensure_admin(&req, &state, path.0).await?;
+
+    let transfers = state.championship_repo.pending_transfers(path.0).await?;
+    Ok(HttpResponse::Ok().json(&transfers))
+}
+
+#[inline]
+pub async fn history(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    ensure_admin(&req, &state, path.0).await?;
+
+    let transfers = state.championship_repo.transfer_history(path.0).await?;
+    Ok(HttpResponse::Ok().json(&transfers))
+}
+
+#[inline]
+pub async fn approve(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipAndTransferId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    state
+        .championship_svc
+        .approve_transfer(path.championship_id, user_id, path.transfer_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[inline]
+pub async fn reject(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ChampionshipAndTransferId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    state
+        .championship_svc
+        .reject_transfer(path.championship_id, user_id, path.transfer_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
@@ -0,0 +1,61 @@
+use garde::Validate;
+use ntex::web::{
+    types::{Json, Path, State},
+    HttpResponse,
+};
+
+use crate::{
+    error::{AppResult, CommonError},
+    services::DriverAdminServiceOperations,
+    states::AppState,
+    structs::{DriverMergeForm, DriverRenameForm, DriverSteamName},
+};
+
+#[inline]
+pub async fn delete_driver(
+    state: State<AppState>,
+    path: Path<DriverSteamName>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    state.driver_svc.admin_delete(&path.0).await?;
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[inline]
+pub async fn rename_driver(
+    state: State<AppState>,
+    path: Path<DriverSteamName>,
+    Json(rename_form): Json<DriverRenameForm>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() || rename_form.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    state
+        .driver_svc
+        .admin_rename(&path.0, &rename_form.steam_name)
+        .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[inline]
+pub async fn merge_drivers(
+    state: State<AppState>,
+    path: Path<DriverSteamName>,
+    Json(merge_form): Json<DriverMergeForm>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() || merge_form.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    state
+        .driver_svc
+        .admin_merge(&path.0, &merge_form.target)
+        .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
@@ -0,0 +1,49 @@
+use garde::Validate;
+use ntex::web::{
+    types::{Path, State},
+    HttpRequest, HttpResponse,
+};
+
+use crate::{
+    entity::UserExtension,
+    error::{AppResult, CommonError, DriverError},
+    states::AppState,
+    structs::{DriverProfileData, DriverSteamName},
+};
+
+pub(crate) mod admin;
+
+#[inline]
+pub async fn get(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<DriverSteamName>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+
+    let Some(driver) = state.driver_repo.find(&path.0).await? else {
+        Err(DriverError::NotFound)?
+    };
+
+    let (user, championships, stats) = tokio::try_join!(
+        async {
+            match driver.user_id {
+                Some(user_id) => state.user_repo.find(user_id).await,
+                None => Ok(None),
+            }
+        },
+        state.driver_repo.championships(&driver.steam_name, user_id),
+        state.driver_repo.stats(&driver.steam_name)
+    )?;
+
+    Ok(HttpResponse::Ok().json(&DriverProfileData {
+        driver,
+        user: user.map(Into::into),
+        championships,
+        stats,
+    }))
+}
@@ -6,8 +6,10 @@ use ntex::web::{
 
 use crate::{
     entity::UserExtension,
-    error::{AppResult, CommonError, UserError},
-    services::{UserAdminServiceOperations, UserServiceOperations},
+    error::{AppResult, CommonError, TwoFactorError, UserError},
+    services::{
+        TwoFactorAdminServiceOperations, UserAdminServiceOperations, UserServiceOperations,
+    },
     states::AppState,
     structs::UserId,
 };
@@ -104,3 +106,27 @@ pub async fn reactivate_user_account(
     state.user_svc.admin_activate(path.0).await?;
     Ok(HttpResponse::Ok().finish())
 }
+
+#[inline]
+pub async fn reset_two_factor(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<UserId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    if state.user_repo.find(path.0).await?.is_none() {
+        Err(UserError::NotFound)?
+    };
+
+    let user_id = req.user_id()?;
+
+    if path.0 == user_id {
+        Err(TwoFactorError::SelfReset)?
+    }
+
+    state.two_factor_svc.admin_reset(path.0).await?;
+    Ok(HttpResponse::Ok().finish())
+}
@@ -0,0 +1,53 @@
+use garde::Validate;
+use ntex::web::{
+    types::{Json, Path, State},
+    HttpRequest, HttpResponse,
+};
+
+use crate::{
+    entity::UserExtension,
+    error::{AppResult, CommonError},
+    services::ApiKeyServiceOperations,
+    states::AppState,
+    structs::{ApiKeyCreationForm, ApiKeyId},
+};
+
+#[inline]
+pub async fn list(req: HttpRequest, state: State<AppState>) -> AppResult<HttpResponse> {
+    let user_id = req.user_id()?;
+    let api_keys = state.api_key_repo.user_keys(user_id).await?;
+
+    Ok(HttpResponse::Ok().json(&api_keys))
+}
+
+#[inline]
+pub async fn create(
+    req: HttpRequest,
+    state: State<AppState>,
+    Json(form): Json<ApiKeyCreationForm>,
+) -> AppResult<HttpResponse> {
+    if form.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    let api_key = state.api_key_svc.create(user_id, &form).await?;
+
+    Ok(HttpResponse::Created().json(&api_key))
+}
+
+#[inline]
+pub async fn revoke(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ApiKeyId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    state.api_key_svc.revoke(user_id, path.0).await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
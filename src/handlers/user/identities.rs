@@ -0,0 +1,50 @@
+use ntex::{
+    http::header::SET_COOKIE,
+    web::{
+        types::{Path, State},
+        HttpRequest, HttpResponse,
+    },
+};
+
+use crate::{
+    entity::UserExtension,
+    error::AppResult,
+    handlers::auth::oauth_state_cookie,
+    services::UserServiceOperations,
+    states::AppState,
+    structs::{AccountLinkRedirect, ProviderPath},
+};
+
+#[inline]
+pub async fn list(req: HttpRequest, state: State<AppState>) -> AppResult<HttpResponse> {
+    let user_id = req.user_id()?;
+    let identities = state.user_repo.identities(user_id).await?;
+
+    Ok(HttpResponse::Ok().json(&identities))
+}
+
+#[inline]
+pub async fn link(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ProviderPath>,
+) -> AppResult<HttpResponse> {
+    let user_id = req.user_id()?;
+    let (authorize_url, oauth_state) = state.oauth_svc.authorize_url(path.0, Some(user_id))?;
+
+    Ok(HttpResponse::Ok()
+        .set_header(SET_COOKIE, oauth_state_cookie(&oauth_state))
+        .json(&AccountLinkRedirect { authorize_url }))
+}
+
+#[inline]
+pub async fn unlink(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<ProviderPath>,
+) -> AppResult<HttpResponse> {
+    let user = req.user()?;
+    state.user_svc.unlink_identity(user, path.0).await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
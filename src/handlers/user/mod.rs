@@ -1,26 +1,34 @@
 use garde::Validate;
 use ntex::web::{
-    types::{Json, State},
+    types::{Json, Path, State},
     HttpRequest, HttpResponse,
 };
 
 use crate::{
     entity::UserExtension,
     error::{AppResult, CommonError},
-    services::UserServiceOperations,
+    services::{DriverServiceOperations, UserServiceOperations},
     states::AppState,
-    structs::{UserProfileData, UserUpdateData},
+    structs::{DriverClaimForm, SessionId, UserProfileData, UserUpdateData},
 };
 
 pub(crate) mod admin;
+pub(crate) mod api_keys;
+pub(crate) mod identities;
+pub(crate) mod two_factor;
 
 #[inline]
 pub(crate) async fn get(req: HttpRequest, state: State<AppState>) -> AppResult<HttpResponse> {
     let user = req.user()?;
-    let championships = state.user_repo.championships(user.id).await?;
+
+    let (driver, championships) = tokio::try_join!(
+        state.driver_repo.find_by_user(user.id),
+        state.user_repo.championships(user.id)
+    )?;
 
     Ok(HttpResponse::Ok().json(&UserProfileData {
         user,
+        driver,
         championships,
     }))
 }
@@ -50,3 +58,65 @@ pub async fn get_championships(
 
     Ok(HttpResponse::Ok().json(&championships))
 }
+
+#[inline]
+pub async fn get_deleted_championships(
+    req: HttpRequest,
+    state: State<AppState>,
+) -> AppResult<HttpResponse> {
+    let user_id = req.user_id()?;
+    let championships = state.championship_repo.deleted_by_owner(user_id).await?;
+
+    Ok(HttpResponse::Ok().json(&championships))
+}
+
+#[inline]
+pub async fn driver_claims(req: HttpRequest, state: State<AppState>) -> AppResult<HttpResponse> {
+    let user_id = req.user_id()?;
+    let claims = state.driver_repo.user_claims(user_id).await?;
+
+    Ok(HttpResponse::Ok().json(&claims))
+}
+
+#[inline]
+pub async fn claim_driver(
+    req: HttpRequest,
+    state: State<AppState>,
+    Json(claim_form): Json<DriverClaimForm>,
+) -> AppResult<HttpResponse> {
+    if claim_form.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    let claim = state
+        .driver_svc
+        .claim(user_id, &claim_form.steam_name, claim_form.championship_id)
+        .await?;
+
+    Ok(HttpResponse::Created().json(&claim))
+}
+
+#[inline]
+pub async fn sessions(req: HttpRequest, state: State<AppState>) -> AppResult<HttpResponse> {
+    let user_id = req.user_id()?;
+    let sessions = state.session_repo.user_sessions(user_id).await?;
+
+    Ok(HttpResponse::Ok().json(&sessions))
+}
+
+#[inline]
+pub async fn revoke_session(
+    req: HttpRequest,
+    state: State<AppState>,
+    path: Path<SessionId>,
+) -> AppResult<HttpResponse> {
+    if path.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    state.token_svc.revoke_session(user_id, path.0).await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
@@ -0,0 +1,53 @@
+use garde::Validate;
+use ntex::web::{
+    types::{Json, State},
+    HttpRequest, HttpResponse,
+};
+
+use crate::{
+    entity::UserExtension,
+    error::{AppResult, CommonError},
+    services::TwoFactorServiceOperations,
+    states::AppState,
+    structs::{RecoveryCodes, TwoFactorCode},
+};
+
+#[inline]
+pub async fn setup(req: HttpRequest, state: State<AppState>) -> AppResult<HttpResponse> {
+    let user = req.user()?;
+    let two_factor_setup = state.two_factor_svc.setup(&user).await?;
+
+    Ok(HttpResponse::Ok().json(&two_factor_setup))
+}
+
+#[inline]
+pub async fn enable(
+    req: HttpRequest,
+    state: State<AppState>,
+    Json(form): Json<TwoFactorCode>,
+) -> AppResult<HttpResponse> {
+    if form.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    let recovery_codes = state.two_factor_svc.enable(user_id, &form.code).await?;
+
+    Ok(HttpResponse::Ok().json(&RecoveryCodes { recovery_codes }))
+}
+
+#[inline]
+pub async fn disable(
+    req: HttpRequest,
+    state: State<AppState>,
+    Json(form): Json<TwoFactorCode>,
+) -> AppResult<HttpResponse> {
+    if form.validate().is_err() {
+        Err(CommonError::ValidationFailed)?
+    }
+
+    let user_id = req.user_id()?;
+    state.two_factor_svc.disable(user_id, &form.code).await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
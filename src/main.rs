@@ -56,7 +56,10 @@ async fn main() -> std::io::Result<()> {
                         header::CONTENT_TYPE,
                         header::AUTHORIZATION,
                         header::ACCESS_CONTROL_ALLOW_CREDENTIALS,
+                        header::HeaderName::from_static("x-api-key"),
                     ])
+                    // Lets the account link flow store its state cookie
+                    .supports_credentials()
                     .max_age(3600)
                     .finish(),
             )
@@ -0,0 +1,106 @@
+use ntex::{
+    service::{Middleware, Service, ServiceCtx},
+    web::{Error, WebRequest, WebResponse},
+};
+
+use crate::{
+    config::constants::API_KEY_HEADER,
+    entity::ApiKeyScope,
+    error::{ApiKeyError, CommonError, UserError},
+    states::AppState,
+};
+
+/// Accepts an API key in place of a user token on the wrapped routes.
+///
+/// Must wrap outside `Authentication`. Requests with a valid key for the
+/// championship in the path and the required scope run as the owner of the key,
+/// requests without a key fall through to `Authentication`.
+pub struct ApiKeyAuthentication {
+    scope: ApiKeyScope,
+}
+
+impl ApiKeyAuthentication {
+    pub fn new(scope: ApiKeyScope) -> Self {
+        Self { scope }
+    }
+}
+
+impl<S> Middleware<S> for ApiKeyAuthentication {
+    type Service = ApiKeyAuthenticationMiddleware<S>;
+
+    fn create(&self, service: S) -> Self::Service {
+        ApiKeyAuthenticationMiddleware {
+            service,
+            scope: self.scope,
+        }
+    }
+}
+
+pub struct ApiKeyAuthenticationMiddleware<S> {
+    service: S,
+    scope: ApiKeyScope,
+}
+
+impl<S, Err> Service<WebRequest<Err>> for ApiKeyAuthenticationMiddleware<S>
+where
+    S: Service<WebRequest<Err>, Response = WebResponse, Error = Error>,
+{
+    type Response = WebResponse;
+    type Error = Error;
+
+    ntex::forward_ready!(service);
+
+    async fn call(
+        &self,
+        req: WebRequest<Err>,
+        ctx: ServiceCtx<'_, Self>,
+    ) -> Result<Self::Response, Self::Error> {
+        let Some(header) = req.headers().get(API_KEY_HEADER) else {
+            return ctx.call(&self.service, req).await;
+        };
+
+        let raw_key = header.to_str().map_err(|_| ApiKeyError::InvalidKey)?;
+
+        let Some(state) = req.app_state::<AppState>() else {
+            Err(CommonError::InternalServerError)?
+        };
+
+        let api_key = state.api_key_svc.authenticate(raw_key).await?;
+
+        let championship_id = req
+            .match_info()
+            .get("id")
+            .or_else(|| req.match_info().get("championship_id"))
+            .and_then(|id| id.parse().ok())
+            .ok_or(ApiKeyError::MissingScope)?;
+
+        if !api_key.allows(championship_id, self.scope) {
+            Err(ApiKeyError::MissingScope)?
+        }
+
+        let user = state
+            .user_repo
+            .find(api_key.user_id)
+            .await?
+            .ok_or(UserError::NotFound)?;
+
+        if !user.active {
+            Err(ApiKeyError::InvalidKey)?
+        }
+
+        // Race management stays with championship admins even after the key was created
+        if self.scope == ApiKeyScope::ManageRaces
+            && !state
+                .championship_repo
+                .is_admin(championship_id, user.id)
+                .await?
+        {
+            Err(ApiKeyError::MissingScope)?
+        }
+
+        req.extensions_mut().insert(user);
+        req.extensions_mut().insert(api_key);
+
+        ctx.call(&self.service, req).await
+    }
+}
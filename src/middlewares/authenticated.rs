@@ -5,8 +5,10 @@ use ntex::{
 
 use crate::{
     config::constants::BEARER_PREFIX,
+    entity::SharedUser,
     error::{CommonError, TokenError, UserError},
     states::AppState,
+    structs::TokenPurpose,
 };
 
 pub struct Authentication;
@@ -37,6 +39,11 @@ where
         req: WebRequest<Err>,
         ctx: ServiceCtx<'_, Self>,
     ) -> Result<Self::Response, Self::Error> {
+        // Already authenticated by an API key
+        if req.extensions().contains::<SharedUser>() {
+            return ctx.call(&self.service, req).await;
+        }
+
         let Some(header) = req.headers().get("Authorization") else {
             Err(TokenError::MissingToken)?
         };
@@ -53,8 +60,23 @@ where
             Err(CommonError::InternalServerError)?
         };
 
-        let id = state.token_svc.validate(header)?.claims.subject_id;
-        let user = state.user_repo.find(id).await?.ok_or(UserError::NotFound)?;
+        // Only access tokens are accepted as bearer tokens
+        let claims = state
+            .token_svc
+            .validate(header, TokenPurpose::Authentication)?
+            .claims;
+
+        let user = state
+            .user_repo
+            .find(claims.subject_id)
+            .await?
+            .ok_or(UserError::NotFound)?;
+
+        // Logout, password resets and deactivations bump the version of the user
+        if claims.ver != Some(user.token_version) || !user.active {
+            Err(TokenError::RevokedToken)?
+        }
+
         req.extensions_mut().insert(user);
 
         let res = ctx.call(&self.service, req).await?;
@@ -1,7 +1,9 @@
 pub(crate) use admin::*;
+pub(crate) use api_key::*;
 pub(crate) use authenticated::*;
 pub(crate) use login::*;
 
 mod admin;
+mod api_key;
 mod authenticated;
 mod login;
@@ -0,0 +1,78 @@
+use tokio_stream::StreamExt;
+
+use crate::{config::Database, entity::ApiKey, error::AppResult};
+
+/// Repository for the API keys users create for bots and integrations.
+pub struct ApiKeyRepository {
+    db: &'static Database,
+}
+
+impl ApiKeyRepository {
+    /// Creates a new ApiKeyRepository instance.
+    ///
+    /// # Arguments
+    /// - `db`: Database connection.
+    ///
+    /// # Returns
+    /// A new ApiKeyRepository instance.
+    pub fn new(db: &'static Database) -> Self {
+        Self { db }
+    }
+
+    /// Finds an API key by its public prefix.
+    ///
+    /// # Arguments
+    /// - `prefix`: The public prefix of the key.
+    ///
+    /// # Returns
+    /// An Option containing the key if found, revoked keys included.
+    pub async fn find_by_prefix(&self, prefix: &str) -> AppResult<Option<ApiKey>> {
+        let conn = self.db.pg.get().await?;
+
+        let find_key_stmt = conn
+            .prepare_cached(
+                r#"
+                    SELECT * FROM api_keys
+                    WHERE prefix = $1
+                "#,
+            )
+            .await?;
+
+        let row = conn.query_opt(&find_key_stmt, &[&prefix]).await?;
+        Ok(row.map(|row| ApiKey::from_row(&row)))
+    }
+
+    /// Retrieves the API keys of a user that are not revoked.
+    ///
+    /// # Arguments
+    /// - `user_id`: The ID of the user.
+    ///
+    /// # Returns
+    /// A vector of keys, newest first.
+    pub async fn user_keys(&self, user_id: i32) -> AppResult<Vec<ApiKey>> {
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let user_keys_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT * FROM api_keys
+                        WHERE user_id = $1 AND revoked_at IS NULL
+                        ORDER BY created_at DESC
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(&user_keys_stmt, &[&user_id]).await?
+        };
+
+        tokio::pin!(stream);
+        let mut keys = Vec::new();
+
+        while let Some(row) = stream.try_next().await? {
+            keys.push(ApiKey::from_row(&row));
+        }
+
+        Ok(keys)
+    }
+}
@@ -1,12 +1,21 @@
 use std::sync::Arc;
 
-use tokio_stream::StreamExt;
+use chrono::{DateTime, Utc};
+use postgres_types::ToSql;
+use prost::Message;
+use tokio_stream::{Stream, StreamExt};
+use tracing::warn;
 
 use crate::{
     cache::EntityCache,
     config::Database,
-    entity::{Championship, ChampionshipRelation, Race},
+    entity::{
+        Championship, ChampionshipDriver, ChampionshipInvitation, ChampionshipRelation,
+        ChampionshipRole, DriverTransfer, JoinRequest, OwnerTransfer, Race, SharedStandings,
+        Standing,
+    },
     error::AppResult,
+    structs::{protos::SessionResult, ChampionshipSearchQuery, SessionResultEntry, SessionType},
     utils::slice_iter,
 };
 
@@ -47,7 +56,7 @@ impl ChampionshipRepository {
                 .prepare_cached(
                     r#"
                         SELECT * FROM championships
-                        WHERE id = $1
+                        WHERE id = $1 AND deleted_at IS NULL
                     "#,
                 )
                 .await?;
@@ -66,6 +75,89 @@ impl ChampionshipRepository {
         }
     }
 
+    /// Finds a soft deleted championship by its ID.
+    ///
+    /// # Arguments
+    /// - `id`: The ID of the championship to find.
+    ///
+    /// # Returns
+    /// An Option containing the Championship if it's waiting to be purged.
+    pub async fn find_deleted(&self, id: i32) -> AppResult<Option<Arc<Championship>>> {
+        let conn = self.db.pg.get().await?;
+
+        let find_deleted_stmt = conn
+            .prepare_cached(
+                r#"
+                    SELECT * FROM championships
+                    WHERE id = $1 AND deleted_at IS NOT NULL
+                "#,
+            )
+            .await?;
+
+        let row = conn.query_opt(&find_deleted_stmt, &[&id]).await?;
+        Ok(row.map(|row| Championship::from_row_arc(&row)))
+    }
+
+    /// Retrieves the soft deleted championships of an owner.
+    ///
+    /// # Arguments
+    /// - `owner_id`: The ID of the owner.
+    ///
+    /// # Returns
+    /// A vector of championships that can still be restored.
+    pub async fn deleted_by_owner(&self, owner_id: i32) -> AppResult<Vec<Arc<Championship>>> {
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let deleted_by_owner_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT * FROM championships
+                        WHERE owner_id = $1 AND deleted_at IS NOT NULL
+                        ORDER BY deleted_at DESC
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(&deleted_by_owner_stmt, &[&owner_id]).await?
+        };
+
+        Championship::from_row_stream(stream).await
+    }
+
+    /// Retrieves the IDs of the championships deleted before a date.
+    ///
+    /// # Arguments
+    /// - `before`: The date the championships must have been deleted before.
+    ///
+    /// # Returns
+    /// A vector of championship IDs ready to be purged.
+    pub async fn deleted_before(&self, before: DateTime<Utc>) -> AppResult<Vec<i32>> {
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let deleted_before_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT id FROM championships
+                        WHERE deleted_at <= $1
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(&deleted_before_stmt, &[&before]).await?
+        };
+
+        tokio::pin!(stream);
+        let mut championships = Vec::new();
+
+        while let Some(row) = stream.try_next().await? {
+            championships.push(row.get(0));
+        }
+
+        Ok(championships)
+    }
+
     pub async fn races(&self, id: i32) -> AppResult<Vec<Arc<Race>>> {
         if let Some(races) = self.db.cache.championship.get_races(&id) {
             return Ok(races);
@@ -98,6 +190,58 @@ impl ChampionshipRepository {
         Ok(races)
     }
 
+    /// Streams the effective results of every race session of a championship.
+    ///
+    /// # Arguments
+    /// - `id`: The ID of the championship.
+    ///
+    /// # Returns
+    /// A stream of session results ordered by race date. It holds its own
+    /// connection until dropped, so a slow reader never delays other queries.
+    pub async fn results_stream(
+        &self,
+        id: i32,
+    ) -> AppResult<impl Stream<Item = AppResult<SessionResultEntry>>> {
+        let conn = self.db.pg.get().await?;
+
+        let championship_results_stmt = conn
+            .prepare_cached(
+                r#"
+                    SELECT r.race_id, r.session_type, COALESCE(r.amended_data, r.data)
+                    FROM results r
+                    JOIN races ra ON ra.id = r.race_id
+                    WHERE ra.championship_id = $1
+                    ORDER BY ra.date, r.race_id, r.session_type
+                "#,
+            )
+            .await?;
+
+        let stream = conn.query_raw(&championship_results_stmt, &[&id]).await?;
+
+        // The connection stays out of the pool while the rows are read at the client's pace
+        Ok(stream.filter_map(move |row| {
+            let _conn = &conn;
+
+            let row = match row {
+                Ok(row) => row,
+                Err(e) => return Some(Err(e.into())),
+            };
+
+            match SessionResult::decode(row.get::<_, &[u8]>(2)) {
+                Ok(result) => Some(Ok(SessionResultEntry {
+                    race_id: row.get(0),
+                    session_type: row.get(1),
+                    result,
+                })),
+
+                Err(e) => {
+                    warn!("Error decoding session result: {}", e);
+                    None
+                }
+            }
+        }))
+    }
+
     /// Finds a championship by its name.
     ///
     /// # Arguments
@@ -128,7 +272,12 @@ impl ChampionshipRepository {
         match row {
             Some(ref row) => {
                 let championship = Championship::from_row_arc(row);
-                self.db.cache.championship.set(championship.clone());
+
+                // Deleted championships keep their name but can't be cached
+                if championship.deleted_at.is_none() {
+                    self.db.cache.championship.set(championship.clone());
+                }
+
                 Ok(Some(championship))
             }
 
@@ -136,6 +285,176 @@ impl ChampionshipRepository {
         }
     }
 
+    /// Searches the public championships.
+    ///
+    /// # Arguments
+    /// - `query`: Name and category filters with the page to retrieve.
+    ///
+    /// # Returns
+    /// The championships of the page and the total number of matches.
+    pub async fn search(
+        &self,
+        query: &ChampionshipSearchQuery,
+    ) -> AppResult<(Vec<Arc<Championship>>, i64)> {
+        // Wildcards in the name are matched literally
+        let name_pattern = query.name.as_ref().map(|name| {
+            let escaped = name
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+
+            format!("%{}%", escaped)
+        });
+
+        let offset = (query.page - 1) * query.per_page;
+
+        let (count_statement, statement, params) = {
+            let mut params_counter = 1u8;
+            let mut clauses = vec![
+                "visibility = 'Public'".to_owned(),
+                "deleted_at IS NULL".to_owned(),
+            ];
+            let mut params: Vec<&(dyn ToSql + Sync)> = Vec::with_capacity(4);
+
+            if let Some(name) = &name_pattern {
+                clauses.push(format!("name ILIKE ${} ESCAPE '\\'", params_counter));
+                params.push(name);
+                params_counter += 1;
+            }
+
+            if let Some(category) = &query.category {
+                clauses.push(format!("category = ${}", params_counter));
+                params.push(category);
+                params_counter += 1;
+            }
+
+            let where_clause = clauses.join(" AND ");
+
+            let count_statement =
+                format!("SELECT COUNT(*) FROM championships WHERE {where_clause}");
+            let statement = format!(
+                "SELECT * FROM championships WHERE {} ORDER BY created_at DESC LIMIT ${} OFFSET ${}",
+                where_clause,
+                params_counter,
+                params_counter + 1,
+            );
+
+            params.push(&query.per_page);
+            params.push(&offset);
+
+            (count_statement, statement, params)
+        };
+
+        // Counted apart from the page, so pages past the end still report the total
+        let filter_params = &params[..params.len() - 2];
+
+        let (count_row, rows) = {
+            let conn = self.db.pg.get().await?;
+
+            tokio::try_join!(
+                conn.query_one(&count_statement, filter_params),
+                conn.query(&statement, &params)
+            )?
+        };
+
+        let total = count_row.get(0);
+        let championships = rows.iter().map(Championship::from_row_arc).collect();
+
+        Ok((championships, total))
+    }
+
+    /// Finds the pending ownership transfer of a championship.
+    ///
+    /// # Arguments
+    /// - `id`: The ID of the championship.
+    ///
+    /// # Returns
+    /// The pending transfer if one exists.
+    pub async fn pending_owner_transfer(&self, id: i32) -> AppResult<Option<OwnerTransfer>> {
+        let conn = self.db.pg.get().await?;
+
+        let pending_owner_transfer_stmt = conn
+            .prepare_cached(
+                r#"
+                    SELECT * FROM championship_owner_transfers
+                    WHERE championship_id = $1 AND status = 'Pending'
+                "#,
+            )
+            .await?;
+
+        let row = conn.query_opt(&pending_owner_transfer_stmt, &[&id]).await?;
+
+        Ok(row.map(|row| OwnerTransfer::from_row(&row)))
+    }
+
+    /// Retrieves the ownership transfers of a championship.
+    ///
+    /// # Arguments
+    /// - `id`: The ID of the championship.
+    ///
+    /// # Returns
+    /// A vector with every ownership transfer, newest first.
+    pub async fn owner_transfers(&self, id: i32) -> AppResult<Vec<OwnerTransfer>> {
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let owner_transfers_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT * FROM championship_owner_transfers
+                        WHERE championship_id = $1
+                        ORDER BY created_at DESC
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(&owner_transfers_stmt, &[&id]).await?
+        };
+
+        tokio::pin!(stream);
+        let mut transfers = Vec::new();
+
+        while let Some(row) = stream.try_next().await? {
+            transfers.push(OwnerTransfer::from_row(&row));
+        }
+
+        Ok(transfers)
+    }
+
+    /// Retrieves the pending join requests of a championship.
+    ///
+    /// # Arguments
+    /// - `id`: The ID of the championship.
+    ///
+    /// # Returns
+    /// A vector of pending join requests, oldest first.
+    pub async fn join_requests(&self, id: i32) -> AppResult<Vec<JoinRequest>> {
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let join_requests_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT * FROM championship_join_requests
+                        WHERE championship_id = $1 AND status = 'Pending'
+                        ORDER BY created_at
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(&join_requests_stmt, &[&id]).await?
+        };
+
+        tokio::pin!(stream);
+        let mut requests = Vec::new();
+
+        while let Some(row) = stream.try_next().await? {
+            requests.push(JoinRequest::from_row(&row));
+        }
+
+        Ok(requests)
+    }
+
     /// Retrieves user IDs associated with a championship.
     ///
     /// # Arguments
@@ -181,8 +500,18 @@ impl ChampionshipRepository {
             let user_rel_stmt = conn
                 .prepare_cached(
                     r#"
-                        SELECT role, team_id FROM championship_users
-                        WHERE championship_id = $1 AND user_id = $2
+                        SELECT
+                            cu.role,
+                            ARRAY_REMOVE(ARRAY_AGG(cet.team_id ORDER BY cet.team_id), NULL)
+                        FROM
+                            championship_users cu
+                        LEFT JOIN
+                            championship_engineer_teams cet
+                            ON cet.championship_id = cu.championship_id AND cet.user_id = cu.user_id
+                        WHERE
+                            cu.championship_id = $1 AND cu.user_id = $2
+                        GROUP BY
+                            cu.role
                     "#,
                 )
                 .await?;
@@ -193,21 +522,170 @@ impl ChampionshipRepository {
         match row {
             Some(row) => Ok(Some(ChampionshipRelation {
                 role: row.get(0),
-                team_id: row.get(1),
+                team_ids: row.get(1),
             })),
 
             None => Ok(None),
         }
     }
 
-    pub async fn drivers_linked(&self, id: i32) -> AppResult<Vec<String>> {
+    /// Finds an invitation by its ID.
+    ///
+    /// # Arguments
+    /// - `invitation_id`: The ID of the invitation.
+    ///
+    /// # Returns
+    /// An Option containing the invitation if found.
+    pub async fn find_invitation(
+        &self,
+        invitation_id: i32,
+    ) -> AppResult<Option<ChampionshipInvitation>> {
+        let conn = self.db.pg.get().await?;
+
+        let find_invitation_stmt = conn
+            .prepare_cached(
+                r#"
+                    SELECT i.*, c.name FROM championship_invitations i
+                    JOIN championships c ON c.id = i.championship_id
+                    WHERE i.id = $1
+                "#,
+            )
+            .await?;
+
+        let row = conn
+            .query_opt(&find_invitation_stmt, &[&invitation_id])
+            .await?;
+        Ok(row.as_ref().map(ChampionshipInvitation::from_row))
+    }
+
+    /// Retrieves the open invitations sent to an email.
+    ///
+    /// # Arguments
+    /// - `email`: The email the invitations were sent to.
+    ///
+    /// # Returns
+    /// A vector of pending and not expired invitations.
+    pub async fn invitations(&self, email: &str) -> AppResult<Vec<ChampionshipInvitation>> {
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let invitations_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT i.*, c.name FROM championship_invitations i
+                        JOIN championships c ON c.id = i.championship_id
+                        WHERE i.email = $1 AND i.status = 'Pending'
+                            AND i.expires_at > CURRENT_TIMESTAMP
+                        ORDER BY i.created_at DESC
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(&invitations_stmt, &[&email]).await?
+        };
+
+        tokio::pin!(stream);
+        let mut invitations = Vec::new();
+
+        while let Some(row) = stream.try_next().await? {
+            invitations.push(ChampionshipInvitation::from_row(&row));
+        }
+
+        Ok(invitations)
+    }
+
+    /// Checks if the user is an admin of the championship.
+    pub async fn is_admin(&self, id: i32, user_id: i32) -> AppResult<bool> {
+        let relation = self.user_relation(id, user_id).await?;
+        Ok(relation.is_some_and(|relation| relation.role == ChampionshipRole::Admin))
+    }
+
+    /// Checks if the user can review incidents as a steward of the championship.
+    pub async fn is_steward(&self, id: i32, user_id: i32) -> AppResult<bool> {
+        let relation = self.user_relation(id, user_id).await?;
+
+        Ok(relation.is_some_and(|relation| {
+            matches!(
+                relation.role,
+                ChampionshipRole::Admin | ChampionshipRole::RaceDirector
+            )
+        }))
+    }
+
+    /// Computes the drivers standings of a championship from its race results.
+    ///
+    /// # Arguments
+    /// - `id`: The ID of the championship.
+    ///
+    /// # Returns
+    /// The standings sorted by points.
+    pub async fn standings(&self, id: i32) -> AppResult<SharedStandings> {
+        if let Some(standings) = self.db.cache.championship.get_standings(&id) {
+            return Ok(standings);
+        }
+
+        let race_sessions = [
+            SessionType::R as i16,
+            SessionType::R2 as i16,
+            SessionType::R3 as i16,
+        ];
+
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let championship_results_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT COALESCE(r.amended_data, r.data) FROM results r
+                        JOIN races ra ON ra.id = r.race_id
+                        WHERE ra.championship_id = $1 AND r.session_type = ANY($2)
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(
+                &championship_results_stmt,
+                slice_iter(&[&id, &race_sessions.as_slice()]),
+            )
+            .await?
+        };
+
+        tokio::pin!(stream);
+        let mut results = Vec::new();
+
+        while let Some(row) = stream.try_next().await? {
+            let data: &[u8] = row.get(0);
+
+            match SessionResult::decode(data) {
+                Ok(result) => results.push(result),
+                Err(e) => warn!("Error decoding session result: {}", e),
+            }
+        }
+
+        let standings = Arc::new(Standing::from_results(results.iter()));
+        self.db
+            .cache
+            .championship
+            .set_standings(id, standings.clone());
+
+        Ok(standings)
+    }
+
+    /// Retrieves the drivers registered in a championship.
+    ///
+    /// # Arguments
+    /// - `id`: The ID of the championship.
+    ///
+    /// # Returns
+    /// A vector with the team and number of each driver.
+    pub async fn drivers_linked(&self, id: i32) -> AppResult<Vec<ChampionshipDriver>> {
         let stream = {
             let conn = self.db.pg.get().await?;
 
             let linked_drivers_stmt = conn
                 .prepare_cached(
                     r#"
-                        SELECT steam_name
+                        SELECT steam_name, team_id, number
                         FROM championship_drivers
                         WHERE championship_id = $1
                     "#,
@@ -221,12 +699,84 @@ impl ChampionshipRepository {
         let mut drivers = Vec::new();
 
         while let Some(row) = stream.try_next().await? {
-            drivers.push(row.get(0));
+            drivers.push(ChampionshipDriver {
+                steam_name: row.get(0),
+                team_id: row.get(1),
+                number: row.get(2),
+            });
         }
 
         Ok(drivers)
     }
 
+    /// Retrieves the driver transfers waiting for approval.
+    ///
+    /// # Arguments
+    /// - `id`: The ID of the championship.
+    ///
+    /// # Returns
+    /// A vector of pending transfers, oldest first.
+    pub async fn pending_transfers(&self, id: i32) -> AppResult<Vec<DriverTransfer>> {
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let pending_transfers_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT * FROM driver_transfers
+                        WHERE championship_id = $1 AND status = 'Pending'
+                        ORDER BY created_at
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(&pending_transfers_stmt, &[&id]).await?
+        };
+
+        tokio::pin!(stream);
+        let mut transfers = Vec::new();
+
+        while let Some(row) = stream.try_next().await? {
+            transfers.push(DriverTransfer::from_row(&row));
+        }
+
+        Ok(transfers)
+    }
+
+    /// Retrieves the resolved driver transfers of a championship.
+    ///
+    /// # Arguments
+    /// - `id`: The ID of the championship.
+    ///
+    /// # Returns
+    /// A vector of approved and rejected transfers, newest first.
+    pub async fn transfer_history(&self, id: i32) -> AppResult<Vec<DriverTransfer>> {
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let transfer_history_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT * FROM driver_transfers
+                        WHERE championship_id = $1 AND status <> 'Pending'
+                        ORDER BY resolved_at DESC
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(&transfer_history_stmt, &[&id]).await?
+        };
+
+        tokio::pin!(stream);
+        let mut transfers = Vec::new();
+
+        while let Some(row) = stream.try_next().await? {
+            transfers.push(DriverTransfer::from_row(&row));
+        }
+
+        Ok(transfers)
+    }
+
     /// Retrieves all used championship IDs.
     ///
     /// This method should only be called once.
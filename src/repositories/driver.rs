@@ -1,6 +1,16 @@
 use std::sync::Arc;
 
-use crate::{config::Database, entity::Driver, error::AppResult};
+use prost::Message;
+use tokio_stream::StreamExt;
+use tracing::warn;
+
+use crate::{
+    config::Database,
+    entity::{Championship, Driver, DriverClaim, DriverStats, SharedDriverStats},
+    error::AppResult,
+    structs::{protos::SessionResult, SessionType},
+    utils::slice_iter,
+};
 
 pub struct DriverRepository {
     db: &'static Database,
@@ -41,4 +51,192 @@ impl DriverRepository {
             None => Ok(None),
         }
     }
+
+    /// Retrieves the championships a driver has raced in, private ones only if
+    /// `user_id` is part of them.
+    pub async fn championships(
+        &self,
+        steam_name: &str,
+        user_id: i32,
+    ) -> AppResult<Vec<Arc<Championship>>> {
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let driver_championships_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT c.* FROM championships c
+                        JOIN championship_drivers cd ON c.id = cd.championship_id
+                        WHERE cd.steam_name = $1 AND c.deleted_at IS NULL AND (
+                            c.visibility <> 'Private' OR EXISTS (
+                                SELECT 1 FROM championship_users cu
+                                WHERE cu.championship_id = c.id AND cu.user_id = $2
+                            )
+                        )
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(
+                &driver_championships_stmt,
+                slice_iter(&[&steam_name, &user_id]),
+            )
+            .await?
+        };
+
+        Championship::from_row_stream(stream).await
+    }
+
+    /// Computes the career statistics of a driver from the stored race results.
+    pub async fn stats(&self, steam_name: &str) -> AppResult<SharedDriverStats> {
+        if let Some(stats) = self.db.cache.driver.get_stats(steam_name) {
+            return Ok(stats);
+        }
+
+        let race_sessions = [
+            SessionType::R as i16,
+            SessionType::R2 as i16,
+            SessionType::R3 as i16,
+        ];
+
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let driver_results_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT COALESCE(r.amended_data, r.data) FROM results r
+                        JOIN races ra ON ra.id = r.race_id
+                        JOIN championship_drivers cd ON cd.championship_id = ra.championship_id
+                        WHERE cd.steam_name = $1 AND r.session_type = ANY($2)
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(
+                &driver_results_stmt,
+                slice_iter(&[&steam_name, &race_sessions.as_slice()]),
+            )
+            .await?
+        };
+
+        tokio::pin!(stream);
+        let mut stats = DriverStats::default();
+
+        while let Some(row) = stream.try_next().await? {
+            let data: &[u8] = row.get(0);
+
+            match SessionResult::decode(data) {
+                Ok(result) => stats.add_result(steam_name, &result),
+                Err(e) => warn!("Error decoding session result: {}", e),
+            }
+        }
+
+        let stats = Arc::new(stats);
+        self.db.cache.driver.set_stats(steam_name, stats.clone());
+
+        Ok(stats)
+    }
+
+    /// Finds the driver linked to a user.
+    pub async fn find_by_user(&self, user_id: i32) -> AppResult<Option<Arc<Driver>>> {
+        let row = {
+            let conn = self.db.pg.get().await?;
+
+            let find_by_user_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT * FROM drivers
+                        WHERE user_id = $1
+                    "#,
+                )
+                .await?;
+
+            conn.query_opt(&find_by_user_stmt, &[&user_id]).await?
+        };
+
+        match row {
+            Some(ref row) => {
+                let driver = Driver::from_row_arc(row);
+                self.db.cache.driver.set(driver.clone());
+                Ok(Some(driver))
+            }
+
+            None => Ok(None),
+        }
+    }
+
+    /// Finds a steam name claim by its ID.
+    pub async fn find_claim(&self, id: i32) -> AppResult<Option<DriverClaim>> {
+        let conn = self.db.pg.get().await?;
+
+        let find_claim_stmt = conn
+            .prepare_cached(
+                r#"
+                    SELECT * FROM driver_claims
+                    WHERE id = $1
+                "#,
+            )
+            .await?;
+
+        let row = conn.query_opt(&find_claim_stmt, &[&id]).await?;
+        Ok(row.as_ref().map(DriverClaim::from_row))
+    }
+
+    /// Retrieves the claims made by a user.
+    pub async fn user_claims(&self, user_id: i32) -> AppResult<Vec<DriverClaim>> {
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let user_claims_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT * FROM driver_claims
+                        WHERE user_id = $1
+                        ORDER BY created_at DESC
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(&user_claims_stmt, &[&user_id]).await?
+        };
+
+        tokio::pin!(stream);
+        let mut claims = Vec::new();
+
+        while let Some(row) = stream.try_next().await? {
+            claims.push(DriverClaim::from_row(&row));
+        }
+
+        Ok(claims)
+    }
+
+    /// Retrieves the pending claims of a championship, waiting for its admins to resolve them.
+    pub async fn championship_claims(&self, championship_id: i32) -> AppResult<Vec<DriverClaim>> {
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let championship_claims_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT * FROM driver_claims
+                        WHERE championship_id = $1 AND status = 'Pending'
+                        ORDER BY created_at
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(&championship_claims_stmt, &[&championship_id])
+                .await?
+        };
+
+        tokio::pin!(stream);
+        let mut claims = Vec::new();
+
+        while let Some(row) = stream.try_next().await? {
+            claims.push(DriverClaim::from_row(&row));
+        }
+
+        Ok(claims)
+    }
 }
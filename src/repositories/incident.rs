@@ -0,0 +1,128 @@
+use tokio_stream::StreamExt;
+
+use crate::{
+    config::Database,
+    entity::{Incident, IncidentComment},
+    error::AppResult,
+};
+
+/// Repository for the incidents recorded during championship races.
+pub struct IncidentRepository {
+    db: &'static Database,
+}
+
+impl IncidentRepository {
+    /// Creates a new IncidentRepository instance.
+    ///
+    /// # Arguments
+    /// - `db`: Database connection.
+    ///
+    /// # Returns
+    /// A new IncidentRepository instance.
+    pub fn new(db: &'static Database) -> Self {
+        Self { db }
+    }
+
+    /// Finds an incident of a championship.
+    ///
+    /// # Arguments
+    /// - `championship_id`: The ID of the championship.
+    /// - `incident_id`: The ID of the incident.
+    ///
+    /// # Returns
+    /// An Option containing the Incident if found.
+    pub async fn find(
+        &self,
+        championship_id: i32,
+        incident_id: i32,
+    ) -> AppResult<Option<Incident>> {
+        let conn = self.db.pg.get().await?;
+
+        let find_incident_stmt = conn
+            .prepare_cached(
+                r#"
+                    SELECT i.* FROM incidents i
+                    JOIN races r ON r.id = i.race_id
+                    WHERE i.id = $1 AND r.championship_id = $2
+                "#,
+            )
+            .await?;
+
+        let row = conn
+            .query_opt(&find_incident_stmt, &[&incident_id, &championship_id])
+            .await?;
+
+        Ok(row.as_ref().map(Incident::from_row))
+    }
+
+    /// Retrieves the incidents recorded in the races of a championship.
+    ///
+    /// # Arguments
+    /// - `championship_id`: The ID of the championship.
+    ///
+    /// # Returns
+    /// A vector of incidents, newest first.
+    pub async fn championship_incidents(&self, championship_id: i32) -> AppResult<Vec<Incident>> {
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let championship_incidents_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT i.* FROM incidents i
+                        JOIN races r ON r.id = i.race_id
+                        WHERE r.championship_id = $1
+                        ORDER BY i.created_at DESC
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(&championship_incidents_stmt, &[&championship_id])
+                .await?
+        };
+
+        tokio::pin!(stream);
+        let mut incidents = Vec::new();
+
+        while let Some(row) = stream.try_next().await? {
+            incidents.push(Incident::from_row(&row));
+        }
+
+        Ok(incidents)
+    }
+
+    /// Retrieves the steward comments of an incident.
+    ///
+    /// # Arguments
+    /// - `incident_id`: The ID of the incident.
+    ///
+    /// # Returns
+    /// A vector of comments, oldest first.
+    pub async fn comments(&self, incident_id: i32) -> AppResult<Vec<IncidentComment>> {
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let incident_comments_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT * FROM incident_comments
+                        WHERE incident_id = $1
+                        ORDER BY created_at
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(&incident_comments_stmt, &[&incident_id])
+                .await?
+        };
+
+        tokio::pin!(stream);
+        let mut comments = Vec::new();
+
+        while let Some(row) = stream.try_next().await? {
+            comments.push(IncidentComment::from_row(&row));
+        }
+
+        Ok(comments)
+    }
+}
@@ -1,11 +1,21 @@
+pub(crate) use api_key::*;
 pub(crate) use championship::*;
-pub(crate) use discord::*;
 pub(crate) use driver::*;
+pub(crate) use incident::*;
+pub(crate) use oauth::*;
+pub(crate) use result::*;
 pub(crate) use server::*;
+pub(crate) use session::*;
+pub(crate) use two_factor::*;
 pub(crate) use user::*;
 
+mod api_key;
 mod championship;
-mod discord;
 mod driver;
+mod incident;
+mod oauth;
+mod result;
 mod server;
+mod session;
+mod two_factor;
 mod user;
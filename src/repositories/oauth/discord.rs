@@ -0,0 +1,101 @@
+use dotenvy::var;
+use reqwest::{Client, Url};
+
+use crate::{
+    config::constants::*,
+    entity::Provider,
+    error::{AppResult, OAuthError},
+    structs::{DiscordUserInfo, OAuthAccount, OAuthCallback, OAuthTokenRequest},
+};
+
+use super::{exchange_code, user_info, OAuthProvider};
+
+/// Discord OAuth2 client.
+pub struct DiscordProvider {
+    client_id: &'static str,
+    client_secret: &'static str,
+    redirect_uri: &'static str,
+    client: Client,
+}
+
+impl DiscordProvider {
+    /// Creates a new DiscordProvider from the environment.
+    ///
+    /// # Panics
+    /// if any of the required environment variables are missing.
+    pub fn new(client: Client) -> Self {
+        DiscordProvider {
+            client_id: var("DISCORD_CLIENT_ID")
+                .expect("Missing DISCORD_CLIENT_ID")
+                .leak(),
+
+            client_secret: var("DISCORD_CLIENT_SECRET")
+                .expect("Missing DISCORD_CLIENT_SECRET")
+                .leak(),
+
+            redirect_uri: var("DISCORD_REDIRECT_URI")
+                .expect("Missing DISCORD_REDIRECT_URI")
+                .leak(),
+
+            client,
+        }
+    }
+}
+
+impl OAuthProvider for DiscordProvider {
+    fn authorize_url(&self, state: &str, code_challenge: &str) -> String {
+        Url::parse_with_params(
+            DISCORD_AUTHORIZE_URL,
+            &[
+                ("client_id", self.client_id),
+                ("response_type", "code"),
+                ("redirect_uri", self.redirect_uri),
+                ("scope", DISCORD_SCOPES),
+                ("state", state),
+                ("code_challenge", code_challenge),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+        .map(String::from)
+        .unwrap_or_default()
+    }
+
+    async fn account(
+        &self,
+        callback: &OAuthCallback,
+        code_verifier: &str,
+    ) -> AppResult<OAuthAccount> {
+        let Some(code) = &callback.code else {
+            Err(OAuthError::ExchangeFailed)?
+        };
+
+        let access_token = exchange_code(
+            &self.client,
+            &format!("{DISCORD_API_URL}/oauth2/token"),
+            &OAuthTokenRequest {
+                client_id: self.client_id,
+                client_secret: self.client_secret,
+                grant_type: "authorization_code",
+                code,
+                redirect_uri: self.redirect_uri,
+                code_verifier,
+            },
+        )
+        .await?;
+
+        let discord_info: DiscordUserInfo = user_info(
+            &self.client,
+            &format!("{DISCORD_API_URL}/users/@me"),
+            &access_token,
+        )
+        .await?;
+
+        Ok(OAuthAccount {
+            provider: Provider::Discord,
+            provider_user_id: discord_info.id.to_string(),
+            avatar: discord_info.avatar_url(),
+            email: discord_info.email.filter(|_| discord_info.verified),
+            username: discord_info.username,
+        })
+    }
+}
@@ -0,0 +1,97 @@
+use dotenvy::var;
+use reqwest::{Client, Url};
+
+use crate::{
+    config::constants::*,
+    entity::Provider,
+    error::{AppResult, OAuthError},
+    structs::{GoogleUserInfo, OAuthAccount, OAuthCallback, OAuthTokenRequest},
+};
+
+use super::{exchange_code, user_info, OAuthProvider};
+
+/// Google OpenID Connect client.
+pub struct GoogleProvider {
+    client_id: &'static str,
+    client_secret: &'static str,
+    redirect_uri: &'static str,
+    client: Client,
+}
+
+impl GoogleProvider {
+    /// Creates a new GoogleProvider from the environment.
+    ///
+    /// # Returns
+    /// None if any of the Google environment variables is missing.
+    pub fn from_env(client: Client) -> Option<Self> {
+        Some(GoogleProvider {
+            client_id: var("GOOGLE_CLIENT_ID").ok()?.leak(),
+            client_secret: var("GOOGLE_CLIENT_SECRET").ok()?.leak(),
+            redirect_uri: var("GOOGLE_REDIRECT_URI").ok()?.leak(),
+            client,
+        })
+    }
+}
+
+impl OAuthProvider for GoogleProvider {
+    fn authorize_url(&self, state: &str, code_challenge: &str) -> String {
+        Url::parse_with_params(
+            GOOGLE_AUTHORIZE_URL,
+            &[
+                ("client_id", self.client_id),
+                ("response_type", "code"),
+                ("redirect_uri", self.redirect_uri),
+                ("scope", GOOGLE_SCOPES),
+                ("state", state),
+                ("code_challenge", code_challenge),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+        .map(String::from)
+        .unwrap_or_default()
+    }
+
+    async fn account(
+        &self,
+        callback: &OAuthCallback,
+        code_verifier: &str,
+    ) -> AppResult<OAuthAccount> {
+        let Some(code) = &callback.code else {
+            Err(OAuthError::ExchangeFailed)?
+        };
+
+        let access_token = exchange_code(
+            &self.client,
+            GOOGLE_TOKEN_URL,
+            &OAuthTokenRequest {
+                client_id: self.client_id,
+                client_secret: self.client_secret,
+                grant_type: "authorization_code",
+                code,
+                redirect_uri: self.redirect_uri,
+                code_verifier,
+            },
+        )
+        .await?;
+
+        let google_info: GoogleUserInfo =
+            user_info(&self.client, GOOGLE_USERINFO_URL, &access_token).await?;
+
+        let email = google_info.email.filter(|_| google_info.email_verified);
+
+        Ok(OAuthAccount {
+            provider: Provider::Google,
+            username: google_info
+                .name
+                .or_else(|| {
+                    email
+                        .as_ref()
+                        .and_then(|email| email.split('@').next().map(str::to_owned))
+                })
+                .unwrap_or_else(|| google_info.sub.clone()),
+            provider_user_id: google_info.sub,
+            avatar: google_info.picture,
+            email,
+        })
+    }
+}
@@ -0,0 +1,184 @@
+use base64_simd::URL_SAFE_NO_PAD;
+use reqwest::Client;
+use ring::digest::{digest, SHA256};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    entity::Provider,
+    error::{AppResult, OAuthError},
+    structs::{OAuthAccount, OAuthCallback, OAuthTokenRequest, OAuthTokenResponse},
+};
+
+pub(crate) use discord::DiscordProvider;
+pub(crate) use google::GoogleProvider;
+pub(crate) use steam::SteamProvider;
+
+mod discord;
+mod google;
+mod steam;
+
+/// Client of an external login provider.
+pub trait OAuthProvider {
+    /// Builds the URL the user is sent to, to authorize the login.
+    ///
+    /// # Arguments
+    /// - `state`: Opaque value the provider returns to the callback.
+    /// - `code_challenge`: PKCE challenge of the code verifier, ignored by providers without PKCE.
+    fn authorize_url(&self, state: &str, code_challenge: &str) -> String;
+
+    /// Exchanges the redirect back from the provider for the account of the user.
+    ///
+    /// # Arguments
+    /// - `callback`: Query of the redirect back from the provider.
+    /// - `code_verifier`: PKCE verifier stored with the state.
+    ///
+    /// # Returns
+    /// The account of the user on the provider.
+    async fn account(
+        &self,
+        callback: &OAuthCallback,
+        code_verifier: &str,
+    ) -> AppResult<OAuthAccount>;
+}
+
+/// Repository for the external login providers.
+///
+/// Discord is always configured, Google and Steam are only enabled when their
+/// environment variables are set.
+pub struct OAuthRepository {
+    discord: DiscordProvider,
+    google: Option<GoogleProvider>,
+    steam: Option<SteamProvider>,
+}
+
+impl OAuthRepository {
+    /// Creates a new OAuthRepository instance.
+    ///
+    /// # Panics
+    /// if the Discord environment variables are missing.
+    ///
+    /// # Returns
+    /// A new OAuthRepository instance.
+    pub fn new() -> Self {
+        let client = Client::new();
+
+        Self {
+            discord: DiscordProvider::new(client.clone()),
+            google: GoogleProvider::from_env(client.clone()),
+            steam: SteamProvider::from_env(client),
+        }
+    }
+
+    /// Builds the authorization URL of a provider.
+    ///
+    /// # Arguments
+    /// - `provider`: The provider to sign in with.
+    /// - `state`: Opaque value the provider returns to the callback.
+    /// - `code_challenge`: PKCE challenge of the code verifier.
+    ///
+    /// # Returns
+    /// The URL to redirect the user to, or an error if the provider is disabled.
+    pub fn authorize_url(
+        &self,
+        provider: Provider,
+        state: &str,
+        code_challenge: &str,
+    ) -> AppResult<String> {
+        let url = match provider {
+            Provider::Discord => self.discord.authorize_url(state, code_challenge),
+            Provider::Google => Self::enabled(&self.google)?.authorize_url(state, code_challenge),
+            Provider::Steam => Self::enabled(&self.steam)?.authorize_url(state, code_challenge),
+            Provider::Local => Err(OAuthError::ProviderDisabled)?,
+        };
+
+        Ok(url)
+    }
+
+    /// Retrieves the account of a user from the redirect back from a provider.
+    ///
+    /// # Arguments
+    /// - `provider`: The provider the user signed in with.
+    /// - `callback`: Query of the redirect back from the provider.
+    /// - `code_verifier`: PKCE verifier stored with the state.
+    ///
+    /// # Returns
+    /// The account of the user on the provider.
+    pub async fn account(
+        &self,
+        provider: Provider,
+        callback: &OAuthCallback,
+        code_verifier: &str,
+    ) -> AppResult<OAuthAccount> {
+        match provider {
+            Provider::Discord => self.discord.account(callback, code_verifier).await,
+            Provider::Google => {
+                Self::enabled(&self.google)?
+                    .account(callback, code_verifier)
+                    .await
+            }
+            Provider::Steam => {
+                Self::enabled(&self.steam)?
+                    .account(callback, code_verifier)
+                    .await
+            }
+            Provider::Local => Err(OAuthError::ProviderDisabled)?,
+        }
+    }
+
+    #[inline]
+    fn enabled<T>(provider: &Option<T>) -> AppResult<&T> {
+        provider
+            .as_ref()
+            .ok_or_else(|| OAuthError::ProviderDisabled.into())
+    }
+}
+
+/// PKCE S256 challenge of a code verifier (RFC 7636).
+#[inline]
+pub fn code_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode_to_string(digest(&SHA256, code_verifier.as_bytes()))
+}
+
+/// Exchanges an authorization code at the token endpoint of a provider.
+async fn exchange_code(
+    client: &Client,
+    token_url: &str,
+    request: &OAuthTokenRequest<'_>,
+) -> AppResult<String> {
+    let response = client.post(token_url).form(request).send().await?;
+
+    if !response.status().is_success() {
+        Err(OAuthError::ExchangeFailed)?
+    }
+
+    Ok(response.json::<OAuthTokenResponse>().await?.access_token)
+}
+
+/// Fetches the profile of the user with the access token of a provider.
+async fn user_info<T: DeserializeOwned>(
+    client: &Client,
+    url: &str,
+    access_token: &str,
+) -> AppResult<T> {
+    let response = client.get(url).bearer_auth(access_token).send().await?;
+
+    if !response.status().is_success() {
+        Err(OAuthError::ExchangeFailed)?
+    }
+
+    Ok(response.json::<T>().await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_challenge_matches_rfc_vector() {
+        // RFC 7636 appendix B
+        assert_eq!(
+            code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+}
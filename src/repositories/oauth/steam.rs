@@ -0,0 +1,154 @@
+use dotenvy::var;
+use reqwest::{Client, Url};
+
+use crate::{
+    config::constants::*,
+    entity::Provider,
+    error::{AppResult, OAuthError},
+    structs::{OAuthAccount, OAuthCallback},
+};
+
+use super::OAuthProvider;
+
+/// Steam OpenID 2.0 client.
+///
+/// Steam has no OAuth2 login, the user is sent back with a signed assertion
+/// that is verified against Steam. PKCE doesn't apply and the `state` travels
+/// in the return URL, which is part of the signed assertion.
+pub struct SteamProvider {
+    return_to: &'static str,
+    realm: &'static str,
+    client: Client,
+}
+
+impl SteamProvider {
+    /// Creates a new SteamProvider from the environment.
+    ///
+    /// # Returns
+    /// None if `STEAM_RETURN_URI` is missing or not a valid URL.
+    pub fn from_env(client: Client) -> Option<Self> {
+        let return_to = var("STEAM_RETURN_URI").ok()?;
+        let realm = Url::parse(&return_to).ok()?.origin().ascii_serialization();
+
+        Some(SteamProvider {
+            return_to: return_to.leak(),
+            realm: realm.leak(),
+            client,
+        })
+    }
+
+    /// URL Steam sends the user back to, carrying the state.
+    #[inline]
+    fn return_url(&self, state: &str) -> Option<Url> {
+        Url::parse_with_params(self.return_to, &[("state", state)]).ok()
+    }
+
+    /// Extracts the 64-bit Steam ID from a claimed identifier.
+    fn steam_id(claimed_id: &str) -> Option<&str> {
+        claimed_id
+            .strip_prefix(STEAM_CLAIMED_ID_PREFIX)
+            .filter(|id| !id.is_empty() && id.len() <= 20 && id.bytes().all(|c| c.is_ascii_digit()))
+    }
+}
+
+impl OAuthProvider for SteamProvider {
+    fn authorize_url(&self, state: &str, _code_challenge: &str) -> String {
+        let Some(return_to) = self.return_url(state) else {
+            return String::new();
+        };
+
+        Url::parse_with_params(
+            STEAM_OPENID_URL,
+            &[
+                ("openid.ns", OPENID_NS),
+                ("openid.mode", "checkid_setup"),
+                ("openid.return_to", return_to.as_str()),
+                ("openid.realm", self.realm),
+                ("openid.identity", OPENID_IDENTIFIER_SELECT),
+                ("openid.claimed_id", OPENID_IDENTIFIER_SELECT),
+            ],
+        )
+        .map(String::from)
+        .unwrap_or_default()
+    }
+
+    async fn account(
+        &self,
+        callback: &OAuthCallback,
+        _code_verifier: &str,
+    ) -> AppResult<OAuthAccount> {
+        let param = |name: &str| callback.params.get(name).map(String::as_str);
+
+        let Some(claimed_id) = param("openid.claimed_id") else {
+            Err(OAuthError::ExchangeFailed)?
+        };
+
+        if param("openid.mode") != Some("id_res")
+            || param("openid.op_endpoint") != Some(STEAM_OPENID_URL)
+            || param("openid.identity") != Some(claimed_id)
+            || self.return_url(&callback.state).as_ref().map(Url::as_str)
+                != param("openid.return_to")
+        {
+            Err(OAuthError::ExchangeFailed)?
+        }
+
+        let Some(steam_id) = Self::steam_id(claimed_id) else {
+            Err(OAuthError::ExchangeFailed)?
+        };
+
+        // Steam checks the signature of the assertion it issued
+        let verification = callback
+            .params
+            .iter()
+            .filter(|(name, _)| name.starts_with("openid.") && *name != "openid.mode")
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .chain([("openid.mode", "check_authentication")])
+            .collect::<Vec<_>>();
+
+        let response = self
+            .client
+            .post(STEAM_OPENID_URL)
+            .form(&verification)
+            .send()
+            .await?;
+
+        if !response.status().is_success()
+            || !response
+                .text()
+                .await?
+                .lines()
+                .any(|line| line.trim() == "is_valid:true")
+        {
+            Err(OAuthError::ExchangeFailed)?
+        }
+
+        Ok(OAuthAccount {
+            provider: Provider::Steam,
+            provider_user_id: steam_id.to_owned(),
+            username: steam_id.to_owned(),
+            email: None,
+            avatar: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steam_id_from_claimed_id() {
+        assert_eq!(
+            SteamProvider::steam_id("https://steamcommunity.com/openid/id/76561197960287930"),
+            Some("76561197960287930")
+        );
+        assert_eq!(
+            SteamProvider::steam_id("https://example.com/openid/id/76561197960287930"),
+            None
+        );
+        assert_eq!(
+            SteamProvider::steam_id("https://steamcommunity.com/openid/id/7656/../1"),
+            None
+        );
+    }
+}
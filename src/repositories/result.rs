@@ -0,0 +1,117 @@
+use prost::Message;
+use tokio_stream::StreamExt;
+use tracing::warn;
+
+use crate::{
+    config::Database, entity::ResultAmendment, error::AppResult, structs::protos::SessionResult,
+    utils::slice_iter,
+};
+
+/// Repository for the stored race session results.
+pub struct ResultRepository {
+    db: &'static Database,
+}
+
+impl ResultRepository {
+    /// Creates a new ResultRepository instance.
+    ///
+    /// # Arguments
+    /// - `db`: Database connection.
+    ///
+    /// # Returns
+    /// A new ResultRepository instance.
+    pub fn new(db: &'static Database) -> Self {
+        Self { db }
+    }
+
+    /// Finds the effective classification of a race session, amendments included.
+    ///
+    /// # Arguments
+    /// - `championship_id`: The ID of the championship the race belongs to.
+    /// - `race_id`: The ID of the race.
+    /// - `session_type`: The session type of the result.
+    ///
+    /// # Returns
+    /// An Option containing the SessionResult if found.
+    pub async fn find(
+        &self,
+        championship_id: i32,
+        race_id: i32,
+        session_type: i16,
+    ) -> AppResult<Option<SessionResult>> {
+        let row = {
+            let conn = self.db.pg.get().await?;
+
+            let find_result_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT COALESCE(r.amended_data, r.data) FROM results r
+                        JOIN races ra ON ra.id = r.race_id
+                        WHERE r.race_id = $1 AND r.session_type = $2 AND ra.championship_id = $3
+                    "#,
+                )
+                .await?;
+
+            conn.query_opt(
+                &find_result_stmt,
+                &[&race_id, &session_type, &championship_id],
+            )
+            .await?
+        };
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        match SessionResult::decode(row.get::<_, &[u8]>(0)) {
+            Ok(result) => Ok(Some(result)),
+            Err(e) => {
+                warn!("Error decoding session result: {}", e);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Retrieves the amendments made to a race session result.
+    ///
+    /// # Arguments
+    /// - `race_id`: The ID of the race.
+    /// - `session_type`: The session type of the result.
+    ///
+    /// # Returns
+    /// A vector of amendments in the order they were applied.
+    pub async fn amendments(
+        &self,
+        race_id: i32,
+        session_type: i16,
+    ) -> AppResult<Vec<ResultAmendment>> {
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let result_amendments_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT * FROM result_amendments
+                        WHERE race_id = $1 AND session_type = $2
+                        ORDER BY id
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(
+                &result_amendments_stmt,
+                slice_iter(&[&race_id, &session_type]),
+            )
+            .await?
+        };
+
+        tokio::pin!(stream);
+        let mut amendments = Vec::new();
+
+        while let Some(row) = stream.try_next().await? {
+            amendments.push(ResultAmendment::from_row(&row));
+        }
+
+        Ok(amendments)
+    }
+}
@@ -0,0 +1,120 @@
+use chrono::Utc;
+use tokio_stream::StreamExt;
+
+use crate::{config::Database, entity::UserSession, error::AppResult};
+
+/// Repository for the devices holding refresh tokens of users.
+pub struct SessionRepository {
+    db: &'static Database,
+}
+
+impl SessionRepository {
+    /// Creates a new SessionRepository instance.
+    ///
+    /// # Arguments
+    /// - `db`: Database connection.
+    ///
+    /// # Returns
+    /// A new SessionRepository instance.
+    pub fn new(db: &'static Database) -> Self {
+        Self { db }
+    }
+
+    /// Finds the active session of a user on a device.
+    ///
+    /// # Arguments
+    /// - `user_id`: The ID of the user.
+    /// - `fingerprint`: Fingerprint of the client device.
+    ///
+    /// # Returns
+    /// An Option containing the session if found and not expired.
+    pub async fn find(&self, user_id: i32, fingerprint: &str) -> AppResult<Option<UserSession>> {
+        let conn = self.db.pg.get().await?;
+
+        let find_session_stmt = conn
+            .prepare_cached(
+                r#"
+                    SELECT * FROM user_sessions
+                    WHERE user_id = $1 AND fingerprint = $2 AND expires_at > NOW()
+                "#,
+            )
+            .await?;
+
+        let row = conn
+            .query_opt(&find_session_stmt, &[&user_id, &fingerprint])
+            .await?;
+
+        Ok(row.map(|row| UserSession::from_row(&row)))
+    }
+
+    /// Retrieves the hash of the refresh token of a user on a device.
+    ///
+    /// The token cache is checked first and filled from the database on a miss,
+    /// so sessions survive restarts and cache evictions.
+    ///
+    /// # Arguments
+    /// - `user_id`: The ID of the user.
+    /// - `fingerprint`: Fingerprint of the client device.
+    ///
+    /// # Returns
+    /// An Option containing the token hash if the session is active.
+    pub async fn token_hash(&self, user_id: i32, fingerprint: &str) -> AppResult<Option<String>> {
+        if let Some(token_hash) = self
+            .db
+            .cache
+            .token
+            .get_refresh_token(user_id, fingerprint.to_owned())
+        {
+            return Ok(Some(token_hash));
+        }
+
+        let Some(session) = self.find(user_id, fingerprint).await? else {
+            return Ok(None);
+        };
+
+        if let Ok(ttl) = (session.expires_at - Utc::now()).to_std() {
+            self.db.cache.token.set_refresh_token(
+                session.user_id,
+                session.fingerprint,
+                session.token_hash.clone(),
+                ttl,
+            );
+        }
+
+        Ok(Some(session.token_hash))
+    }
+
+    /// Retrieves the active sessions of a user.
+    ///
+    /// # Arguments
+    /// - `user_id`: The ID of the user.
+    ///
+    /// # Returns
+    /// A vector of sessions, most recently used first.
+    pub async fn user_sessions(&self, user_id: i32) -> AppResult<Vec<UserSession>> {
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let user_sessions_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT * FROM user_sessions
+                        WHERE user_id = $1 AND expires_at > NOW()
+                        ORDER BY last_used_at DESC
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(&user_sessions_stmt, &[&user_id]).await?
+        };
+
+        tokio::pin!(stream);
+        let mut sessions = Vec::new();
+
+        while let Some(row) = stream.try_next().await? {
+            sessions.push(UserSession::from_row(&row));
+        }
+
+        Ok(sessions)
+    }
+}
@@ -0,0 +1,104 @@
+use tokio_stream::StreamExt;
+
+use crate::{
+    config::Database,
+    entity::{RecoveryCode, UserTwoFactor},
+    error::AppResult,
+};
+
+/// Repository for the TOTP enrolments and recovery codes of users.
+pub struct TwoFactorRepository {
+    db: &'static Database,
+}
+
+impl TwoFactorRepository {
+    /// Creates a new TwoFactorRepository instance.
+    ///
+    /// # Arguments
+    /// - `db`: Database connection.
+    ///
+    /// # Returns
+    /// A new TwoFactorRepository instance.
+    pub fn new(db: &'static Database) -> Self {
+        Self { db }
+    }
+
+    /// Finds the TOTP enrolment of a user.
+    ///
+    /// # Arguments
+    /// - `user_id`: The ID of the user.
+    ///
+    /// # Returns
+    /// An Option containing the enrolment if found, pending ones included.
+    pub async fn find(&self, user_id: i32) -> AppResult<Option<UserTwoFactor>> {
+        let conn = self.db.pg.get().await?;
+
+        let find_two_factor_stmt = conn
+            .prepare_cached(
+                r#"
+                    SELECT * FROM user_two_factor
+                    WHERE user_id = $1
+                "#,
+            )
+            .await?;
+
+        let row = conn.query_opt(&find_two_factor_stmt, &[&user_id]).await?;
+        Ok(row.map(|row| UserTwoFactor::from_row(&row)))
+    }
+
+    /// Checks if a user has two-factor authentication enabled.
+    ///
+    /// # Arguments
+    /// - `user_id`: The ID of the user.
+    ///
+    /// # Returns
+    /// True if the TOTP enrolment of the user was confirmed.
+    pub async fn enabled(&self, user_id: i32) -> AppResult<bool> {
+        let conn = self.db.pg.get().await?;
+
+        let enabled_stmt = conn
+            .prepare_cached(
+                r#"
+                    SELECT 1 FROM user_two_factor
+                    WHERE user_id = $1 AND enabled_at IS NOT NULL
+                "#,
+            )
+            .await?;
+
+        let row = conn.query_opt(&enabled_stmt, &[&user_id]).await?;
+        Ok(row.is_some())
+    }
+
+    /// Retrieves the recovery codes of a user that were not used yet.
+    ///
+    /// # Arguments
+    /// - `user_id`: The ID of the user.
+    ///
+    /// # Returns
+    /// A vector of hashed recovery codes.
+    pub async fn unused_recovery_codes(&self, user_id: i32) -> AppResult<Vec<RecoveryCode>> {
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let recovery_codes_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT id, code_hash FROM user_recovery_codes
+                        WHERE user_id = $1 AND used_at IS NULL
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(&recovery_codes_stmt, &[&user_id]).await?
+        };
+
+        tokio::pin!(stream);
+        let mut codes = Vec::new();
+
+        while let Some(row) = stream.try_next().await? {
+            codes.push(RecoveryCode::from_row(&row));
+        }
+
+        Ok(codes)
+    }
+}
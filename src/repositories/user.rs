@@ -5,7 +5,7 @@ use tokio_stream::StreamExt;
 use crate::{
     cache::EntityCache,
     config::Database,
-    entity::{Championship, SharedUser, User},
+    entity::{Championship, Provider, SharedUser, User, UserIdentity},
     error::AppResult,
     utils::{slice_iter, PasswordHasher},
 };
@@ -108,6 +108,82 @@ impl UserRepository {
         }
     }
 
+    /// Finds a user by a linked external identity.
+    ///
+    /// # Arguments
+    /// - `provider`: The provider of the identity.
+    /// - `provider_user_id`: The ID of the account on the provider.
+    ///
+    /// # Returns
+    /// An Option containing the user if the identity is linked.
+    pub async fn find_by_identity(
+        &self,
+        provider: Provider,
+        provider_user_id: &str,
+    ) -> AppResult<Option<SharedUser>> {
+        let row = {
+            let conn = self.db.pg.get().await?;
+
+            let find_by_identity_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT users.* FROM users
+                        JOIN user_identities ON user_identities.user_id = users.id
+                        WHERE user_identities.provider = $1
+                            AND user_identities.provider_user_id = $2
+                    "#,
+                )
+                .await?;
+
+            conn.query_opt(&find_by_identity_stmt, &[&provider, &provider_user_id])
+                .await?
+        };
+
+        match row {
+            Some(ref row) => {
+                let user = User::from_row_arc(row);
+                self.db.cache.user.set(user.clone());
+                Ok(Some(user))
+            }
+
+            None => Ok(None),
+        }
+    }
+
+    /// Retrieves the external identities linked to a user.
+    ///
+    /// # Arguments
+    /// - `id`: The ID of the user.
+    ///
+    /// # Returns
+    /// A vector of identities, oldest first.
+    pub async fn identities(&self, id: i32) -> AppResult<Vec<UserIdentity>> {
+        let stream = {
+            let conn = self.db.pg.get().await?;
+
+            let identities_stmt = conn
+                .prepare_cached(
+                    r#"
+                        SELECT provider, provider_user_id, created_at FROM user_identities
+                        WHERE user_id = $1
+                        ORDER BY created_at
+                    "#,
+                )
+                .await?;
+
+            conn.query_raw(&identities_stmt, &[&id]).await?
+        };
+
+        tokio::pin!(stream);
+        let mut identities = Vec::new();
+
+        while let Some(row) = stream.try_next().await? {
+            identities.push(UserIdentity::from_row(&row));
+        }
+
+        Ok(identities)
+    }
+
     /// Retrieves all championships associated with a user.
     ///
     /// # Arguments
@@ -129,7 +205,7 @@ impl UserRepository {
                         SELECT c.*
                         FROM championships c
                         JOIN championship_users cu ON c.id = cu.championship_id
-                        WHERE cu.user_id = $1
+                        WHERE cu.user_id = $1 AND c.deleted_at IS NULL
                     "#,
                 )
                 .await?;
@@ -1,7 +1,7 @@
-use ntex::web::{self, delete, get, post, scope, ServiceConfig};
+use ntex::web::{self, delete, get, post, put, scope, ServiceConfig};
 
 use crate::{
-    handlers::{admin::server_active_pools, championships, user},
+    handlers::{admin::server_active_pools, auth, championships, driver, user},
     middlewares::{Admin, Authentication},
 };
 
@@ -21,16 +21,28 @@ pub(crate) fn admin_routes(cfg: &mut ServiceConfig) {
                     .route(
                         "/deactivate",
                         post().to(user::admin::deactivate_user_account),
+                    )
+                    .route(
+                        "/two-factor/reset",
+                        post().to(user::admin::reset_two_factor),
                     ),
             )
-            .service(scope("/championships").route(
-                "/{id}",
-                delete().to(championships::admin::delete_championship),
-            ))
+            .service(
+                scope("/drivers/{steam_name}")
+                    .route("", delete().to(driver::admin::delete_driver))
+                    .route("/rename", post().to(driver::admin::rename_driver))
+                    .route("/merge", post().to(driver::admin::merge_drivers)),
+            )
+            .service(
+                scope("/championships/{id}")
+                    .route("", delete().to(championships::admin::delete_championship))
+                    .route("/owner", put().to(championships::admin::transfer_owner)),
+            )
             .route(
                 "/services",
                 get().to(championships::admin::active_championships),
             )
-            .route("/pools", get().to(server_active_pools)),
+            .route("/pools", get().to(server_active_pools))
+            .route("/jwt/reload", post().to(auth::reload_jwt_keys)),
     );
 }
@@ -1,15 +1,21 @@
 use std::net::IpAddr;
 
 use dashmap::DashMap;
-use ntex::web::{self, delete, get, post, put, resource, scope, ServiceConfig};
+use ntex::web::{
+    self, delete, get, guard, patch, post, put, resource, scope, types::JsonConfig, ServiceConfig,
+};
 
 use crate::{
-    handlers::{auth, championships, system_health_check, user},
-    middlewares::{Authentication, LoginLimit, VisitorData},
+    config::constants::CHAMPIONSHIP_IMPORT_LIMIT,
+    entity::ApiKeyScope,
+    handlers::{auth, championships, driver, system_health_check, user},
+    middlewares::{ApiKeyAuthentication, Authentication, LoginLimit, VisitorData},
 };
 
 #[inline]
 pub(crate) fn api_routes(cfg: &mut ServiceConfig, visitors: &'static DashMap<IpAddr, VisitorData>) {
+    cfg.route("/.well-known/jwks.json", get().to(auth::jwks));
+
     cfg.service(
         scope("/auth")
             .route("/register", post().to(auth::register))
@@ -18,6 +24,16 @@ pub(crate) fn api_routes(cfg: &mut ServiceConfig, visitors: &'static DashMap<IpA
                     .route(post().to(auth::login))
                     .wrap(LoginLimit::new(visitors)),
             )
+            .service(
+                resource("/login/2fa")
+                    .route(post().to(auth::login_two_factor))
+                    .wrap(LoginLimit::new(visitors)),
+            )
+            .service(
+                resource("/exchange")
+                    .route(post().to(auth::oauth_exchange))
+                    .wrap(LoginLimit::new(visitors)),
+            )
             .service(
                 resource("/logout")
                     .route(get().to(auth::logout))
@@ -30,7 +46,9 @@ pub(crate) fn api_routes(cfg: &mut ServiceConfig, visitors: &'static DashMap<IpA
                     .route("/forgot", post().to(auth::forgot_password))
                     .route("/reset", post().to(auth::reset_password)),
             )
-            .route("/discord/callback", get().to(auth::discord_callback)),
+            // Matched last so the provider doesn't shadow the routes above
+            .route("/{provider}", get().to(auth::oauth_authorize))
+            .route("/{provider}/callback", get().to(auth::oauth_callback)),
     );
 
     cfg.service(
@@ -38,36 +56,190 @@ pub(crate) fn api_routes(cfg: &mut ServiceConfig, visitors: &'static DashMap<IpA
             .route("", get().to(user::get))
             .route("", put().to(user::update))
             .route("/championships", get().to(user::get_championships))
+            .route(
+                "/championships/deleted",
+                get().to(user::get_deleted_championships),
+            )
+            .service(
+                scope("/invitations")
+                    .route("", get().to(championships::invitations::user_invitations))
+                    .route(
+                        "/{id}/accept",
+                        post().to(championships::invitations::accept),
+                    )
+                    .route(
+                        "/{id}/decline",
+                        post().to(championships::invitations::decline),
+                    ),
+            )
+            .service(
+                scope("/driver/claims")
+                    .route("", get().to(user::driver_claims))
+                    .route("", post().to(user::claim_driver)),
+            )
+            .service(
+                scope("/sessions")
+                    .route("", get().to(user::sessions))
+                    .route("/{id}", delete().to(user::revoke_session)),
+            )
+            .service(
+                scope("/api-keys")
+                    .route("", get().to(user::api_keys::list))
+                    .route("", post().to(user::api_keys::create))
+                    .route("/{id}", delete().to(user::api_keys::revoke)),
+            )
+            .service(
+                scope("/identities")
+                    .route("", get().to(user::identities::list))
+                    .route("/{provider}", post().to(user::identities::link))
+                    .route("/{provider}", delete().to(user::identities::unlink)),
+            )
+            .service(
+                scope("/two-factor")
+                    .route("/setup", post().to(user::two_factor::setup))
+                    .route("/enable", post().to(user::two_factor::enable))
+                    .route("/disable", post().to(user::two_factor::disable)),
+            )
             .wrap(Authentication),
     );
 
+    // Read routes that also accept API keys, matched before the championships scope
+    cfg.service((
+        resource("/championships/{id:\\d+}")
+            .guard(guard::Get())
+            .route(get().to(championships::core::get))
+            .wrap(Authentication)
+            .wrap(ApiKeyAuthentication::new(ApiKeyScope::ReadResults)),
+        resource("/championships/{id:\\d+}/standings")
+            .route(get().to(championships::core::standings))
+            .wrap(Authentication)
+            .wrap(ApiKeyAuthentication::new(ApiKeyScope::ReadResults)),
+        resource("/championships/{id:\\d+}/export")
+            .route(get().to(championships::export::export))
+            .wrap(Authentication)
+            .wrap(ApiKeyAuthentication::new(ApiKeyScope::ReadResults)),
+        resource("/championships/{id:\\d+}/races/{race_id}/results/{session_type}")
+            .route(get().to(championships::results::get))
+            .wrap(Authentication)
+            .wrap(ApiKeyAuthentication::new(ApiKeyScope::ReadResults)),
+    ));
+
     cfg.service(
         scope("/championships")
             .route("", post().to(championships::core::create))
+            .route("/search", get().to(championships::core::search))
             .service(
                 scope("/{id}")
-                    .route("", get().to(championships::core::get))
                     .route("", put().to(championships::core::update))
+                    .route("", delete().to(championships::core::delete))
+                    .route("/restore", post().to(championships::core::restore))
+                    .service(
+                        resource("/import")
+                            .state(JsonConfig::default().limit(CHAMPIONSHIP_IMPORT_LIMIT))
+                            .route(post().to(championships::core::import)),
+                    )
                     .service(
                         scope("/users")
-                            .route("", put().to(championships::core::add_user))
+                            .route("", put().to(championships::core::invite_user))
+                            .route("/{user_id}", patch().to(championships::core::update_user))
                             .route("/{user_id}", delete().to(championships::core::remove_user)),
+                    )
+                    .service(
+                        scope("/transfers")
+                            .route("", get().to(championships::transfers::pending))
+                            .route("/history", get().to(championships::transfers::history))
+                            .route(
+                                "/{transfer_id}/approve",
+                                post().to(championships::transfers::approve),
+                            )
+                            .route(
+                                "/{transfer_id}/reject",
+                                post().to(championships::transfers::reject),
+                            ),
+                    )
+                    .service(
+                        scope("/owner")
+                            .route("", post().to(championships::owner::transfer))
+                            .route("/transfers", get().to(championships::owner::history))
+                            .route("/accept", post().to(championships::owner::accept))
+                            .route("/decline", post().to(championships::owner::decline)),
+                    )
+                    .route("/join", post().to(championships::join_requests::request))
+                    .service(
+                        scope("/join-requests")
+                            .route("", get().to(championships::join_requests::pending))
+                            .route(
+                                "/{request_id}/approve",
+                                post().to(championships::join_requests::approve),
+                            )
+                            .route(
+                                "/{request_id}/reject",
+                                post().to(championships::join_requests::reject),
+                            ),
+                    )
+                    .service(
+                        scope("/claims")
+                            .route("", get().to(championships::claims::pending))
+                            .route(
+                                "/{claim_id}/approve",
+                                post().to(championships::claims::approve),
+                            )
+                            .route(
+                                "/{claim_id}/reject",
+                                post().to(championships::claims::reject),
+                            ),
+                    )
+                    .service(
+                        scope("/races/{race_id}/results/{session_type}")
+                            .route("/amendments", post().to(championships::results::amend)),
+                    )
+                    .service(
+                        scope("/incidents")
+                            .route("", get().to(championships::incidents::list))
+                            .route("/{incident_id}", get().to(championships::incidents::get))
+                            .route(
+                                "/{incident_id}/comments",
+                                post().to(championships::incidents::comment),
+                            )
+                            .route(
+                                "/{incident_id}/penalty",
+                                post().to(championships::incidents::penalty),
+                            )
+                            .route(
+                                "/{incident_id}/dismiss",
+                                post().to(championships::incidents::dismiss),
+                            ),
                     ),
             )
             .wrap(Authentication),
     );
 
     cfg.service(
-        scope("/services")
-            .service(
-                scope("/championships/{id}")
-                    .route("/start", post().to(championships::service::start))
-                    .route("/status", get().to(championships::service::status))
-                    .route("/stop", post().to(championships::service::stop)),
-            )
+        scope("/invitations")
+            .route("", get().to(championships::invitations::get))
+            .route(
+                "/decline",
+                post().to(championships::invitations::decline_by_token),
+            ),
+    );
+
+    cfg.service(
+        scope("/drivers")
+            .route("/{steam_name}", get().to(driver::get))
             .wrap(Authentication),
     );
 
+    cfg.service(
+        scope("/services").service(
+            scope("/championships/{id}")
+                .route("/start", post().to(championships::service::start))
+                .route("/status", get().to(championships::service::status))
+                .route("/stop", post().to(championships::service::stop))
+                .wrap(Authentication)
+                .wrap(ApiKeyAuthentication::new(ApiKeyScope::ManageRaces)),
+        ),
+    );
+
     cfg.service(scope("/system").route("/health-check", get().to(system_health_check)));
 
     cfg.service(
@@ -77,6 +249,7 @@ pub(crate) fn api_routes(cfg: &mut ServiceConfig, visitors: &'static DashMap<IpA
                 .service(
                     web::resource("/telemetry")
                         .wrap(Authentication)
+                        .wrap(ApiKeyAuthentication::new(ApiKeyScope::ReadLive))
                         .route(get().to(championships::stream::stream_telemetry_session)),
                 ),
         ),
@@ -0,0 +1,289 @@
+use std::sync::Arc;
+
+use base64_simd::URL_SAFE_NO_PAD;
+use ring::{
+    digest::{digest, SHA256},
+    rand::{SecureRandom, SystemRandom},
+};
+
+use crate::{
+    config::{
+        constants::{API_KEY_LIMIT, API_KEY_PREFIX},
+        Database,
+    },
+    entity::{ApiKey, ApiKeyScope, SharedApiKey},
+    error::{ApiKeyError, AppResult, CommonError},
+    repositories::{ApiKeyRepository, ChampionshipRepository},
+    structs::{ApiKeyCreationForm, CreatedApiKey},
+    utils::PasswordHasher,
+};
+
+/// Defines the operations for managing the API keys of a user.
+pub trait ApiKeyServiceOperations {
+    /// Creates an API key scoped to a championship.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The ID of the user creating the key.
+    /// * `form` - The name, championship and scopes of the key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user is not part of the championship, asks for
+    /// `ManageRaces` without being an admin, or has reached the key limit.
+    async fn create(&self, user_id: i32, form: &ApiKeyCreationForm) -> AppResult<CreatedApiKey>;
+
+    /// Revokes an API key of the user.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The ID of the user owning the key.
+    /// * `key_id` - The ID of the key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is not found or already revoked.
+    async fn revoke(&self, user_id: i32, key_id: i32) -> AppResult<()>;
+}
+
+/// Implementation of the API key service.
+pub struct ApiKeyService {
+    db: &'static Database,
+    championship_repo: &'static ChampionshipRepository,
+    api_key_repo: &'static ApiKeyRepository,
+    password_hasher: PasswordHasher,
+    rng: SystemRandom,
+}
+
+impl ApiKeyService {
+    /// Creates a new ApiKeyService instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database connection.
+    /// * `championship_repo` - The championship repository.
+    /// * `api_key_repo` - The API key repository.
+    pub fn new(
+        db: &'static Database,
+        championship_repo: &'static ChampionshipRepository,
+        api_key_repo: &'static ApiKeyRepository,
+    ) -> Self {
+        Self {
+            db,
+            championship_repo,
+            api_key_repo,
+            password_hasher: PasswordHasher::new(10),
+            rng: SystemRandom::new(),
+        }
+    }
+
+    /// Resolves a raw API key sent by a client.
+    ///
+    /// Keys are verified against their hash once and then served from the cache
+    /// until it expires or the key is revoked. After a wrong secret the prefix
+    /// is rejected without hashing for a short delay.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ApiKeyError::InvalidKey` if the key is malformed, unknown, revoked
+    /// or its secret doesn't match.
+    pub async fn authenticate(&self, raw_key: &str) -> AppResult<SharedApiKey> {
+        let Some((prefix, secret)) = Self::parse_key(raw_key) else {
+            Err(ApiKeyError::InvalidKey)?
+        };
+
+        let secret_digest = URL_SAFE_NO_PAD.encode_to_string(digest(&SHA256, secret.as_bytes()));
+
+        if let Some(api_key) = self.db.cache.api_key.get(prefix, &secret_digest) {
+            return Ok(api_key);
+        }
+
+        if self.db.cache.api_key.recently_failed(prefix) {
+            Err(ApiKeyError::InvalidKey)?
+        }
+
+        let Some(api_key) = self.api_key_repo.find_by_prefix(prefix).await? else {
+            Err(ApiKeyError::InvalidKey)?
+        };
+
+        if api_key.revoked_at.is_some() {
+            Err(ApiKeyError::InvalidKey)?
+        }
+
+        if !self
+            .password_hasher
+            .verify_password(api_key.key_hash.clone(), secret.to_owned())
+            .await?
+        {
+            self.db.cache.api_key.set_failure(prefix);
+            Err(ApiKeyError::InvalidKey)?
+        }
+
+        self.touch(api_key.id).await?;
+
+        let api_key = Arc::new(api_key);
+        self.db.cache.api_key.set(secret_digest, api_key.clone());
+
+        Ok(api_key)
+    }
+
+    /// Splits a raw key in its prefix and secret, `None` if it's malformed.
+    #[inline]
+    fn parse_key(raw_key: &str) -> Option<(&str, &str)> {
+        raw_key
+            .strip_prefix(API_KEY_PREFIX)
+            .and_then(|key| key.split_once('.'))
+            .filter(|(prefix, secret)| !prefix.is_empty() && !secret.is_empty())
+    }
+
+    /// Records the last use of a key, done on every hash verification.
+    #[inline]
+    async fn touch(&self, key_id: i32) -> AppResult<()> {
+        let conn = self.db.pg.get().await?;
+
+        let touch_key_stmt = conn
+            .prepare_cached(
+                r#"
+                    UPDATE api_keys
+                    SET last_used_at = NOW()
+                    WHERE id = $1
+                "#,
+            )
+            .await?;
+
+        conn.execute(&touch_key_stmt, &[&key_id]).await?;
+        Ok(())
+    }
+
+    /// Generates `N` random bytes.
+    #[inline]
+    fn random_bytes<const N: usize>(&self) -> AppResult<[u8; N]> {
+        let mut bytes = [0u8; N];
+
+        self.rng
+            .fill(&mut bytes)
+            .map_err(|_| CommonError::InternalServerError)?;
+
+        Ok(bytes)
+    }
+
+    /// Internal method to create an API key.
+    #[inline]
+    async fn _create(&self, user_id: i32, form: &ApiKeyCreationForm) -> AppResult<CreatedApiKey> {
+        let prefix = self
+            .random_bytes::<8>()?
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        let secret = URL_SAFE_NO_PAD.encode_to_string(self.random_bytes::<32>()?);
+        let key_hash = self.password_hasher.hash_password(secret.clone()).await?;
+
+        let mut scopes = form.scopes.clone();
+        scopes.sort_by_key(|scope| *scope as u8);
+        scopes.dedup();
+
+        let row = {
+            let conn = self.db.pg.get().await?;
+
+            let create_key_stmt = conn
+                .prepare_cached(
+                    r#"
+                        INSERT INTO api_keys (user_id, championship_id, name, prefix, key_hash, scopes)
+                        VALUES ($1, $2, $3, $4, $5, $6)
+                        RETURNING *
+                    "#,
+                )
+                .await?;
+
+            conn.query_one(
+                &create_key_stmt,
+                &[
+                    &user_id,
+                    &form.championship_id,
+                    &form.name,
+                    &prefix,
+                    &key_hash,
+                    &scopes,
+                ],
+            )
+            .await?
+        };
+
+        Ok(CreatedApiKey {
+            key: format!("{API_KEY_PREFIX}{prefix}.{secret}"),
+            api_key: ApiKey::from_row(&row),
+        })
+    }
+
+    /// Internal method to revoke an API key.
+    #[inline]
+    async fn _revoke(&self, user_id: i32, key_id: i32) -> AppResult<()> {
+        let row = {
+            let conn = self.db.pg.get().await?;
+
+            let revoke_key_stmt = conn
+                .prepare_cached(
+                    r#"
+                        UPDATE api_keys
+                        SET revoked_at = NOW()
+                        WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL
+                        RETURNING prefix
+                    "#,
+                )
+                .await?;
+
+            conn.query_opt(&revoke_key_stmt, &[&key_id, &user_id])
+                .await?
+        };
+
+        let Some(row) = row else {
+            Err(ApiKeyError::NotFound)?
+        };
+
+        self.db.cache.api_key.delete(row.get(0));
+        Ok(())
+    }
+}
+
+impl ApiKeyServiceOperations for ApiKeyService {
+    async fn create(&self, user_id: i32, form: &ApiKeyCreationForm) -> AppResult<CreatedApiKey> {
+        let (relation, is_admin, keys) = tokio::try_join!(
+            self.championship_repo
+                .user_relation(form.championship_id, user_id),
+            self.championship_repo
+                .is_admin(form.championship_id, user_id),
+            self.api_key_repo.user_keys(user_id)
+        )?;
+
+        if relation.is_none() || (form.scopes.contains(&ApiKeyScope::ManageRaces) && !is_admin) {
+            Err(ApiKeyError::NotMember)?
+        }
+
+        if keys.len() >= API_KEY_LIMIT {
+            Err(ApiKeyError::LimitReached)?
+        }
+
+        self._create(user_id, form).await
+    }
+
+    async fn revoke(&self, user_id: i32, key_id: i32) -> AppResult<()> {
+        self._revoke(user_id, key_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_key_splits_prefix_and_secret() {
+        assert_eq!(
+            ApiKeyService::parse_key("ik_0a1b2c3d.c2VjcmV0"),
+            Some(("0a1b2c3d", "c2VjcmV0"))
+        );
+        assert_eq!(ApiKeyService::parse_key("0a1b2c3d.c2VjcmV0"), None);
+        assert_eq!(ApiKeyService::parse_key("ik_0a1b2c3d"), None);
+        assert_eq!(ApiKeyService::parse_key("ik_.c2VjcmV0"), None);
+        assert_eq!(ApiKeyService::parse_key("ik_0a1b2c3d."), None);
+    }
+}
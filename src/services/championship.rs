@@ -1,11 +1,27 @@
+use ahash::AHashSet;
 use chrono::{DateTime, Duration, Utc};
+use dotenvy::var;
 use postgres_types::ToSql;
+use prost::Message;
+use tracing::{error, info};
 
 use crate::{
-    config::Database,
+    config::{
+        constants::{CHAMPIONSHIP_DELETION_GRACE_DAYS, CHAMPIONSHIP_PURGE_INTERVAL},
+        Database,
+    },
+    entity::{
+        ChampionshipDriver, ChampionshipInvitation, ChampionshipRelation, ChampionshipRole,
+        InvitationStatus, OwnerTransfer, SharedUser, Visibility,
+    },
     error::{AppResult, ChampionshipError, CommonError, UserError},
     repositories::{ChampionshipRepository, UserRepository},
-    structs::{ChampionshipCreationData, ChampionshipUpdateData, ChampionshipUserAddForm},
+    services::ResultService,
+    structs::{
+        protos::SessionResult, ChampionshipCreationData, ChampionshipImportForm,
+        ChampionshipUpdateData, ChampionshipUserAddForm, ChampionshipUserUpdateForm, ImportReport,
+        SessionType, TeamIds, TokenPurpose,
+    },
     utils::{IdsGenerator, MachinePorts},
 };
 
@@ -55,20 +71,56 @@ pub trait ChampionshipServiceOperations {
     /// or if the update interval hasn't been reached.
     async fn update(&self, id: i32, user_id: i32, form: &ChampionshipUpdateData) -> AppResult<()>;
 
-    /// Adds a user to a championship.
+    /// Invites someone to join a championship by email.
+    ///
+    /// The invitee doesn't need to be registered, the invitation is bound to the email.
     ///
     /// # Arguments
     ///
     /// * `id` - The ID of the championship.
-    /// * `user_id` - The ID of the user adding another user.
-    /// * `form` - The form containing the new user's details.
+    /// * `user_id` - The ID of the user sending the invitation.
+    /// * `form` - The form containing the invitee's details.
+    ///
+    /// # Returns
+    ///
+    /// Returns the ID of the invitation.
     ///
     /// # Errors
     ///
     /// Returns an error if the championship is not found, the user is not the owner,
-    /// or if the user to be added doesn't exist.
-    async fn add_user(&self, id: i32, user_id: i32, form: ChampionshipUserAddForm)
-        -> AppResult<()>;
+    /// the invitee is already a member or an invitation is already pending.
+    async fn invite_user(
+        &self,
+        id: i32,
+        user_id: i32,
+        form: &ChampionshipUserAddForm,
+    ) -> AppResult<i32>;
+
+    /// Accepts an invitation, adding the user to the championship.
+    ///
+    /// # Arguments
+    ///
+    /// * `invitation_id` - The ID of the invitation.
+    /// * `user` - The user accepting the invitation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the invitation is not found, expired,
+    /// or was sent to a different email.
+    async fn accept_invitation(&self, invitation_id: i32, user: &SharedUser) -> AppResult<()>;
+
+    /// Declines an invitation.
+    ///
+    /// # Arguments
+    ///
+    /// * `invitation_id` - The ID of the invitation.
+    /// * `email` - The email of the user declining, `None` when declined through the invitation link.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the invitation is not found, expired,
+    /// or was sent to a different email.
+    async fn decline_invitation(&self, invitation_id: i32, email: Option<&str>) -> AppResult<()>;
 
     /// Adds a driver to a championship.
     ///
@@ -90,20 +142,208 @@ pub trait ChampionshipServiceOperations {
         number: i16,
     ) -> AppResult<()>;
 
+    /// Requests to join a public or unlisted championship.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the championship.
+    /// * `user_id` - The ID of the user asking to join.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the championship is not found or private, the user is
+    /// already a member, or a request is already pending.
+    async fn request_join(&self, id: i32, user_id: i32) -> AppResult<()>;
+
+    /// Approves a join request, adding the requester as a visitor.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the championship.
+    /// * `user_id` - The ID of the admin approving the request.
+    /// * `request_id` - The ID of the join request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user is not an admin of the championship
+    /// or if the request is not found or not pending.
+    async fn approve_join_request(&self, id: i32, user_id: i32, request_id: i32) -> AppResult<()>;
+
+    /// Rejects a join request.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the championship.
+    /// * `user_id` - The ID of the admin rejecting the request.
+    /// * `request_id` - The ID of the join request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user is not an admin of the championship
+    /// or if the request is not found or not pending.
+    async fn reject_join_request(&self, id: i32, user_id: i32, request_id: i32) -> AppResult<()>;
+
+    /// Queues a team or number change of a driver for approval.
+    ///
+    /// If a pending transfer already exists for the driver it's updated with the new values.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the championship.
+    /// * `driver` - The driver as currently registered in the championship.
+    /// * `team_id` - The team the driver was detected with.
+    /// * `number` - The racing number the driver was detected with.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's a database error.
+    async fn request_transfer(
+        &self,
+        id: i32,
+        driver: &ChampionshipDriver,
+        team_id: i16,
+        number: i16,
+    ) -> AppResult<()>;
+
+    /// Approves a pending transfer, moving the driver to the new team and number.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the championship.
+    /// * `user_id` - The ID of the user approving the transfer.
+    /// * `transfer_id` - The ID of the transfer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user is not an admin of the championship
+    /// or if the transfer is not found or not pending.
+    async fn approve_transfer(&self, id: i32, user_id: i32, transfer_id: i32) -> AppResult<()>;
+
+    /// Rejects a pending transfer, keeping the driver registration unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the championship.
+    /// * `user_id` - The ID of the user rejecting the transfer.
+    /// * `transfer_id` - The ID of the transfer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user is not an admin of the championship
+    /// or if the transfer is not found or not pending.
+    async fn reject_transfer(&self, id: i32, user_id: i32, transfer_id: i32) -> AppResult<()>;
+
     /// Adds a race result to a specific race in the championship.
     ///
     /// # Arguments
     ///
     /// * `race_id` - The ID of the race to which the result belongs.
     /// * `session_type` - The type of session (e.g., practice, qualifying, race).
-    /// * `data` - The raw data of the race result.
+    /// * `result` - The final classification of the session.
     ///
     /// # Errors
     ///
     /// Returns an error if the race is not found, the session type is invalid,
     /// or if there's a database error while storing the result.
-    #[allow(unused)]
-    async fn add_race_result(&self, race_id: i32, session_type: i16, data: &[u8]) -> AppResult<()>;
+    async fn add_race_result(
+        &self,
+        race_id: i32,
+        session_type: i16,
+        result: &SessionResult,
+    ) -> AppResult<()>;
+
+    /// Imports a historical season into a championship.
+    ///
+    /// Every row is checked before writing, if any of them fails nothing is imported.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the championship.
+    /// * `user_id` - The ID of the user importing the season.
+    /// * `form` - The drivers, races and classifications to import.
+    ///
+    /// # Returns
+    ///
+    /// A report with the imported rows or the errors of each failing row.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user is not an admin or if there's a database error.
+    async fn import(
+        &self,
+        id: i32,
+        user_id: i32,
+        form: &ChampionshipImportForm,
+    ) -> AppResult<ImportReport>;
+
+    /// Updates the role and team of a championship member.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the championship.
+    /// * `user_id` - The ID of the user performing the update.
+    /// * `member_id` - The ID of the member being updated.
+    /// * `form` - The new role and team of the member.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated relation of the member with the championship.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user is not an admin, if a non-owner grants or revokes
+    /// the Admin role, if the member is the owner or not in the championship,
+    /// or if an engineer ends up without a team.
+    async fn update_user(
+        &self,
+        id: i32,
+        user_id: i32,
+        member_id: i32,
+        form: &ChampionshipUserUpdateForm,
+    ) -> AppResult<ChampionshipRelation>;
+
+    /// Offers the ownership of a championship to one of its members.
+    ///
+    /// The transfer only takes effect once the new owner accepts it,
+    /// offering it again replaces the pending transfer.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the championship.
+    /// * `user_id` - The ID of the current owner.
+    /// * `new_owner_id` - The ID of the member receiving the ownership.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the championship is not found, the user is not the owner,
+    /// or the new owner is not a member of the championship.
+    async fn transfer_ownership(&self, id: i32, user_id: i32, new_owner_id: i32) -> AppResult<()>;
+
+    /// Accepts the pending ownership transfer, making the user the owner.
+    ///
+    /// The previous owner takes over the role and team the new owner had.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the championship.
+    /// * `user_id` - The ID of the user the ownership was offered to.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's no pending transfer for the user.
+    async fn accept_ownership(&self, id: i32, user_id: i32) -> AppResult<()>;
+
+    /// Declines the pending ownership transfer.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the championship.
+    /// * `user_id` - The ID of the user the ownership was offered to.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's no pending transfer for the user.
+    async fn decline_ownership(&self, id: i32, user_id: i32) -> AppResult<()>;
 
     /// Removes a user from a championship.
     ///
@@ -134,6 +374,9 @@ pub trait ChampionshipServiceOperations {
 
     /// Deletes a championship.
     ///
+    /// The championship is hidden and can be restored until the grace period ends,
+    /// then it's purged by the background job.
+    ///
     /// # Arguments
     ///
     /// * `id` - The ID of the championship to delete.
@@ -142,8 +385,20 @@ pub trait ChampionshipServiceOperations {
     /// # Errors
     ///
     /// Returns an error if the championship is not found or if the user is not the owner.
-    #[allow(unused)]
     async fn delete(&self, id: i32, user_id: i32) -> AppResult<()>;
+
+    /// Restores a deleted championship still in its grace period.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the championship to restore.
+    /// * `user_id` - The ID of the user attempting to restore the championship.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the championship is not deleted, was already purged,
+    /// or if the user is not the owner.
+    async fn restore(&self, id: i32, user_id: i32) -> AppResult<()>;
 }
 
 /// Defines additional admin-level operations for managing championships.
@@ -158,11 +413,31 @@ pub trait ChampionshipAdminServiceOperations: ChampionshipServiceOperations {
     ///
     /// Returns an error if the championship is not found or if there's a database error.
     async fn admin_delete_championship(&self, id: i32) -> AppResult<()>;
+
+    /// Allows an admin to change the owner of a championship without confirmation.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the championship.
+    /// * `admin_id` - The ID of the admin forcing the change.
+    /// * `new_owner_id` - The ID of the user receiving the ownership.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the championship or the user is not found,
+    /// or if the user already owns the championship.
+    async fn admin_transfer_ownership(
+        &self,
+        id: i32,
+        admin_id: i32,
+        new_owner_id: i32,
+    ) -> AppResult<()>;
 }
 
 /// Implements the championship service logic.
 pub struct ChampionshipService {
     db: &'static Database,
+    deletion_grace: Duration,
     machine_ports: MachinePorts,
     user_repo: &'static UserRepository,
     championship_repo: &'static ChampionshipRepository,
@@ -196,8 +471,18 @@ impl ChampionshipService {
             IdsGenerator::new(700000000..799999999, used_ids)
         };
 
+        let deletion_grace = {
+            let days = var("CHAMPIONSHIP_DELETION_GRACE_DAYS")
+                .ok()
+                .and_then(|days| days.parse().ok())
+                .unwrap_or(CHAMPIONSHIP_DELETION_GRACE_DAYS);
+
+            Duration::days(days)
+        };
+
         Ok(Self {
             db,
+            deletion_grace,
             user_repo,
             championship_repo,
             machine_ports,
@@ -221,8 +506,8 @@ impl ChampionshipService {
 
         let create_championship_stmt_fut = conn.prepare_cached(
             r#"
-                INSERT INTO championships (id, port, name, category, owner_id)
-                VALUES ($1,$2,$3,$4,$5)
+                INSERT INTO championships (id, port, name, category, owner_id, visibility)
+                VALUES ($1,$2,$3,$4,$5,$6)
             "#,
         );
 
@@ -248,7 +533,14 @@ impl ChampionshipService {
         let result = conn
             .execute(
                 &create_championship_stmt,
-                &[&id, &port, &payload.name, &payload.category, &user_id],
+                &[
+                    &id,
+                    &port,
+                    &payload.name,
+                    &payload.category,
+                    &user_id,
+                    &payload.visibility,
+                ],
             )
             .await;
 
@@ -293,8 +585,8 @@ impl ChampionshipService {
     async fn _update(&self, id: i32, form: &ChampionshipUpdateData) -> AppResult<()> {
         let (query, params) = {
             let mut params_counter = 1u8;
-            let mut clauses = Vec::with_capacity(3);
-            let mut params: Vec<&(dyn ToSql + Sync)> = Vec::with_capacity(5);
+            let mut clauses = Vec::with_capacity(4);
+            let mut params: Vec<&(dyn ToSql + Sync)> = Vec::with_capacity(4);
 
             if let Some(name) = &form.name {
                 clauses.push(format!("name = ${}", params_counter));
@@ -308,6 +600,12 @@ impl ChampionshipService {
                 params_counter += 1;
             }
 
+            if let Some(visibility) = &form.visibility {
+                clauses.push(format!("visibility = ${}", params_counter));
+                params.push(visibility);
+                params_counter += 1;
+            }
+
             if clauses.is_empty() {
                 Err(CommonError::NotValidUpdate)?
             }
@@ -336,39 +634,187 @@ impl ChampionshipService {
         Ok(())
     }
 
-    /// Internal method to add a user to a championship.
+    /// Internal method to invite a user to a championship.
     #[inline]
-    async fn _add_user(&self, id: i32, form: ChampionshipUserAddForm) -> AppResult<()> {
-        let bind_user_id = {
-            let Some(bind_user) = self.user_repo.find_by_email(&form.email).await? else {
-                Err(UserError::NotFound)?
-            };
+    async fn _invite_user(
+        &self,
+        id: i32,
+        user_id: i32,
+        form: &ChampionshipUserAddForm,
+    ) -> AppResult<i32> {
+        let conn = self.db.pg.get().await?;
+
+        // Expired invitations are renewed instead of blocking new ones
+        let invite_user_stmt = conn
+            .prepare_cached(
+                r#"
+                    INSERT INTO championship_invitations
+                        (championship_id, email, role, team_id, invited_by, expires_at)
+                    VALUES ($1, $2, $3, $4, $5, $6)
+                    ON CONFLICT (championship_id, email) WHERE status = 'Pending'
+                    DO UPDATE SET
+                        role = EXCLUDED.role,
+                        team_id = EXCLUDED.team_id,
+                        invited_by = EXCLUDED.invited_by,
+                        expires_at = EXCLUDED.expires_at,
+                        created_at = CURRENT_TIMESTAMP
+                    WHERE championship_invitations.expires_at <= CURRENT_TIMESTAMP
+                    RETURNING id
+                "#,
+            )
+            .await?;
 
-            bind_user.id
+        let formatted_team_id = form
+            .team_id
+            .clone()
+            .map(|valid_team_id| valid_team_id as i16);
+        let expires_at = TokenPurpose::ChampionshipInvitation.expiration_date();
+
+        let Some(row) = conn
+            .query_opt(
+                &invite_user_stmt,
+                &[
+                    &id,
+                    &form.email,
+                    &form.role,
+                    &formatted_team_id,
+                    &user_id,
+                    &expires_at,
+                ],
+            )
+            .await?
+        else {
+            Err(ChampionshipError::InvitationAlreadyPending)?
         };
 
+        Ok(row.get(0))
+    }
+
+    /// Internal method to accept an invitation, adding the user to the championship.
+    #[inline]
+    async fn _accept_invitation(
+        &self,
+        invitation: &ChampionshipInvitation,
+        user_id: i32,
+    ) -> AppResult<()> {
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
+
+        let accept_invitation_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE championship_invitations
+                SET status = 'Accepted', responded_at = CURRENT_TIMESTAMP
+                WHERE id = $1 AND status = 'Pending'
+            "#,
+        );
+
+        let add_user_stmt_fut = tx.prepare_cached(
+            r#"
+                INSERT INTO championship_users (user_id, championship_id, role)
+                VALUES ($1,$2,$3)
+                ON CONFLICT (user_id, championship_id) DO NOTHING
+            "#,
+        );
+
+        let assign_team_stmt_fut = tx.prepare_cached(
+            r#"
+                INSERT INTO championship_engineer_teams (championship_id, user_id, team_id)
+                VALUES ($1,$2,$3)
+            "#,
+        );
+
+        let (accept_invitation_stmt, add_user_stmt, assign_team_stmt) = tokio::try_join!(
+            accept_invitation_stmt_fut,
+            add_user_stmt_fut,
+            assign_team_stmt_fut
+        )?;
+
+        if tx
+            .execute(&accept_invitation_stmt, &[&invitation.id])
+            .await?
+            == 0
+        {
+            Err(ChampionshipError::InvitationNotFound)?
+        }
+
+        let added = tx
+            .execute(
+                &add_user_stmt,
+                &[&user_id, &invitation.championship_id, &invitation.role],
+            )
+            .await?;
+
+        if let (1, ChampionshipRole::Engineer, Some(team_id)) =
+            (added, invitation.role, invitation.team_id)
+        {
+            tx.execute(
+                &assign_team_stmt,
+                &[&invitation.championship_id, &user_id, &team_id],
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        self.db.cache.championship.delete_by_user(&user_id);
+
+        Ok(())
+    }
+
+    /// Internal method to decline an invitation.
+    #[inline]
+    async fn _decline_invitation(&self, invitation_id: i32) -> AppResult<()> {
         let conn = self.db.pg.get().await?;
 
-        let add_user_stmt = conn
+        let decline_invitation_stmt = conn
             .prepare_cached(
                 r#"
-                    INSERT INTO championship_users (user_id, championship_id, role, team_id)
-                    VALUES ($1,$2,$3,$4)
+                    UPDATE championship_invitations
+                    SET status = 'Declined', responded_at = CURRENT_TIMESTAMP
+                    WHERE id = $1 AND status = 'Pending'
                 "#,
             )
             .await?;
 
-        let formatted_team_id = form.team_id.map(|valid_team_id| valid_team_id as i16);
+        if conn
+            .execute(&decline_invitation_stmt, &[&invitation_id])
+            .await?
+            == 0
+        {
+            Err(ChampionshipError::InvitationNotFound)?
+        }
 
-        conn.execute(
-            &add_user_stmt,
-            &[&bind_user_id, &id, &form.role, &formatted_team_id],
-        )
-        .await?;
+        Ok(())
+    }
 
-        self.db.cache.championship.delete_by_user(&bind_user_id);
+    /// Finds an invitation that can still be answered by `email`.
+    #[inline]
+    async fn open_invitation(
+        &self,
+        invitation_id: i32,
+        email: Option<&str>,
+    ) -> AppResult<ChampionshipInvitation> {
+        let Some(invitation) = self
+            .championship_repo
+            .find_invitation(invitation_id)
+            .await?
+        else {
+            Err(ChampionshipError::InvitationNotFound)?
+        };
 
-        Ok(())
+        if email.is_some_and(|email| !invitation.email.eq_ignore_ascii_case(email)) {
+            Err(ChampionshipError::InvitationNotForUser)?
+        }
+
+        if invitation.status != InvitationStatus::Pending {
+            Err(ChampionshipError::InvitationNotFound)?
+        }
+
+        if !invitation.is_open() {
+            Err(ChampionshipError::InvitationExpired)?
+        }
+
+        Ok(invitation)
     }
 
     async fn _add_driver(
@@ -395,39 +841,724 @@ impl ChampionshipService {
         Ok(())
     }
 
-    async fn _add_race_result(
-        &self,
-        race_id: i32,
-        session_type: i16,
-        data: &[u8],
-    ) -> AppResult<()> {
+    #[inline]
+    async fn _request_join(&self, id: i32, user_id: i32) -> AppResult<()> {
         let conn = self.db.pg.get().await?;
 
-        let add_result_stmt = conn
+        let request_join_stmt = conn
             .prepare_cached(
                 r#"
-                    INSERT INTO results (race_id, session_type, data)
-                    VALUES ($1, $2, $3)
+                    INSERT INTO championship_join_requests (championship_id, user_id)
+                    VALUES ($1, $2)
+                    ON CONFLICT (championship_id, user_id) WHERE status = 'Pending' DO NOTHING
                 "#,
             )
             .await?;
 
-        conn.execute(&add_result_stmt, &[&race_id, &session_type, &data])
-            .await?;
+        if conn.execute(&request_join_stmt, &[&id, &user_id]).await? == 0 {
+            Err(ChampionshipError::JoinRequestAlreadyPending)?
+        }
 
         Ok(())
     }
 
-    /// Internal method to remove a user from a championship.
     #[inline]
-    async fn _remove_user(&self, id: i32, remove_user_id: i32) -> AppResult<()> {
-        if self.user_repo.find(remove_user_id).await?.is_none() {
-            Err(UserError::NotFound)?
-        };
-
-        let conn = self.db.pg.get().await?;
+    async fn _approve_join_request(&self, id: i32, user_id: i32, request_id: i32) -> AppResult<()> {
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
 
-        let remove_user_stmt = conn
+        let approve_request_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE championship_join_requests
+                SET status = 'Approved', resolved_by = $3, resolved_at = CURRENT_TIMESTAMP
+                WHERE id = $1 AND championship_id = $2 AND status = 'Pending'
+                RETURNING user_id
+            "#,
+        );
+
+        let add_user_stmt_fut = tx.prepare_cached(
+            r#"
+                INSERT INTO championship_users (user_id, championship_id, role)
+                VALUES ($1, $2, 'Visitor')
+                ON CONFLICT (user_id, championship_id) DO NOTHING
+            "#,
+        );
+
+        let (approve_request_stmt, add_user_stmt) =
+            tokio::try_join!(approve_request_stmt_fut, add_user_stmt_fut)?;
+
+        let Some(row) = tx
+            .query_opt(&approve_request_stmt, &[&request_id, &id, &user_id])
+            .await?
+        else {
+            Err(ChampionshipError::JoinRequestNotFound)?
+        };
+
+        let requester_id: i32 = row.get(0);
+
+        tx.execute(&add_user_stmt, &[&requester_id, &id]).await?;
+        tx.commit().await?;
+
+        self.db.cache.championship.delete_by_user(&requester_id);
+
+        Ok(())
+    }
+
+    #[inline]
+    async fn _reject_join_request(&self, id: i32, user_id: i32, request_id: i32) -> AppResult<()> {
+        let conn = self.db.pg.get().await?;
+
+        let reject_request_stmt = conn
+            .prepare_cached(
+                r#"
+                    UPDATE championship_join_requests
+                    SET status = 'Rejected', resolved_by = $3, resolved_at = CURRENT_TIMESTAMP
+                    WHERE id = $1 AND championship_id = $2 AND status = 'Pending'
+                "#,
+            )
+            .await?;
+
+        if conn
+            .execute(&reject_request_stmt, &[&request_id, &id, &user_id])
+            .await?
+            == 0
+        {
+            Err(ChampionshipError::JoinRequestNotFound)?
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    async fn _request_transfer(
+        &self,
+        id: i32,
+        driver: &ChampionshipDriver,
+        team_id: i16,
+        number: i16,
+    ) -> AppResult<()> {
+        let conn = self.db.pg.get().await?;
+
+        // Rejected changes are not queued again until the driver moves somewhere else
+        let request_transfer_stmt = conn
+            .prepare_cached(
+                r#"
+                    INSERT INTO driver_transfers
+                        (steam_name, championship_id, from_team_id, from_number, to_team_id, to_number)
+                    SELECT $1, $2, $3, $4, $5, $6
+                    WHERE NOT EXISTS (
+                        SELECT 1 FROM driver_transfers
+                        WHERE steam_name = $1 AND championship_id = $2 AND status = 'Rejected'
+                            AND from_team_id = $3 AND from_number = $4
+                            AND to_team_id = $5 AND to_number = $6
+                    )
+                    ON CONFLICT (championship_id, steam_name) WHERE status = 'Pending'
+                    DO UPDATE SET
+                        to_team_id = EXCLUDED.to_team_id,
+                        to_number = EXCLUDED.to_number,
+                        created_at = CURRENT_TIMESTAMP
+                    WHERE driver_transfers.to_team_id <> EXCLUDED.to_team_id
+                        OR driver_transfers.to_number <> EXCLUDED.to_number
+                "#,
+            )
+            .await?;
+
+        conn.execute(
+            &request_transfer_stmt,
+            &[
+                &driver.steam_name,
+                &id,
+                &driver.team_id,
+                &driver.number,
+                &team_id,
+                &number,
+            ],
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    #[inline]
+    async fn _approve_transfer(&self, id: i32, user_id: i32, transfer_id: i32) -> AppResult<()> {
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
+
+        let approve_transfer_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE driver_transfers
+                SET status = 'Approved', resolved_by = $3, resolved_at = CURRENT_TIMESTAMP
+                WHERE id = $1 AND championship_id = $2 AND status = 'Pending'
+                RETURNING steam_name, to_team_id, to_number
+            "#,
+        );
+
+        let update_driver_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE championship_drivers
+                SET team_id = $3, number = $4, updated_at = CURRENT_TIMESTAMP
+                WHERE steam_name = $1 AND championship_id = $2
+            "#,
+        );
+
+        let (approve_transfer_stmt, update_driver_stmt) =
+            tokio::try_join!(approve_transfer_stmt_fut, update_driver_stmt_fut)?;
+
+        let Some(row) = tx
+            .query_opt(&approve_transfer_stmt, &[&transfer_id, &id, &user_id])
+            .await?
+        else {
+            Err(ChampionshipError::TransferNotFound)?
+        };
+
+        let steam_name: String = row.get(0);
+        let team_id: i16 = row.get(1);
+        let number: i16 = row.get(2);
+
+        tx.execute(&update_driver_stmt, &[&steam_name, &id, &team_id, &number])
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    #[inline]
+    async fn _reject_transfer(&self, id: i32, user_id: i32, transfer_id: i32) -> AppResult<()> {
+        let conn = self.db.pg.get().await?;
+
+        let reject_transfer_stmt = conn
+            .prepare_cached(
+                r#"
+                    UPDATE driver_transfers
+                    SET status = 'Rejected', resolved_by = $3, resolved_at = CURRENT_TIMESTAMP
+                    WHERE id = $1 AND championship_id = $2 AND status = 'Pending'
+                "#,
+            )
+            .await?;
+
+        let updated = conn
+            .execute(&reject_transfer_stmt, &[&transfer_id, &id, &user_id])
+            .await?;
+
+        if updated == 0 {
+            Err(ChampionshipError::TransferNotFound)?
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the user is an admin of the championship.
+    #[inline]
+    async fn ensure_admin(&self, id: i32, user_id: i32) -> AppResult<()> {
+        if !self.championship_repo.is_admin(id, user_id).await? {
+            Err(ChampionshipError::NotAdmin)?
+        }
+
+        Ok(())
+    }
+
+    async fn _add_race_result(
+        &self,
+        race_id: i32,
+        session_type: i16,
+        result: &SessionResult,
+    ) -> AppResult<()> {
+        let data = result.encode_to_vec();
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
+
+        let add_result_stmt = tx
+            .prepare_cached(
+                r#"
+                    INSERT INTO results (race_id, session_type, data)
+                    VALUES ($1, $2, $3)
+                    ON CONFLICT (race_id, session_type) DO UPDATE SET data = EXCLUDED.data
+                    RETURNING (SELECT championship_id FROM races WHERE id = $1)
+                "#,
+            )
+            .await?;
+
+        let row = tx
+            .query_one(&add_result_stmt, &[&race_id, &session_type, &data])
+            .await?;
+
+        // Amendments made by the admins still apply to a resent classification
+        let result = ResultService::replay_amendments(&tx, race_id, session_type, result).await?;
+
+        tx.commit().await?;
+
+        ResultService::invalidate_caches(self.db, row.get(0), &result);
+
+        Ok(())
+    }
+
+    /// Checks every row of an import, collecting the errors of each one.
+    fn check_import(
+        form: &ChampionshipImportForm,
+        registered: &[ChampionshipDriver],
+    ) -> ImportReport {
+        let mut report = ImportReport::default();
+        report.validate_row(String::from("import"), form);
+
+        let mut drivers: AHashSet<&str> = registered
+            .iter()
+            .map(|driver| driver.steam_name.as_str())
+            .collect();
+
+        for (i, driver) in form.drivers.iter().enumerate() {
+            let row = format!("drivers[{}]", i);
+            report.validate_row(row.clone(), driver);
+
+            if TeamIds::try_from(driver.team_id).is_err() {
+                report.push_error(row.clone(), "Invalid Team Id");
+            }
+
+            if form.drivers[..i]
+                .iter()
+                .any(|other| other.steam_name == driver.steam_name)
+            {
+                report.push_error(row, "Duplicate driver");
+            }
+
+            drivers.insert(&driver.steam_name);
+        }
+
+        for (i, race) in form.races.iter().enumerate() {
+            report.validate_row(format!("races[{}]", i), race);
+
+            for (j, session) in race.sessions.iter().enumerate() {
+                let row = format!("races[{}].sessions[{}]", i, j);
+                report.validate_row(row.clone(), session);
+
+                if SessionType::try_from(session.session_type as u8).is_err() {
+                    report.push_error(row.clone(), "Invalid session type");
+                }
+
+                if race.sessions[..j]
+                    .iter()
+                    .any(|other| other.session_type == session.session_type)
+                {
+                    report.push_error(row.clone(), "Duplicate session type");
+                }
+
+                for (k, classification) in session.classification.iter().enumerate() {
+                    let row = format!("{}.classification[{}]", row, k);
+                    report.validate_row(row.clone(), classification);
+
+                    if !drivers.contains(classification.steam_name.as_str()) {
+                        report.push_error(row.clone(), "Driver not registered in championship");
+                    }
+
+                    let previous = &session.classification[..k];
+
+                    if previous
+                        .iter()
+                        .any(|other| other.steam_name == classification.steam_name)
+                    {
+                        report.push_error(row.clone(), "Duplicate driver");
+                    }
+
+                    if previous
+                        .iter()
+                        .any(|other| other.position == classification.position)
+                    {
+                        report.push_error(row, "Duplicate position");
+                    }
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Internal method to write an import in a single transaction.
+    #[inline]
+    async fn _import(
+        &self,
+        id: i32,
+        form: &ChampionshipImportForm,
+        mut report: ImportReport,
+    ) -> AppResult<ImportReport> {
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
+
+        let create_driver_stmt_fut = tx.prepare_cached(
+            r#"
+                INSERT INTO drivers (steam_name, nationality)
+                VALUES ($1, $2)
+                ON CONFLICT (steam_name) DO NOTHING
+            "#,
+        );
+
+        let register_driver_stmt_fut = tx.prepare_cached(
+            r#"
+                INSERT INTO championship_drivers (steam_name, championship_id, team_id, number)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (steam_name, championship_id) DO UPDATE
+                SET team_id = EXCLUDED.team_id, number = EXCLUDED.number,
+                    updated_at = CURRENT_TIMESTAMP
+            "#,
+        );
+
+        let create_race_stmt_fut = tx.prepare_cached(
+            r#"
+                INSERT INTO races (championship_id, track_id, date)
+                VALUES ($1, $2, $3)
+                RETURNING id
+            "#,
+        );
+
+        let add_result_stmt_fut = tx.prepare_cached(
+            r#"
+                INSERT INTO results (race_id, session_type, data)
+                VALUES ($1, $2, $3)
+            "#,
+        );
+
+        let (create_driver_stmt, register_driver_stmt, create_race_stmt, add_result_stmt) = tokio::try_join!(
+            create_driver_stmt_fut,
+            register_driver_stmt_fut,
+            create_race_stmt_fut,
+            add_result_stmt_fut
+        )?;
+
+        for driver in &form.drivers {
+            tx.execute(
+                &create_driver_stmt,
+                &[&driver.steam_name, &driver.nationality],
+            )
+            .await?;
+
+            tx.execute(
+                &register_driver_stmt,
+                &[&driver.steam_name, &id, &driver.team_id, &driver.number],
+            )
+            .await?;
+        }
+
+        for race in &form.races {
+            let race_id: i32 = tx
+                .query_one(&create_race_stmt, &[&id, &race.track_id, &race.date])
+                .await?
+                .get(0);
+
+            for session in &race.sessions {
+                let data = SessionResult::from(session).encode_to_vec();
+
+                tx.execute(&add_result_stmt, &[&race_id, &session.session_type, &data])
+                    .await?;
+            }
+
+            report.results += race.sessions.len();
+        }
+
+        tx.commit().await?;
+
+        report.drivers = form.drivers.len();
+        report.races = form.races.len();
+
+        self.db.cache.championship.delete_races(&id);
+        self.db.cache.championship.delete_standings(&id);
+
+        let imported_drivers = form
+            .races
+            .iter()
+            .flat_map(|race| &race.sessions)
+            .flat_map(|session| &session.classification);
+
+        for classification in imported_drivers {
+            self.db
+                .cache
+                .driver
+                .delete_stats(&classification.steam_name);
+        }
+
+        Ok(report)
+    }
+
+    /// Internal method to update the role and team of a championship member.
+    #[inline]
+    async fn _update_user(
+        &self,
+        id: i32,
+        member_id: i32,
+        relation: &ChampionshipRelation,
+    ) -> AppResult<()> {
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
+
+        let update_user_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE championship_users
+                SET role = $3, updated_at = CURRENT_TIMESTAMP
+                WHERE championship_id = $1 AND user_id = $2
+            "#,
+        );
+
+        let clear_teams_stmt_fut = tx.prepare_cached(
+            r#"
+                DELETE FROM championship_engineer_teams
+                WHERE championship_id = $1 AND user_id = $2
+            "#,
+        );
+
+        let assign_teams_stmt_fut = tx.prepare_cached(
+            r#"
+                INSERT INTO championship_engineer_teams (championship_id, user_id, team_id)
+                SELECT $1, $2, UNNEST($3::SMALLINT[])
+            "#,
+        );
+
+        let (update_user_stmt, clear_teams_stmt, assign_teams_stmt) = tokio::try_join!(
+            update_user_stmt_fut,
+            clear_teams_stmt_fut,
+            assign_teams_stmt_fut
+        )?;
+
+        tx.execute(&update_user_stmt, &[&id, &member_id, &relation.role])
+            .await?;
+
+        tx.execute(&clear_teams_stmt, &[&id, &member_id]).await?;
+
+        tx.execute(&assign_teams_stmt, &[&id, &member_id, &relation.team_ids])
+            .await?;
+
+        tx.commit().await?;
+
+        self.db.cache.championship.delete_by_user(&member_id);
+
+        Ok(())
+    }
+
+    /// Internal method to offer the ownership of a championship.
+    #[inline]
+    async fn _transfer_ownership(&self, id: i32, user_id: i32, new_owner_id: i32) -> AppResult<()> {
+        let conn = self.db.pg.get().await?;
+
+        let transfer_ownership_stmt = conn
+            .prepare_cached(
+                r#"
+                    INSERT INTO championship_owner_transfers
+                        (championship_id, from_user_id, to_user_id, initiated_by)
+                    VALUES ($1, $2, $3, $2)
+                    ON CONFLICT (championship_id) WHERE status = 'Pending'
+                    DO UPDATE SET
+                        to_user_id = EXCLUDED.to_user_id,
+                        created_at = CURRENT_TIMESTAMP
+                "#,
+            )
+            .await?;
+
+        conn.execute(&transfer_ownership_stmt, &[&id, &user_id, &new_owner_id])
+            .await?;
+
+        Ok(())
+    }
+
+    /// Internal method to change the owner of a championship.
+    ///
+    /// Swaps the relations of both users, team assignments included, and resolves
+    /// the transfer, or records a forced one when no transfer is given.
+    async fn _change_owner(
+        &self,
+        id: i32,
+        owner_id: i32,
+        new_owner_id: i32,
+        transfer_id: Option<i32>,
+        initiated_by: i32,
+    ) -> AppResult<()> {
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
+
+        let new_owner_relation_stmt_fut = tx.prepare_cached(
+            r#"
+                SELECT role FROM championship_users
+                WHERE championship_id = $1 AND user_id = $2
+                FOR UPDATE
+            "#,
+        );
+
+        let clear_owner_teams_stmt_fut = tx.prepare_cached(
+            r#"
+                DELETE FROM championship_engineer_teams
+                WHERE championship_id = $1 AND user_id = $2
+            "#,
+        );
+
+        let move_teams_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE championship_engineer_teams SET user_id = $3
+                WHERE championship_id = $1 AND user_id = $2
+            "#,
+        );
+
+        let change_owner_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE championships SET owner_id = $2
+                WHERE id = $1 AND owner_id = $3
+            "#,
+        );
+
+        let promote_new_owner_stmt_fut = tx.prepare_cached(
+            r#"
+                INSERT INTO championship_users (user_id, championship_id, role)
+                VALUES ($2, $1, 'Admin')
+                ON CONFLICT (user_id, championship_id)
+                DO UPDATE SET role = 'Admin'
+            "#,
+        );
+
+        let demote_owner_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE championship_users SET role = $3
+                WHERE championship_id = $1 AND user_id = $2
+            "#,
+        );
+
+        let (
+            new_owner_relation_stmt,
+            clear_owner_teams_stmt,
+            move_teams_stmt,
+            change_owner_stmt,
+            promote_new_owner_stmt,
+            demote_owner_stmt,
+        ) = tokio::try_join!(
+            new_owner_relation_stmt_fut,
+            clear_owner_teams_stmt_fut,
+            move_teams_stmt_fut,
+            change_owner_stmt_fut,
+            promote_new_owner_stmt_fut,
+            demote_owner_stmt_fut
+        )?;
+
+        let role = match tx
+            .query_opt(&new_owner_relation_stmt, &[&id, &new_owner_id])
+            .await?
+        {
+            Some(row) => row.get::<_, ChampionshipRole>(0),
+            None => ChampionshipRole::Visitor,
+        };
+
+        if tx
+            .execute(&change_owner_stmt, &[&id, &new_owner_id, &owner_id])
+            .await?
+            == 0
+        {
+            Err(ChampionshipError::NotOwner)?
+        }
+
+        tx.execute(&clear_owner_teams_stmt, &[&id, &owner_id])
+            .await?;
+
+        tx.execute(&move_teams_stmt, &[&id, &new_owner_id, &owner_id])
+            .await?;
+
+        tx.execute(&promote_new_owner_stmt, &[&id, &new_owner_id])
+            .await?;
+
+        tx.execute(&demote_owner_stmt, &[&id, &owner_id, &role])
+            .await?;
+
+        match transfer_id {
+            Some(transfer_id) => {
+                let accept_transfer_stmt = tx
+                    .prepare_cached(
+                        r#"
+                            UPDATE championship_owner_transfers
+                            SET status = 'Accepted', resolved_at = CURRENT_TIMESTAMP
+                            WHERE id = $1 AND status = 'Pending'
+                        "#,
+                    )
+                    .await?;
+
+                if tx.execute(&accept_transfer_stmt, &[&transfer_id]).await? == 0 {
+                    Err(ChampionshipError::OwnerTransferNotFound)?
+                }
+            }
+
+            None => {
+                let cancel_transfers_stmt_fut = tx.prepare_cached(
+                    r#"
+                        UPDATE championship_owner_transfers
+                        SET status = 'Cancelled', resolved_at = CURRENT_TIMESTAMP
+                        WHERE championship_id = $1 AND status = 'Pending'
+                    "#,
+                );
+
+                let force_transfer_stmt_fut = tx.prepare_cached(
+                    r#"
+                        INSERT INTO championship_owner_transfers
+                            (championship_id, from_user_id, to_user_id, status, initiated_by, resolved_at)
+                        VALUES ($1, $2, $3, 'Forced', $4, CURRENT_TIMESTAMP)
+                    "#,
+                );
+
+                let (cancel_transfers_stmt, force_transfer_stmt) =
+                    tokio::try_join!(cancel_transfers_stmt_fut, force_transfer_stmt_fut)?;
+
+                tx.execute(&cancel_transfers_stmt, &[&id]).await?;
+                tx.execute(
+                    &force_transfer_stmt,
+                    &[&id, &owner_id, &new_owner_id, &initiated_by],
+                )
+                .await?;
+            }
+        }
+
+        tx.commit().await?;
+
+        let users = self.championship_repo.users(id).await?;
+        self.db.cache.championship.prune(id, users);
+
+        Ok(())
+    }
+
+    /// Internal method to decline the pending ownership transfer.
+    #[inline]
+    async fn _decline_ownership(&self, transfer_id: i32) -> AppResult<()> {
+        let conn = self.db.pg.get().await?;
+
+        let decline_ownership_stmt = conn
+            .prepare_cached(
+                r#"
+                    UPDATE championship_owner_transfers
+                    SET status = 'Declined', resolved_at = CURRENT_TIMESTAMP
+                    WHERE id = $1 AND status = 'Pending'
+                "#,
+            )
+            .await?;
+
+        if conn
+            .execute(&decline_ownership_stmt, &[&transfer_id])
+            .await?
+            == 0
+        {
+            Err(ChampionshipError::OwnerTransferNotFound)?
+        }
+
+        Ok(())
+    }
+
+    /// Finds the pending ownership transfer offered to the user.
+    #[inline]
+    async fn offered_owner_transfer(&self, id: i32, user_id: i32) -> AppResult<OwnerTransfer> {
+        match self.championship_repo.pending_owner_transfer(id).await? {
+            Some(transfer) if transfer.to_user_id == Some(user_id) => Ok(transfer),
+            _ => Err(ChampionshipError::OwnerTransferNotFound)?,
+        }
+    }
+
+    /// Internal method to remove a user from a championship.
+    #[inline]
+    async fn _remove_user(&self, id: i32, remove_user_id: i32) -> AppResult<()> {
+        if self.user_repo.find(remove_user_id).await?.is_none() {
+            Err(UserError::NotFound)?
+        };
+
+        let conn = self.db.pg.get().await?;
+
+        let remove_user_stmt = conn
             .prepare_cached(
                 r#"
                     DELETE FROM championship_users
@@ -463,6 +1594,78 @@ impl ChampionshipService {
         Ok(())
     }
 
+    /// Internal method to soft delete a championship.
+    #[inline]
+    async fn _soft_delete(&self, id: i32) -> AppResult<()> {
+        let conn = self.db.pg.get().await?;
+
+        let soft_delete_stmt = conn
+            .prepare_cached(
+                r#"
+                    UPDATE championships SET deleted_at = CURRENT_TIMESTAMP
+                    WHERE id = $1 AND deleted_at IS NULL
+                "#,
+            )
+            .await?;
+
+        conn.execute(&soft_delete_stmt, &[&id]).await?;
+
+        let users = self.championship_repo.users(id).await?;
+        self.db.cache.championship.prune(id, users);
+
+        Ok(())
+    }
+
+    /// Internal method to restore a soft deleted championship.
+    #[inline]
+    async fn _restore(&self, id: i32) -> AppResult<()> {
+        let conn = self.db.pg.get().await?;
+
+        let restore_stmt = conn
+            .prepare_cached(
+                r#"
+                    UPDATE championships SET deleted_at = NULL
+                    WHERE id = $1
+                "#,
+            )
+            .await?;
+
+        conn.execute(&restore_stmt, &[&id]).await?;
+
+        let users = self.championship_repo.users(id).await?;
+        self.db.cache.championship.prune(id, users);
+
+        Ok(())
+    }
+
+    /// Purges the deleted championships whose grace period ended.
+    pub async fn purge_deleted(&self) -> AppResult<()> {
+        let expired = self
+            .championship_repo
+            .deleted_before(Utc::now() - self.deletion_grace)
+            .await?;
+
+        for id in expired {
+            self._delete(id).await?;
+            info!("Purged deleted championship: {}", id);
+        }
+
+        Ok(())
+    }
+
+    /// Runs the purge of deleted championships periodically, never returns.
+    pub async fn run_purge_job(&self) {
+        let mut interval = tokio::time::interval(CHAMPIONSHIP_PURGE_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.purge_deleted().await {
+                error!("Failed to purge deleted championships: {}", e);
+            }
+        }
+    }
+
     /// Internal method to delete a championship.
     #[inline]
     async fn _delete(&self, id: i32) -> AppResult<()> {
@@ -477,6 +1680,7 @@ impl ChampionshipService {
         let delete_championship_stmt_fut = conn.prepare_cached(
             r#"
                 DELETE FROM championships WHERE id = $1
+                RETURNING port
             "#,
         );
 
@@ -485,14 +1689,25 @@ impl ChampionshipService {
             delete_championship_stmt_fut
         )?;
 
-        let users = self.championship_repo.users(id).await?;
+        let (users, drivers) = tokio::try_join!(
+            self.championship_repo.users(id),
+            self.championship_repo.drivers_linked(id)
+        )?;
 
         conn.execute_raw(&delete_championship_relations_stmt, &[&id])
             .await?;
 
         self.db.cache.championship.prune(id, users);
+        self.db.cache.championship.delete_races(&id);
 
-        conn.execute_raw(&delete_championship_stmt, &[&id]).await?;
+        // The results of the championship no longer count for the career stats
+        for driver in drivers {
+            self.db.cache.driver.delete_stats(&driver.steam_name);
+        }
+
+        if let Some(row) = conn.query_opt(&delete_championship_stmt, &[&id]).await? {
+            self.machine_ports.return_port(row.get(0));
+        }
 
         Ok(())
     }
@@ -532,12 +1747,12 @@ impl ChampionshipServiceOperations for ChampionshipService {
         self._update(id, form).await
     }
 
-    async fn add_user(
+    async fn invite_user(
         &self,
         id: i32,
         user_id: i32,
-        form: ChampionshipUserAddForm,
-    ) -> AppResult<()> {
+        form: &ChampionshipUserAddForm,
+    ) -> AppResult<i32> {
         {
             let Some(championship) = self.championship_repo.find(id).await? else {
                 Err(ChampionshipError::NotFound)?
@@ -548,7 +1763,31 @@ impl ChampionshipServiceOperations for ChampionshipService {
             }
         }
 
-        self._add_user(id, form).await
+        if let Some(invitee) = self.user_repo.find_by_email(&form.email).await? {
+            if self
+                .championship_repo
+                .user_relation(id, invitee.id)
+                .await?
+                .is_some()
+            {
+                Err(ChampionshipError::AlreadyMember)?
+            }
+        }
+
+        self._invite_user(id, user_id, form).await
+    }
+
+    async fn accept_invitation(&self, invitation_id: i32, user: &SharedUser) -> AppResult<()> {
+        let invitation = self
+            .open_invitation(invitation_id, Some(&user.email))
+            .await?;
+
+        self._accept_invitation(&invitation, user.id).await
+    }
+
+    async fn decline_invitation(&self, invitation_id: i32, email: Option<&str>) -> AppResult<()> {
+        let invitation = self.open_invitation(invitation_id, email).await?;
+        self._decline_invitation(invitation.id).await
     }
 
     async fn add_driver(
@@ -562,9 +1801,193 @@ impl ChampionshipServiceOperations for ChampionshipService {
         self._add_driver(id, steam_name, team_id, number).await
     }
 
-    async fn add_race_result(&self, race_id: i32, session_type: i16, data: &[u8]) -> AppResult<()> {
+    async fn request_join(&self, id: i32, user_id: i32) -> AppResult<()> {
+        {
+            let Some(championship) = self.championship_repo.find(id).await? else {
+                Err(ChampionshipError::NotFound)?
+            };
+
+            if championship.visibility == Visibility::Private {
+                Err(ChampionshipError::NotFound)?
+            }
+        }
+
+        if self
+            .championship_repo
+            .user_relation(id, user_id)
+            .await?
+            .is_some()
+        {
+            Err(ChampionshipError::AlreadyMember)?
+        }
+
+        self._request_join(id, user_id).await
+    }
+
+    async fn approve_join_request(&self, id: i32, user_id: i32, request_id: i32) -> AppResult<()> {
+        self.ensure_admin(id, user_id).await?;
+        self._approve_join_request(id, user_id, request_id).await
+    }
+
+    async fn reject_join_request(&self, id: i32, user_id: i32, request_id: i32) -> AppResult<()> {
+        self.ensure_admin(id, user_id).await?;
+        self._reject_join_request(id, user_id, request_id).await
+    }
+
+    async fn request_transfer(
+        &self,
+        id: i32,
+        driver: &ChampionshipDriver,
+        team_id: i16,
+        number: i16,
+    ) -> AppResult<()> {
+        self._request_transfer(id, driver, team_id, number).await
+    }
+
+    async fn approve_transfer(&self, id: i32, user_id: i32, transfer_id: i32) -> AppResult<()> {
+        self.ensure_admin(id, user_id).await?;
+        self._approve_transfer(id, user_id, transfer_id).await
+    }
+
+    async fn reject_transfer(&self, id: i32, user_id: i32, transfer_id: i32) -> AppResult<()> {
+        self.ensure_admin(id, user_id).await?;
+        self._reject_transfer(id, user_id, transfer_id).await
+    }
+
+    async fn add_race_result(
+        &self,
+        race_id: i32,
+        session_type: i16,
+        result: &SessionResult,
+    ) -> AppResult<()> {
         // TODO: Maybe add checks for race_id
-        self._add_race_result(race_id, session_type, data).await
+        self._add_race_result(race_id, session_type, result).await
+    }
+
+    async fn import(
+        &self,
+        id: i32,
+        user_id: i32,
+        form: &ChampionshipImportForm,
+    ) -> AppResult<ImportReport> {
+        if !self.championship_repo.is_admin(id, user_id).await? {
+            Err(ChampionshipError::NotAdmin)?
+        }
+
+        let registered = self.championship_repo.drivers_linked(id).await?;
+        let report = Self::check_import(form, &registered);
+
+        if report.has_errors() {
+            return Ok(report);
+        }
+
+        self._import(id, form, report).await
+    }
+
+    async fn update_user(
+        &self,
+        id: i32,
+        user_id: i32,
+        member_id: i32,
+        form: &ChampionshipUserUpdateForm,
+    ) -> AppResult<ChampionshipRelation> {
+        let Some(championship) = self.championship_repo.find(id).await? else {
+            Err(ChampionshipError::NotFound)?
+        };
+
+        if championship.owner_id == member_id {
+            Err(ChampionshipError::CannotModifyOwner)?
+        }
+
+        let is_owner = championship.owner_id == user_id;
+
+        if !is_owner {
+            self.ensure_admin(id, user_id).await?;
+        }
+
+        let Some(current) = self.championship_repo.user_relation(id, member_id).await? else {
+            Err(UserError::NotFound)?
+        };
+
+        let role = form.role.unwrap_or(current.role);
+
+        // Only the owner can grant or take away the Admin role
+        if !is_owner && (role == ChampionshipRole::Admin || current.role == ChampionshipRole::Admin)
+        {
+            Err(ChampionshipError::NotOwner)?
+        }
+
+        let team_ids = match role {
+            ChampionshipRole::Engineer => {
+                let mut team_ids = match &form.team_ids {
+                    Some(team_ids) => team_ids
+                        .iter()
+                        .map(|valid_team_id| valid_team_id.clone() as i16)
+                        .collect(),
+                    None => current.team_ids,
+                };
+
+                team_ids.sort_unstable();
+                team_ids.dedup();
+
+                if team_ids.is_empty() {
+                    Err(ChampionshipError::InvalidTeamId)?
+                }
+
+                team_ids
+            }
+
+            _ => Vec::new(),
+        };
+
+        let relation = ChampionshipRelation { role, team_ids };
+        self._update_user(id, member_id, &relation).await?;
+
+        Ok(relation)
+    }
+
+    async fn transfer_ownership(&self, id: i32, user_id: i32, new_owner_id: i32) -> AppResult<()> {
+        {
+            let Some(championship) = self.championship_repo.find(id).await? else {
+                Err(ChampionshipError::NotFound)?
+            };
+
+            if championship.owner_id != user_id {
+                Err(ChampionshipError::NotOwner)?
+            }
+
+            if championship.owner_id == new_owner_id {
+                Err(ChampionshipError::AlreadyOwner)?
+            }
+        }
+
+        if self
+            .championship_repo
+            .user_relation(id, new_owner_id)
+            .await?
+            .is_none()
+        {
+            Err(UserError::NotFound)?
+        }
+
+        self._transfer_ownership(id, user_id, new_owner_id).await
+    }
+
+    async fn accept_ownership(&self, id: i32, user_id: i32) -> AppResult<()> {
+        let transfer = self.offered_owner_transfer(id, user_id).await?;
+
+        // A transfer offered by someone who is no longer the owner can't be accepted
+        let Some(owner_id) = transfer.from_user_id else {
+            Err(ChampionshipError::OwnerTransferNotFound)?
+        };
+
+        self._change_owner(id, owner_id, user_id, Some(transfer.id), owner_id)
+            .await
+    }
+
+    async fn decline_ownership(&self, id: i32, user_id: i32) -> AppResult<()> {
+        let transfer = self.offered_owner_transfer(id, user_id).await?;
+        self._decline_ownership(transfer.id).await
     }
 
     async fn remove_user(&self, id: i32, user_id: i32, remove_user_id: i32) -> AppResult<()> {
@@ -601,7 +2024,27 @@ impl ChampionshipServiceOperations for ChampionshipService {
             }
         }
 
-        self._delete(id).await
+        self._soft_delete(id).await
+    }
+
+    async fn restore(&self, id: i32, user_id: i32) -> AppResult<()> {
+        let Some(championship) = self.championship_repo.find_deleted(id).await? else {
+            Err(ChampionshipError::NotFound)?
+        };
+
+        if championship.owner_id != user_id {
+            Err(ChampionshipError::NotOwner)?
+        }
+
+        // The purge job may not have run yet
+        if championship
+            .deleted_at
+            .is_some_and(|deleted_at| deleted_at + self.deletion_grace <= Utc::now())
+        {
+            Err(ChampionshipError::NotFound)?
+        }
+
+        self._restore(id).await
     }
 }
 
@@ -609,4 +2052,26 @@ impl ChampionshipAdminServiceOperations for ChampionshipService {
     async fn admin_delete_championship(&self, id: i32) -> AppResult<()> {
         self._delete(id).await
     }
+
+    async fn admin_transfer_ownership(
+        &self,
+        id: i32,
+        admin_id: i32,
+        new_owner_id: i32,
+    ) -> AppResult<()> {
+        let Some(championship) = self.championship_repo.find(id).await? else {
+            Err(ChampionshipError::NotFound)?
+        };
+
+        if championship.owner_id == new_owner_id {
+            Err(ChampionshipError::AlreadyOwner)?
+        }
+
+        if self.user_repo.find(new_owner_id).await?.is_none() {
+            Err(UserError::NotFound)?
+        }
+
+        self._change_owner(id, championship.owner_id, new_owner_id, None, admin_id)
+            .await
+    }
 }
@@ -1,9 +1,19 @@
+use deadpool_postgres::Transaction;
+use prost::Message;
+use ring::rand::{SecureRandom, SystemRandom};
+use tracing::warn;
+
 use crate::{
     config::Database,
-    error::{AppResult, DriverError},
-    repositories::DriverRepository,
+    entity::{ClaimStatus, DriverClaim},
+    error::{AppResult, ChampionshipError, CommonError, DriverError},
+    repositories::{ChampionshipRepository, DriverRepository},
+    structs::protos::SessionResult,
 };
 
+/// Characters used for claim codes, without the easily confused ones.
+const CLAIM_CODE_CHARSET: &[u8; 32] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
 pub trait DriverServiceOperations {
     async fn create(
         &self,
@@ -14,21 +24,112 @@ pub trait DriverServiceOperations {
 
     // TODO: Implement driver update
     // async fn update(&self, form: DriverUpdateData) -> AppResult<()>;
+
+    /// Requests to link a steam name to a user.
+    ///
+    /// Steam names aren't unique and anyone can race under any name, so the
+    /// telemetry can't prove who owns one. Claims are only resolved by an admin
+    /// of `championship_id`, the returned code lets the claimant identify
+    /// themselves to the admin.
+    ///
+    /// # Arguments
+    /// - `user_id`: The ID of the user claiming the steam name.
+    /// - `steam_name`: The claimed steam name.
+    /// - `championship_id`: Championship whose admins can approve the claim.
+    ///
+    /// # Errors
+    /// Returns an error if the user or driver is already linked, the driver
+    /// is not part of the championship, or a claim is already pending.
+    async fn claim(
+        &self,
+        user_id: i32,
+        steam_name: &str,
+        championship_id: i32,
+    ) -> AppResult<DriverClaim>;
+
+    /// Approves a claim on behalf of a championship admin.
+    ///
+    /// # Arguments
+    /// - `championship_id`: The championship the claim was made for.
+    /// - `user_id`: The ID of the admin approving the claim.
+    /// - `claim_id`: The ID of the claim.
+    ///
+    /// # Errors
+    /// Returns an error if the user is not an admin or the claim is not pending.
+    async fn approve_claim(
+        &self,
+        championship_id: i32,
+        user_id: i32,
+        claim_id: i32,
+    ) -> AppResult<()>;
+
+    /// Rejects a claim on behalf of a championship admin.
+    ///
+    /// # Arguments
+    /// - `championship_id`: The championship the claim was made for.
+    /// - `user_id`: The ID of the admin rejecting the claim.
+    /// - `claim_id`: The ID of the claim.
+    ///
+    /// # Errors
+    /// Returns an error if the user is not an admin or the claim is not pending.
+    async fn reject_claim(
+        &self,
+        championship_id: i32,
+        user_id: i32,
+        claim_id: i32,
+    ) -> AppResult<()>;
 }
 
 pub trait DriverAdminServiceOperations: DriverServiceOperations {
-    #[allow(unused)]
+    /// Deletes a driver and its championship registrations.
+    ///
+    /// # Errors
+    /// Returns an error if the driver is not found.
     async fn admin_delete(&self, steam_name: &str) -> AppResult<()>;
+
+    /// Renames a driver, rewriting every reference to the old steam name.
+    ///
+    /// # Arguments
+    /// - `steam_name`: The current steam name.
+    /// - `new_steam_name`: The steam name to rename the driver to.
+    ///
+    /// # Errors
+    /// Returns an error if the driver is not found or the new name is already taken.
+    async fn admin_rename(&self, steam_name: &str, new_steam_name: &str) -> AppResult<()>;
+
+    /// Merges a driver into another one, moving its registrations and results.
+    ///
+    /// If both drivers are registered in the same championship the target's
+    /// registration is kept. The source driver is deleted afterwards.
+    ///
+    /// # Arguments
+    /// - `source`: The steam name of the driver to merge.
+    /// - `target`: The steam name of the driver that remains.
+    ///
+    /// # Errors
+    /// Returns an error if any of the drivers is not found or both are linked to different users.
+    async fn admin_merge(&self, source: &str, target: &str) -> AppResult<()>;
 }
 
 pub struct DriverService {
     db: &'static Database,
+    rng: SystemRandom,
     driver_repo: &'static DriverRepository,
+    championship_repo: &'static ChampionshipRepository,
 }
 
 impl DriverService {
-    pub async fn new(db: &'static Database, driver_repo: &'static DriverRepository) -> Self {
-        DriverService { db, driver_repo }
+    pub async fn new(
+        db: &'static Database,
+        driver_repo: &'static DriverRepository,
+        championship_repo: &'static ChampionshipRepository,
+    ) -> Self {
+        DriverService {
+            db,
+            rng: SystemRandom::new(),
+            driver_repo,
+            championship_repo,
+        }
     }
 
     async fn _create(
@@ -54,34 +155,484 @@ impl DriverService {
         Ok(())
     }
 
-    async fn _delete(&self, steam_name: &str) -> AppResult<()> {
+    fn claim_code(&self) -> AppResult<String> {
+        let mut bytes = [0u8; 6];
+
+        self.rng
+            .fill(&mut bytes)
+            .map_err(|_| CommonError::InternalServerError)?;
+
+        Ok(bytes
+            .iter()
+            .map(|byte| CLAIM_CODE_CHARSET[(byte % 32) as usize] as char)
+            .collect())
+    }
+
+    async fn _claim(
+        &self,
+        user_id: i32,
+        steam_name: &str,
+        championship_id: i32,
+    ) -> AppResult<DriverClaim> {
+        let code = self.claim_code()?;
         let conn = self.db.pg.get().await?;
 
-        let delete_driver_rel_stmt = conn.prepare_cached(
+        let create_claim_stmt = conn
+            .prepare_cached(
+                r#"
+                    INSERT INTO driver_claims (steam_name, user_id, championship_id, code)
+                    VALUES ($1, $2, $3, $4)
+                    ON CONFLICT (user_id, steam_name) WHERE status = 'Pending' DO NOTHING
+                    RETURNING *
+                "#,
+            )
+            .await?;
+
+        let Some(row) = conn
+            .query_opt(
+                &create_claim_stmt,
+                &[&steam_name, &user_id, &championship_id, &code],
+            )
+            .await?
+        else {
+            Err(DriverError::ClaimAlreadyPending)?
+        };
+
+        Ok(DriverClaim::from_row(&row))
+    }
+
+    /// Links the claimed driver to the user and resolves the claim in a single transaction.
+    async fn _link_claim(&self, claim: &DriverClaim, resolved_by: i32) -> AppResult<()> {
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
+
+        let link_driver_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE drivers
+                SET user_id = $2, updated_at = CURRENT_TIMESTAMP
+                WHERE steam_name = $1 AND user_id IS NULL
+            "#,
+        );
+
+        let resolve_claim_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE driver_claims
+                SET status = 'Approved', resolved_by = $2, resolved_at = CURRENT_TIMESTAMP
+                WHERE id = $1 AND status = 'Pending'
+            "#,
+        );
+
+        let reject_other_claims_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE driver_claims
+                SET status = 'Rejected', resolved_at = CURRENT_TIMESTAMP
+                WHERE steam_name = $1 AND status = 'Pending'
+            "#,
+        );
+
+        let (link_driver_stmt, resolve_claim_stmt, reject_other_claims_stmt) = tokio::try_join!(
+            link_driver_stmt_fut,
+            resolve_claim_stmt_fut,
+            reject_other_claims_stmt_fut
+        )?;
+
+        if tx
+            .execute(&link_driver_stmt, &[&claim.steam_name, &claim.user_id])
+            .await?
+            == 0
+        {
+            Err(DriverError::AlreadyLinked)?
+        }
+
+        if tx
+            .execute(&resolve_claim_stmt, &[&claim.id, &resolved_by])
+            .await?
+            == 0
+        {
+            Err(DriverError::ClaimNotFound)?
+        }
+
+        tx.execute(&reject_other_claims_stmt, &[&claim.steam_name])
+            .await?;
+
+        tx.commit().await?;
+
+        self.db.cache.driver.delete(&claim.steam_name);
+
+        Ok(())
+    }
+
+    async fn _reject_claim(&self, claim_id: i32, user_id: i32) -> AppResult<()> {
+        let conn = self.db.pg.get().await?;
+
+        let reject_claim_stmt = conn
+            .prepare_cached(
+                r#"
+                    UPDATE driver_claims
+                    SET status = 'Rejected', resolved_by = $2, resolved_at = CURRENT_TIMESTAMP
+                    WHERE id = $1 AND status = 'Pending'
+                "#,
+            )
+            .await?;
+
+        if conn
+            .execute(&reject_claim_stmt, &[&claim_id, &user_id])
+            .await?
+            == 0
+        {
+            Err(DriverError::ClaimNotFound)?
+        }
+
+        Ok(())
+    }
+
+    /// Finds a pending claim made for the championship, checking the user is one of its admins.
+    async fn championship_claim(
+        &self,
+        championship_id: i32,
+        user_id: i32,
+        claim_id: i32,
+    ) -> AppResult<DriverClaim> {
+        if !self
+            .championship_repo
+            .is_admin(championship_id, user_id)
+            .await?
+        {
+            Err(ChampionshipError::NotAdmin)?
+        }
+
+        match self.driver_repo.find_claim(claim_id).await? {
+            Some(claim)
+                if claim.championship_id == Some(championship_id)
+                    && claim.status == ClaimStatus::Pending =>
+            {
+                Ok(claim)
+            }
+
+            _ => Err(DriverError::ClaimNotFound)?,
+        }
+    }
+
+    async fn _delete(&self, steam_name: &str) -> AppResult<()> {
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
+
+        let delete_driver_rel_stmt = tx.prepare_cached(
             r#"
                 DELETE FROM championship_drivers
                 WHERE steam_name = $1
+                RETURNING championship_id
+            "#,
+        );
+
+        let reject_claims_stmt = tx.prepare_cached(
+            r#"
+                UPDATE driver_claims
+                SET status = 'Rejected', resolved_at = CURRENT_TIMESTAMP
+                WHERE steam_name = $1 AND status = 'Pending'
             "#,
         );
 
-        let delete_driver_stmt = conn.prepare_cached(
+        let delete_driver_stmt = tx.prepare_cached(
             r#"
                 DELETE FROM drivers
                 WHERE steam_name = $1
             "#,
         );
 
-        let (delete_driver_rel, delete_driver) =
-            tokio::try_join!(delete_driver_rel_stmt, delete_driver_stmt)?;
+        let (delete_driver_rel, reject_claims, delete_driver) = tokio::try_join!(
+            delete_driver_rel_stmt,
+            reject_claims_stmt,
+            delete_driver_stmt
+        )?;
+
+        let championship_ids = tx
+            .query(&delete_driver_rel, &[&steam_name])
+            .await?
+            .iter()
+            .map(|row| row.get(0))
+            .collect::<Vec<i32>>();
+
+        tx.execute(&reject_claims, &[&steam_name]).await?;
+        tx.execute(&delete_driver, &[&steam_name]).await?;
+
+        tx.commit().await?;
+
+        self.db.cache.driver.delete(steam_name);
+        self.delete_standings(&championship_ids);
+
+        Ok(())
+    }
+
+    async fn _rename(&self, steam_name: &str, new_steam_name: &str) -> AppResult<()> {
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
+
+        let championship_ids = Self::rename_in_results(&tx, steam_name, new_steam_name).await?;
+
+        // Championship registrations and transfers follow through `ON UPDATE CASCADE`
+        let rename_driver_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE drivers
+                SET steam_name = $2, updated_at = CURRENT_TIMESTAMP
+                WHERE steam_name = $1
+            "#,
+        );
+
+        let rename_claims_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE driver_claims
+                SET steam_name = $2
+                WHERE steam_name = $1
+            "#,
+        );
 
-        conn.execute_raw(&delete_driver_rel, &[&steam_name]).await?;
+        let (rename_driver_stmt, rename_claims_stmt) =
+            tokio::try_join!(rename_driver_stmt_fut, rename_claims_stmt_fut)?;
 
-        conn.execute_raw(&delete_driver, &[&steam_name]).await?;
+        tx.execute(&rename_driver_stmt, &[&steam_name, &new_steam_name])
+            .await?;
+
+        tx.execute(&rename_claims_stmt, &[&steam_name, &new_steam_name])
+            .await?;
+
+        tx.commit().await?;
 
         self.db.cache.driver.delete(steam_name);
+        self.db.cache.driver.delete(new_steam_name);
+        self.delete_standings(&championship_ids);
 
         Ok(())
     }
+
+    async fn _merge(&self, source: &str, target: &str, user_id: Option<i32>) -> AppResult<()> {
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
+
+        let championship_ids = Self::rename_in_results(&tx, source, target).await?;
+
+        let move_registrations_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE championship_drivers cd
+                SET steam_name = $2, updated_at = CURRENT_TIMESTAMP
+                WHERE cd.steam_name = $1 AND NOT EXISTS (
+                    SELECT 1 FROM championship_drivers t
+                    WHERE t.steam_name = $2 AND t.championship_id = cd.championship_id
+                )
+            "#,
+        );
+
+        let delete_pending_transfers_stmt_fut = tx.prepare_cached(
+            r#"
+                DELETE FROM driver_transfers
+                WHERE steam_name = $1 AND status = 'Pending'
+            "#,
+        );
+
+        let move_transfers_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE driver_transfers
+                SET steam_name = $2
+                WHERE steam_name = $1
+            "#,
+        );
+
+        let reject_claims_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE driver_claims
+                SET status = 'Rejected', resolved_at = CURRENT_TIMESTAMP
+                WHERE steam_name = $1 AND status = 'Pending'
+            "#,
+        );
+
+        let move_claims_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE driver_claims
+                SET steam_name = $2
+                WHERE steam_name = $1
+            "#,
+        );
+
+        let delete_driver_stmt_fut = tx.prepare_cached(
+            r#"
+                DELETE FROM drivers
+                WHERE steam_name = $1
+            "#,
+        );
+
+        let link_user_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE drivers
+                SET user_id = $2, updated_at = CURRENT_TIMESTAMP
+                WHERE steam_name = $1
+            "#,
+        );
+
+        let (
+            move_registrations_stmt,
+            delete_pending_transfers_stmt,
+            move_transfers_stmt,
+            reject_claims_stmt,
+            move_claims_stmt,
+            delete_driver_stmt,
+            link_user_stmt,
+        ) = tokio::try_join!(
+            move_registrations_stmt_fut,
+            delete_pending_transfers_stmt_fut,
+            move_transfers_stmt_fut,
+            reject_claims_stmt_fut,
+            move_claims_stmt_fut,
+            delete_driver_stmt_fut,
+            link_user_stmt_fut
+        )?;
+
+        tx.execute(&move_registrations_stmt, &[&source, &target])
+            .await?;
+        tx.execute(&delete_pending_transfers_stmt, &[&source])
+            .await?;
+        tx.execute(&move_transfers_stmt, &[&source, &target])
+            .await?;
+        tx.execute(&reject_claims_stmt, &[&source]).await?;
+        tx.execute(&move_claims_stmt, &[&source, &target]).await?;
+
+        // Remaining registrations of the source cascade with the driver
+        tx.execute(&delete_driver_stmt, &[&source]).await?;
+
+        tx.execute(&link_user_stmt, &[&target, &user_id]).await?;
+
+        tx.commit().await?;
+
+        self.db.cache.driver.delete(source);
+        self.db.cache.driver.delete(target);
+        self.delete_standings(&championship_ids);
+
+        Ok(())
+    }
+
+    /// Invalidates the cached standings of the championships a driver change touched.
+    #[inline]
+    fn delete_standings(&self, championship_ids: &[i32]) {
+        for championship_id in championship_ids {
+            self.db.cache.championship.delete_standings(championship_id);
+        }
+    }
+
+    /// Renames a driver inside every stored result of the championships it's registered in,
+    /// along with the amendments and incidents it was involved in.
+    ///
+    /// Returns the championships the driver is registered in.
+    async fn rename_in_results(tx: &Transaction<'_>, from: &str, to: &str) -> AppResult<Vec<i32>> {
+        let driver_results_stmt_fut = tx.prepare_cached(
+            r#"
+                SELECT r.race_id, r.session_type, r.data, r.amended_data, ra.championship_id
+                FROM results r
+                JOIN races ra ON ra.id = r.race_id
+                JOIN championship_drivers cd ON cd.championship_id = ra.championship_id
+                WHERE cd.steam_name = $1
+                FOR UPDATE OF r
+            "#,
+        );
+
+        let update_result_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE results
+                SET data = COALESCE($3, data), amended_data = COALESCE($4, amended_data)
+                WHERE race_id = $1 AND session_type = $2
+            "#,
+        );
+
+        let rename_amendments_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE result_amendments
+                SET steam_name = CASE WHEN steam_name = $1 THEN $2 ELSE steam_name END,
+                    classification_order = ARRAY_REPLACE(classification_order, $1, $2)
+                WHERE steam_name = $1 OR $1 = ANY(classification_order)
+            "#,
+        );
+
+        let rename_incidents_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE incidents
+                SET steam_name = CASE WHEN steam_name = $1 THEN $2 ELSE steam_name END,
+                    other_steam_name = CASE WHEN other_steam_name = $1 THEN $2 ELSE other_steam_name END
+                WHERE steam_name = $1 OR other_steam_name = $1
+            "#,
+        );
+
+        let (
+            driver_results_stmt,
+            update_result_stmt,
+            rename_amendments_stmt,
+            rename_incidents_stmt,
+        ) = tokio::try_join!(
+            driver_results_stmt_fut,
+            update_result_stmt_fut,
+            rename_amendments_stmt_fut,
+            rename_incidents_stmt_fut
+        )?;
+
+        let rows = tx.query(&driver_results_stmt, &[&from]).await?;
+        let mut championship_ids = Vec::new();
+
+        for row in rows {
+            let race_id: i32 = row.get(0);
+            let session_type: i16 = row.get(1);
+            let championship_id: i32 = row.get(4);
+
+            if !championship_ids.contains(&championship_id) {
+                championship_ids.push(championship_id);
+            }
+
+            let data = Self::rename_in_result(row.get(2), from, to);
+            let amended_data = row
+                .get::<_, Option<&[u8]>>(3)
+                .and_then(|amended_data| Self::rename_in_result(amended_data, from, to));
+
+            if data.is_none() && amended_data.is_none() {
+                continue;
+            }
+
+            tx.execute(
+                &update_result_stmt,
+                &[&race_id, &session_type, &data, &amended_data],
+            )
+            .await?;
+        }
+
+        tx.execute(&rename_amendments_stmt, &[&from, &to]).await?;
+        tx.execute(&rename_incidents_stmt, &[&from, &to]).await?;
+
+        Ok(championship_ids)
+    }
+
+    /// Renames a driver inside an encoded session result.
+    ///
+    /// Returns the new encoded result, or `None` if the driver isn't classified in it.
+    fn rename_in_result(data: &[u8], from: &str, to: &str) -> Option<Vec<u8>> {
+        let mut result = match SessionResult::decode(data) {
+            Ok(result) => result,
+            Err(e) => {
+                warn!("Error decoding session result: {}", e);
+                return None;
+            }
+        };
+
+        let classification = result.classification.remove(from)?;
+
+        result
+            .classification
+            .entry(to.to_owned())
+            .or_insert(classification);
+
+        if let Some(lap_history) = result.lap_history.remove(from) {
+            result
+                .lap_history
+                .entry(to.to_owned())
+                .or_insert(lap_history);
+        }
+
+        Some(result.encode_to_vec())
+    }
 }
 
 impl DriverServiceOperations for DriverService {
@@ -97,10 +648,111 @@ impl DriverServiceOperations for DriverService {
 
         self._create(steam_name, nationality, user_id).await
     }
+
+    async fn claim(
+        &self,
+        user_id: i32,
+        steam_name: &str,
+        championship_id: i32,
+    ) -> AppResult<DriverClaim> {
+        if self.driver_repo.find_by_user(user_id).await?.is_some() {
+            Err(DriverError::UserAlreadyLinked)?
+        }
+
+        if let Some(driver) = self.driver_repo.find(steam_name).await? {
+            if driver.user_id.is_some() {
+                Err(DriverError::AlreadyLinked)?
+            }
+        }
+
+        let drivers = self
+            .championship_repo
+            .drivers_linked(championship_id)
+            .await?;
+
+        if !drivers.iter().any(|driver| driver.steam_name == steam_name) {
+            Err(DriverError::NotFound)?
+        }
+
+        self._claim(user_id, steam_name, championship_id).await
+    }
+
+    async fn approve_claim(
+        &self,
+        championship_id: i32,
+        user_id: i32,
+        claim_id: i32,
+    ) -> AppResult<()> {
+        let claim = self
+            .championship_claim(championship_id, user_id, claim_id)
+            .await?;
+
+        if self
+            .driver_repo
+            .find_by_user(claim.user_id)
+            .await?
+            .is_some()
+        {
+            Err(DriverError::UserAlreadyLinked)?
+        }
+
+        self._link_claim(&claim, user_id).await
+    }
+
+    async fn reject_claim(
+        &self,
+        championship_id: i32,
+        user_id: i32,
+        claim_id: i32,
+    ) -> AppResult<()> {
+        let claim = self
+            .championship_claim(championship_id, user_id, claim_id)
+            .await?;
+
+        self._reject_claim(claim.id, user_id).await
+    }
 }
 
 impl DriverAdminServiceOperations for DriverService {
     async fn admin_delete(&self, steam_name: &str) -> AppResult<()> {
+        if self.driver_repo.find(steam_name).await?.is_none() {
+            Err(DriverError::NotFound)?
+        }
+
         self._delete(steam_name).await
     }
+
+    async fn admin_rename(&self, steam_name: &str, new_steam_name: &str) -> AppResult<()> {
+        if self.driver_repo.find(steam_name).await?.is_none() {
+            Err(DriverError::NotFound)?
+        }
+
+        if self.driver_repo.find(new_steam_name).await?.is_some() {
+            Err(DriverError::AlreadyExists)?
+        }
+
+        self._rename(steam_name, new_steam_name).await
+    }
+
+    async fn admin_merge(&self, source: &str, target: &str) -> AppResult<()> {
+        if source == target {
+            Err(CommonError::ValidationFailed)?
+        }
+
+        let (Some(source_driver), Some(target_driver)) =
+            tokio::try_join!(self.driver_repo.find(source), self.driver_repo.find(target))?
+        else {
+            Err(DriverError::NotFound)?
+        };
+
+        let user_id = match (source_driver.user_id, target_driver.user_id) {
+            (Some(source_user), Some(target_user)) if source_user != target_user => {
+                Err(DriverError::AlreadyLinked)?
+            }
+
+            (source_user, target_user) => target_user.or(source_user),
+        };
+
+        self._merge(source, target, user_id).await
+    }
 }
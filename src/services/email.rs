@@ -45,6 +45,30 @@ impl EmailService {
     /// # Returns
     /// `AppResult<()>`: Ok if successfully queued, Err otherwise.
     pub async fn send_mail<T>(&self, user: SharedUser, subject: &str, body: T) -> AppResult<()>
+    where
+        T: TemplateSimple,
+    {
+        self.send_mail_to(&user.email, Some(&user.username), subject, body)
+            .await
+    }
+
+    /// Sends an email to an address that may not belong to a registered user.
+    ///
+    /// # Parameters
+    /// - `email`: Recipient address.
+    /// - `name`: Recipient display name, if known.
+    /// - `subject`: Email subject.
+    /// - `body`: Email body (must implement `TemplateOnce`).
+    ///
+    /// # Returns
+    /// `AppResult<()>`: Ok if successfully queued, Err otherwise.
+    pub async fn send_mail_to<T>(
+        &self,
+        email: &str,
+        name: Option<&str>,
+        subject: &str,
+        body: T,
+    ) -> AppResult<()>
     where
         T: TemplateSimple,
     {
@@ -54,8 +78,8 @@ impl EmailService {
                 Address::from_str(&dotenvy::var("EMAIL_FROM").unwrap()).unwrap(),
             ))
             .to(Mailbox::new(
-                Some(user.username.to_owned()),
-                Address::from_str(&user.email).unwrap(),
+                name.map(ToOwned::to_owned),
+                Address::from_str(email).unwrap(),
             ))
             .subject(subject)
             .header(ContentType::TEXT_HTML)
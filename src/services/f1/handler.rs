@@ -15,7 +15,7 @@ use crate::{
 pub use super::{
     firewall::FirewallService,
     manager::F1SessionDataManager,
-    service::{F1Service, F1ServiceData},
+    service::{EngineerUpdate, F1Service, F1ServiceData, TeamAccess, TeamSubscription},
 };
 
 /// Manages F1 championship services, including caching, subscriptions, and service lifecycle.
@@ -35,10 +35,41 @@ impl F1ServiceHandler {
         Self { services, f1_state }
     }
 
-    /// Subscribes to a team-specific channel for a championship service.
-    pub fn subscribe_team(&self, championship_id: &i32, team_id: u8) -> Option<Receiver<Bytes>> {
+    /// Subscribes to the team-specific channels a member can access for a championship service.
+    ///
+    /// The second receiver notifies about team changes of engineers.
+    pub fn subscribe_teams(
+        &self,
+        championship_id: &i32,
+        access: &TeamAccess,
+    ) -> Option<TeamSubscription> {
+        let service = self.services.get(championship_id)?;
+        service.team_sub(access)
+    }
+
+    /// Subscribes to the channel of a team that showed up after the stream opened.
+    #[inline]
+    pub fn join_team(
+        &self,
+        championship_id: &i32,
+        access: &TeamAccess,
+        team_id: u8,
+    ) -> Option<(u8, Receiver<Bytes>)> {
         let service = self.services.get(championship_id)?;
-        service.team_sub(team_id)
+        service.team_join(access, team_id)
+    }
+
+    /// Re-checks the open engineer streams of a member after their teams changed.
+    ///
+    /// # Arguments
+    /// - `championship_id`: The ID of the championship.
+    /// - `user_id`: The ID of the member.
+    /// - `access`: The teams the member can stream now.
+    #[inline]
+    pub fn update_engineer(&self, championship_id: &i32, user_id: i32, access: TeamAccess) {
+        if let Some(service) = self.services.get(championship_id) {
+            service.update_engineer(EngineerUpdate { user_id, access });
+        }
     }
 
     /// Retrieves cache and subscribes to a channel for a specific championship service.
@@ -67,11 +98,13 @@ impl F1ServiceHandler {
         }
     }
 
-    /// Unsubscribes from the team-specific channel of a championship service.
+    /// Unsubscribes from the team-specific channels of a championship service.
     #[inline]
-    pub fn unsubscribe_team(&self, championship_id: &i32, team_id: u8) {
+    pub fn unsubscribe_teams(&self, championship_id: &i32, team_ids: &[u8]) {
         if let Some(service) = self.services.get(championship_id) {
-            service.team_unsub(team_id);
+            for &team_id in team_ids {
+                service.team_unsub(team_id);
+            }
         }
     }
 
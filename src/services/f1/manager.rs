@@ -1,4 +1,4 @@
-use std::{ops::Deref, sync::Arc};
+use std::{collections::hash_map::Entry, ops::Deref, sync::Arc};
 
 use ahash::{AHashMap, AHashSet};
 use ntex::{
@@ -17,11 +17,13 @@ use crate::{
     config::constants::{GENERAL_INTERVAL, TELEMETRY_INTERVAL},
     structs::{
         protos::*, PacketCarDamageData, PacketCarStatusData, PacketCarTelemetryData,
-        PacketEventData, PacketFinalClassificationData, PacketMotionData, PacketParticipantsData,
-        PacketSessionData, PacketSessionHistoryData,
+        PacketEventData, PacketFinalClassificationData, PacketLapData, PacketMotionData,
+        PacketParticipantsData, PacketSessionData, PacketSessionHistoryData,
     },
 };
 
+use super::timing::TimingTracker;
+
 #[derive(Debug)]
 pub struct DriverInfo {
     pub name: Box<str>,
@@ -33,10 +35,13 @@ pub struct F1SessionDataManagerInner {
     driver_info: RwLock<AHashMap<usize, DriverInfo>>,
     general: RwLock<F1GeneralInfo>,
     telemetry: RwLock<F1TelemetryInfo>,
+    timing: RwLock<TimingTracker>,
     last_general: RwLock<F1GeneralInfo>,
     last_general_encoded: RwLock<Option<Bytes>>,
     last_telemetry: RwLock<F1TelemetryInfo>,
     team_senders: RwLock<AHashMap<u8, Sender<Bytes>>>,
+    /// Announces the teams whose channel opens after streams subscribed.
+    new_teams: Sender<u8>,
     stop_sender: Mutex<Option<oneshot::Sender<()>>>,
 }
 
@@ -59,10 +64,12 @@ impl F1SessionDataManager {
             driver_info: RwLock::new(AHashMap::new()),
             general: RwLock::new(F1GeneralInfo::default()),
             telemetry: RwLock::new(F1TelemetryInfo::default()),
+            timing: RwLock::new(TimingTracker::default()),
             last_general: RwLock::new(F1GeneralInfo::default()),
             last_general_encoded: RwLock::new(None),
             last_telemetry: RwLock::new(F1TelemetryInfo::default()),
             team_senders: RwLock::new(AHashMap::new()),
+            new_teams: Sender::new(32),
             stop_sender: Mutex::new(None),
         });
 
@@ -76,6 +83,34 @@ impl F1SessionDataManager {
         self.last_general_encoded.read().clone()
     }
 
+    /// Teams with a telemetry channel, one per team seen in the session.
+    pub fn team_ids(&self) -> Vec<u8> {
+        self.team_senders.read().keys().copied().collect()
+    }
+
+    /// Steam name of the driver in the given car index, if registered.
+    pub fn driver_name(&self, car_idx: u8) -> Option<String> {
+        self.driver_info
+            .read()
+            .get(&(car_idx as usize))
+            .map(|driver| driver.name.to_string())
+    }
+
+    /// Subscribes to the teams whose channel opens from now on.
+    pub fn subscribe_new_teams(&self) -> Receiver<u8> {
+        self.new_teams.subscribe()
+    }
+
+    /// Opens the telemetry channel of a team the first time it's seen.
+    pub fn open_team_channel(&self, team_id: u8) {
+        if let Entry::Vacant(entry) = self.team_senders.write().entry(team_id) {
+            entry.insert(Sender::new(30));
+
+            // Fails only when no stream waits for new teams
+            let _ = self.new_teams.send(team_id);
+        }
+    }
+
     pub fn get_team_receiver(&self, team_id: u8) -> Option<Receiver<Bytes>> {
         self.team_senders
             .read()
@@ -119,6 +154,20 @@ impl F1SessionDataManager {
     pub fn save_session(&self, packet: &PacketSessionData) {
         let mut general = self.general.write();
         general.update_session(packet);
+
+        self.timing.write().set_track_length(packet.track_length);
+    }
+
+    /// Drops the gaps of the previous session, line crossings don't carry over.
+    #[inline]
+    pub fn reset_timing(&self) {
+        self.timing.write().reset();
+    }
+
+    #[inline]
+    pub fn save_lap_data(&self, packet: &PacketLapData) {
+        let session_time = packet.header.session_time;
+        self.timing.write().update(&packet.lap_data, session_time);
     }
 
     #[inline]
@@ -138,7 +187,6 @@ impl F1SessionDataManager {
         let mut driver_info = self.driver_info.write();
         let mut general = self.general.write();
         let mut telemetry = self.telemetry.write();
-        let mut team_senders = self.team_senders.write();
 
         for i in 0..packet.num_active_cars as usize {
             let Some(participant) = packet.participants.get(i) else {
@@ -174,9 +222,7 @@ impl F1SessionDataManager {
                     new_player
                 });
 
-            team_senders
-                .entry(participant.team_id)
-                .or_insert_with(|| Sender::new(30));
+            self.open_team_channel(participant.team_id);
         }
     }
 
@@ -215,6 +261,32 @@ impl F1SessionDataManager {
         }
     }
 
+    /// Builds the final classification of every player to be stored as the session result.
+    pub fn session_result(&self) -> SessionResult {
+        let general = self.general.read();
+
+        SessionResult {
+            classification: general
+                .players
+                .iter()
+                .filter_map(|(name, player)| {
+                    let classification = player.final_classification.clone()?;
+                    Some((name.clone(), classification))
+                })
+                .collect(),
+            lap_history: general
+                .players
+                .iter()
+                .filter(|(_, player)| player.final_classification.is_some())
+                .filter_map(|(name, player)| {
+                    let lap_history = player.lap_history.clone()?;
+                    Some((name.clone(), lap_history))
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
     #[inline]
     fn process_telemetry_packet<T, F>(&self, packet_data: &[T], mut process_fn: F)
     where
@@ -265,6 +337,8 @@ impl F1SessionDataManager {
             return;
         }
 
+        Self::update_timing(inner);
+
         let general = inner.general.read();
         let mut last_general = inner.last_general.write();
         let mut last_general_encoded = inner.last_general_encoded.write();
@@ -279,6 +353,27 @@ impl F1SessionDataManager {
         }
     }
 
+    /// Writes the latest gaps and intervals into each player's info.
+    #[inline]
+    fn update_timing(inner: &Arc<F1SessionDataManagerInner>) {
+        let tower = inner.timing.read().compute();
+
+        if tower.is_empty() {
+            return;
+        }
+
+        let driver_info = inner.driver_info.read();
+        let mut general = inner.general.write();
+
+        for (idx, timing) in tower {
+            if let Some(driver) = driver_info.get(&idx) {
+                if let Some(player) = general.players.get_mut(driver.name.as_ref()) {
+                    player.timing = Some(timing);
+                }
+            }
+        }
+    }
+
     #[inline]
     fn send_telemetry_updates(inner: &Arc<F1SessionDataManagerInner>) {
         let driver_info = inner.driver_info.read();
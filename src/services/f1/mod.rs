@@ -2,6 +2,7 @@ mod firewall;
 mod handler;
 mod manager;
 mod service;
+mod timing;
 
 pub(crate) use handler::*;
 pub(crate) use manager::DriverInfo;
@@ -15,8 +15,8 @@ use parking_lot::RwLock;
 use tokio::{
     net::UdpSocket,
     sync::{
-        broadcast::{Receiver, Sender},
-        oneshot,
+        broadcast::{channel, Receiver, Sender},
+        mpsc, oneshot,
     },
     time::{timeout, Instant},
 };
@@ -24,16 +24,21 @@ use tracing::{error, info, info_span, warn};
 
 use crate::{
     config::constants::{
-        BUFFER_SIZE, HISTORY_INTERVAL, MOTION_INTERVAL, SESSION_INTERVAL, SOCKET_HOST,
-        SOCKET_TIMEOUT, TELEMETRY_INTERVAL,
+        BUFFER_SIZE, HISTORY_INTERVAL, INCIDENT_QUEUE_SIZE, MOTION_INTERVAL, SESSION_INTERVAL,
+        SOCKET_HOST, SOCKET_TIMEOUT, TELEMETRY_INTERVAL,
     },
+    entity::{ChampionshipRelation, ChampionshipRole, IncidentType},
     error::{AppResult, CommonError, F1ServiceError},
-    services::{ChampionshipServiceOperations, DriverServiceOperations},
+    services::{
+        ChampionshipServiceOperations, DriverServiceOperations, IncidentService,
+        IncidentServiceOperations,
+    },
     states::F1State,
     structs::{
-        F1PacketData, PacketCarDamageData, PacketCarStatusData, PacketCarTelemetryData,
-        PacketEventData, PacketFinalClassificationData, PacketMotionData, PacketParticipantsData,
-        PacketSessionData, PacketSessionHistoryData, SessionType,
+        EventCode, F1PacketData, NewIncident, PacketCarDamageData, PacketCarStatusData,
+        PacketCarTelemetryData, PacketEventData, PacketFinalClassificationData, PacketLapData,
+        PacketMotionData, PacketParticipantsData, PacketSessionData, PacketSessionHistoryData,
+        SessionType,
     },
 };
 
@@ -51,7 +56,11 @@ pub struct F1Service {
     last_updates: LastUpdates,
     socket: UdpSocket,
     shutdown: oneshot::Receiver<()>,
+    session_uid: u64,
     session_type: Option<SessionType>,
+    current_laps: [u8; 22],
+    /// Incidents waiting to be saved, off the packet loop.
+    incidents: mpsc::Sender<NewIncident>,
     data_manager: F1SessionDataManager,
     services: &'static DashMap<i32, F1ServiceData>,
     f1_state: &'static F1State,
@@ -61,6 +70,57 @@ pub struct F1ServiceDataInner {
     global_channel: Sender<Bytes>,
     global_subscribers: AtomicU32,
     team_subscribers: RwLock<AHashMap<u8, u32>>,
+    engineer_updates: Sender<EngineerUpdate>,
+}
+
+/// Notifies open engineer streams that the teams of a member changed.
+#[derive(Debug, Clone)]
+pub struct EngineerUpdate {
+    pub user_id: i32,
+    pub access: TeamAccess,
+}
+
+/// Receivers of the allowed team channels, the teams opened later and the engineer updates.
+pub type TeamSubscription = (
+    Vec<(u8, Receiver<Bytes>)>,
+    Receiver<u8>,
+    Receiver<EngineerUpdate>,
+);
+
+/// Teams a championship member can follow the telemetry of.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TeamAccess {
+    None,
+    Teams(Vec<u8>),
+    All,
+}
+
+impl From<&ChampionshipRelation> for TeamAccess {
+    fn from(relation: &ChampionshipRelation) -> Self {
+        match relation.role {
+            ChampionshipRole::RaceDirector => TeamAccess::All,
+            ChampionshipRole::Engineer if !relation.team_ids.is_empty() => TeamAccess::Teams(
+                relation
+                    .team_ids
+                    .iter()
+                    .map(|&team_id| team_id as u8)
+                    .collect(),
+            ),
+            _ => TeamAccess::None,
+        }
+    }
+}
+
+impl TeamAccess {
+    /// Checks if the telemetry of a team can be followed.
+    #[inline]
+    pub fn allows(&self, team_id: u8) -> bool {
+        match self {
+            TeamAccess::None => false,
+            TeamAccess::Teams(team_ids) => team_ids.contains(&team_id),
+            TeamAccess::All => true,
+        }
+    }
 }
 
 /// Holds data related to an F1 service instance.
@@ -116,13 +176,34 @@ impl F1Service {
             last_updates: LastUpdates::new(),
             shutdown,
             socket: UdpSocket::bind("0.0.0.0:0").await.unwrap(),
+            session_uid: 0,
             session_type: None,
+            current_laps: [0; 22],
+            incidents: Self::spawn_incident_recorder(f1_state.incident_svc),
             data_manager,
             services,
             f1_state,
         }
     }
 
+    /// Spawns the task saving the incidents of the session, it ends with the service.
+    #[inline]
+    fn spawn_incident_recorder(
+        incident_svc: &'static IncidentService,
+    ) -> mpsc::Sender<NewIncident> {
+        let (tx, mut rx) = mpsc::channel::<NewIncident>(INCIDENT_QUEUE_SIZE);
+
+        ntex::rt::spawn(async move {
+            while let Some(incident) = rx.recv().await {
+                if let Err(e) = incident_svc.record(&incident).await {
+                    warn!("Error recording incident: {}", e);
+                }
+            }
+        });
+
+        tx
+    }
+
     /// Initializes the F1 service with a specific port and championship ID.
     ///
     /// # Arguments
@@ -249,11 +330,18 @@ impl F1Service {
             return Ok(());
         }
 
+        if header.session_uid != self.session_uid {
+            self.session_uid = header.session_uid;
+            self.current_laps = [0; 22];
+            self.data_manager.reset_timing();
+        }
+
         match packet {
             F1PacketData::Motion(motion_data) => self.handle_motion_packet(motion_data, now),
             F1PacketData::Session(session_data) => {
                 self.handle_session_packet(session_data, now).await
             }
+            F1PacketData::LapData(lap_data) => self.handle_lap_data_packet(lap_data),
             F1PacketData::Participants(participants_data) => {
                 self.handle_participants_packet(participants_data, now)
                     .await?
@@ -309,6 +397,23 @@ impl F1Service {
         self.last_updates.session = now;
     }
 
+    #[inline]
+    fn handle_lap_data_packet(&mut self, lap_data: &PacketLapData) {
+        let Some(session_type) = &self.session_type else {
+            return;
+        };
+
+        if ![SessionType::R, SessionType::R2, SessionType::R3].contains(session_type) {
+            return;
+        }
+
+        for (current_lap, lap) in self.current_laps.iter_mut().zip(lap_data.lap_data.iter()) {
+            *current_lap = lap.current_lap_num;
+        }
+
+        self.data_manager.save_lap_data(lap_data);
+    }
+
     #[inline]
     async fn handle_participants_packet(
         &mut self,
@@ -335,15 +440,78 @@ impl F1Service {
 
     #[inline]
     fn handle_event_packet(&mut self, event_data: &PacketEventData) {
-        let Some(session_type) = &self.session_type else {
+        let Some(session_type) = self.session_type else {
             return;
         };
 
-        if ![SessionType::R, SessionType::R2, SessionType::R3].contains(session_type) {
+        if ![SessionType::R, SessionType::R2, SessionType::R3].contains(&session_type) {
             return;
         }
 
         self.data_manager.push_event(event_data);
+
+        let Some(incident) = self.incident_from_event(session_type, event_data) else {
+            return;
+        };
+
+        // Never wait on the database here, a full queue means it can't keep up
+        if let Err(e) = self.incidents.try_send(incident) {
+            warn!("Error queueing incident: {}", e);
+        }
+    }
+
+    /// Builds the incident to be reviewed by the stewards from a collision or penalty event.
+    fn incident_from_event(
+        &self,
+        session_type: SessionType,
+        event_data: &PacketEventData,
+    ) -> Option<NewIncident> {
+        let (incident_type, vehicle_idx, other_vehicle_idx, lap, penalty) =
+            match EventCode::try_from(&event_data.event_string_code).ok()? {
+                EventCode::Collision => {
+                    let collision = unsafe { &event_data.event_details.collision };
+                    let lap = *self.current_laps.get(collision.vehicle1_idx as usize)?;
+
+                    (
+                        IncidentType::Collision,
+                        collision.vehicle1_idx,
+                        collision.vehicle2_idx,
+                        lap,
+                        None,
+                    )
+                }
+
+                EventCode::PenaltyIssued => {
+                    let penalty = unsafe { &event_data.event_details.penalty };
+
+                    (
+                        IncidentType::Penalty,
+                        penalty.vehicle_idx,
+                        penalty.other_vehicle_idx,
+                        penalty.lap_num,
+                        Some(*penalty),
+                    )
+                }
+
+                _ => return None,
+            };
+
+        Some(NewIncident {
+            race_id: self.race_id,
+            session_type: session_type as i16,
+            incident_type,
+            lap: lap as i16,
+            session_time: event_data.header.session_time,
+            steam_name: self.data_manager.driver_name(vehicle_idx)?,
+            other_steam_name: self.data_manager.driver_name(other_vehicle_idx),
+            penalty_type: penalty.map(|penalty| penalty.penalty_type as i16),
+            infringement_type: penalty.map(|penalty| penalty.infringement_type as i16),
+            // 255 when the penalty has no time attached
+            game_penalty_time: penalty
+                .map(|penalty| penalty.time)
+                .filter(|&time| time != 255)
+                .map(|time| time as i16),
+        })
     }
 
     #[inline]
@@ -372,20 +540,21 @@ impl F1Service {
         &mut self,
         final_classification: &PacketFinalClassificationData,
     ) -> AppResult<()> {
-        let Some(_session_type) = self.session_type.take() else {
+        let Some(session_type) = self.session_type.take() else {
             error!("Not defined session type when trying to save final_classification_data");
             return Ok(());
         };
 
-        // Only testing, we should save last lastHistoryData with the final_classification as a tuple or something
-        // self.f1_state
-        //     .championship_svc
-        //     .add_race_result(self.race_id, session_type as i16, &[0, 0, 0])
-        //     .await?;
-
         self.data_manager
             .save_final_classification(final_classification);
 
+        let session_result = self.data_manager.session_result();
+
+        self.f1_state
+            .championship_svc
+            .add_race_result(self.race_id, session_type as i16, &session_result)
+            .await?;
+
         Ok(())
     }
 
@@ -425,7 +594,7 @@ impl F1Service {
             .drivers_linked(self.championship_id)
             .await?;
 
-        drivers.sort_unstable();
+        drivers.sort_unstable_by(|a, b| a.steam_name.cmp(&b.steam_name));
 
         for idx in 0..participants_data.num_active_cars {
             let Some(participant) = participants_data.participants.get(idx as usize) else {
@@ -455,19 +624,29 @@ impl F1Service {
                     .await?;
             }
 
-            if drivers
-                .binary_search_by(|probe| probe.as_str().cmp(steam_name))
-                .is_err()
-            {
-                self.f1_state
-                    .championship_svc
-                    .add_driver(
-                        self.championship_id,
-                        steam_name,
-                        participant.team_id as i16,
-                        participant.race_number as i16,
-                    )
-                    .await?;
+            let registered = drivers
+                .binary_search_by(|probe| probe.steam_name.as_str().cmp(steam_name))
+                .map(|idx| &drivers[idx]);
+
+            let team_id = participant.team_id as i16;
+            let number = participant.race_number as i16;
+
+            match registered {
+                Ok(driver) => {
+                    if driver.team_id != team_id || driver.number != number {
+                        self.f1_state
+                            .championship_svc
+                            .request_transfer(self.championship_id, driver, team_id, number)
+                            .await?;
+                    }
+                }
+
+                Err(_) => {
+                    self.f1_state
+                        .championship_svc
+                        .add_driver(self.championship_id, steam_name, team_id, number)
+                        .await?;
+                }
             }
         }
 
@@ -501,6 +680,7 @@ impl F1ServiceData {
             global_channel,
             global_subscribers: AtomicU32::new(0),
             team_subscribers: RwLock::new(AHashMap::new()),
+            engineer_updates: channel(16).0,
         });
 
         Self {
@@ -522,12 +702,46 @@ impl F1ServiceData {
         self.global_channel.subscribe()
     }
 
-    /// Subscribes to a team-specific broadcast channel.
-    pub fn team_sub(&self, team_id: u8) -> Option<Receiver<Bytes>> {
+    /// Subscribes to the broadcast channels of the allowed teams and to the engineer updates.
+    ///
+    /// Teams without a channel yet are announced by the second receiver once
+    /// they show up, to be joined with `team_join`.
+    ///
+    /// Returns `None` if the member can't follow any team.
+    pub fn team_sub(&self, access: &TeamAccess) -> Option<TeamSubscription> {
+        if *access == TeamAccess::None {
+            return None;
+        }
+
+        // Subscribed first so a team opened meanwhile isn't missed
+        let new_teams = self.session_manager.subscribe_new_teams();
+
+        let receivers: Vec<_> = self
+            .session_manager
+            .team_ids()
+            .into_iter()
+            .filter_map(|team_id| self.team_join(access, team_id))
+            .collect();
+
+        Some((receivers, new_teams, self.engineer_updates.subscribe()))
+    }
+
+    /// Subscribes to the channel of a team if the member can follow it.
+    pub fn team_join(&self, access: &TeamAccess, team_id: u8) -> Option<(u8, Receiver<Bytes>)> {
+        if !access.allows(team_id) {
+            return None;
+        }
+
         let receiver = self.session_manager.get_team_receiver(team_id)?;
-        let mut team_subs = self.team_subscribers.write();
-        *team_subs.entry(team_id).or_insert(0) += 1;
-        Some(receiver)
+        *self.team_subscribers.write().entry(team_id).or_insert(0) += 1;
+
+        Some((team_id, receiver))
+    }
+
+    /// Notifies the open engineer streams that the team of a member changed.
+    pub fn update_engineer(&self, update: EngineerUpdate) {
+        // Fails only when no engineer stream is open
+        let _ = self.engineer_updates.send(update);
     }
 
     /// Gets the current number of global subscribers.
@@ -583,3 +797,31 @@ impl LastUpdates {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ntex::test]
+    async fn race_director_joins_teams_opened_later() {
+        let (tx, _) = channel::<Bytes>(8);
+        let session_manager = F1SessionDataManager::new(tx.clone());
+        let service = F1ServiceData::new(session_manager.clone(), tx, oneshot::channel().0);
+
+        // Opened before any participant showed up
+        let (receivers, mut new_teams, _) = service.team_sub(&TeamAccess::All).unwrap();
+        assert!(receivers.is_empty());
+
+        session_manager.open_team_channel(3);
+        session_manager.open_team_channel(3);
+        assert_eq!(new_teams.try_recv().unwrap(), 3);
+        assert!(new_teams.try_recv().is_err());
+
+        let (team_id, _rx) = service.team_join(&TeamAccess::All, 3).unwrap();
+        assert_eq!(team_id, 3);
+        assert_eq!(service.team_count(3), 1);
+
+        assert!(service.team_join(&TeamAccess::Teams(vec![1]), 3).is_none());
+        assert!(service.team_sub(&TeamAccess::None).is_none());
+    }
+}
@@ -0,0 +1,245 @@
+use crate::{
+    config::constants::GAP_SAMPLE_DISTANCE,
+    structs::{protos::TimingData, LapData},
+};
+
+/// Timing state of a single car, sampled by distance travelled.
+#[derive(Debug, Default)]
+struct CarTiming {
+    active: bool,
+    position: u8,
+    total_distance: f32,
+    /// Session time at which the car reached each sample point of the race.
+    crossings: Vec<f32>,
+}
+
+impl CarTiming {
+    /// Records the session time for every sample point reached up to `total_distance`.
+    #[inline]
+    fn record(&mut self, total_distance: f32, session_time: f32) {
+        // Line hasn't been crossed yet
+        if total_distance < 0f32 {
+            return;
+        }
+
+        let sample = (total_distance / GAP_SAMPLE_DISTANCE) as usize;
+
+        // Flashbacks move the car backwards, forget the samples ahead of it
+        self.crossings.truncate(sample + 1);
+
+        while self.crossings.len() <= sample {
+            self.crossings.push(session_time);
+        }
+    }
+}
+
+/// Computes live gaps between cars from lap distance and session timing.
+///
+/// Every car records the session time at which it passes each sample point
+/// (every `GAP_SAMPLE_DISTANCE` metres of total distance). Gaps are then the
+/// difference between the times two cars crossed the same point.
+#[derive(Debug, Default)]
+pub struct TimingTracker {
+    track_length: f32,
+    cars: [CarTiming; 22],
+}
+
+impl TimingTracker {
+    /// Forgets every car, used when a new session starts.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Updates the track length used to detect lapped cars.
+    #[inline]
+    pub fn set_track_length(&mut self, track_length: u16) {
+        self.track_length = track_length as f32;
+    }
+
+    /// Records the latest lap data of every car.
+    ///
+    /// # Arguments
+    /// - `lap_data`: Lap data for all cars.
+    /// - `session_time`: Session timestamp of the packet.
+    pub fn update(&mut self, lap_data: &[LapData], session_time: f32) {
+        for (car, data) in self.cars.iter_mut().zip(lap_data) {
+            let total_distance = data.total_distance;
+
+            // 2 = active, 3 = finished
+            car.active = matches!(data.result_status, 2 | 3) && data.car_position != 0;
+            car.position = data.car_position;
+            car.total_distance = total_distance;
+
+            if car.active {
+                car.record(total_distance, session_time);
+            }
+        }
+    }
+
+    /// Computes the timing tower for every active car.
+    ///
+    /// # Returns
+    /// A vector of car indexes with their timing data, ordered by position.
+    pub fn compute(&self) -> Vec<(usize, TimingData)> {
+        let mut order: Vec<usize> = (0..self.cars.len())
+            .filter(|&idx| self.cars[idx].active)
+            .collect();
+
+        order.sort_unstable_by_key(|&idx| self.cars[idx].position);
+
+        let Some(&leader_idx) = order.first() else {
+            return Vec::new();
+        };
+
+        let leader = &self.cars[leader_idx];
+        let mut tower = Vec::with_capacity(order.len());
+
+        tower.push((
+            leader_idx,
+            TimingData {
+                position: Some(leader.position as u32),
+                ..Default::default()
+            },
+        ));
+
+        for window in order.windows(2) {
+            let ahead = &self.cars[window[0]];
+            let car = &self.cars[window[1]];
+
+            let (gap_to_leader, laps_to_leader) = self.gap(leader, car);
+            let (interval, laps_to_ahead) = self.gap(ahead, car);
+
+            tower.push((
+                window[1],
+                TimingData {
+                    position: Some(car.position as u32),
+                    gap_to_leader,
+                    laps_to_leader: Some(laps_to_leader),
+                    interval,
+                    laps_to_ahead: Some(laps_to_ahead),
+                },
+            ));
+        }
+
+        tower
+    }
+
+    /// Gap in milliseconds between `car` and the car in front of it (`reference`).
+    ///
+    /// If the car is a lap or more behind, the gap is expressed in laps instead.
+    #[inline]
+    fn gap(&self, reference: &CarTiming, car: &CarTiming) -> (Option<u32>, u32) {
+        if self.track_length > 0f32 {
+            let laps = ((reference.total_distance - car.total_distance) / self.track_length)
+                .floor()
+                .max(0f32) as u32;
+
+            if laps > 0 {
+                return (None, laps);
+            }
+        }
+
+        let Some(sample) = car.crossings.len().checked_sub(1) else {
+            return (None, 0);
+        };
+
+        let gap = match (car.crossings.get(sample), reference.crossings.get(sample)) {
+            (Some(car_time), Some(reference_time)) => {
+                Some(((car_time - reference_time).max(0f32) * 1000f32) as u32)
+            }
+            _ => None,
+        };
+
+        (gap, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker(track_length: u16) -> TimingTracker {
+        let mut tracker = TimingTracker::default();
+        tracker.set_track_length(track_length);
+        tracker
+    }
+
+    fn place(tracker: &mut TimingTracker, idx: usize, position: u8, distance: f32, time: f32) {
+        let car = &mut tracker.cars[idx];
+        car.active = true;
+        car.position = position;
+        car.total_distance = distance;
+        car.record(distance, time);
+    }
+
+    #[test]
+    fn gap_to_leader_and_interval() {
+        let mut tracker = tracker(5000);
+
+        place(&mut tracker, 0, 1, 100f32, 10f32);
+        place(&mut tracker, 1, 2, 100f32, 11f32);
+        place(&mut tracker, 2, 3, 100f32, 11.5);
+
+        let tower = tracker.compute();
+
+        assert_eq!(tower.len(), 3);
+        assert_eq!(tower[0].0, 0);
+        assert_eq!(tower[0].1.gap_to_leader, None);
+        assert_eq!(tower[1].1.gap_to_leader, Some(1000));
+        assert_eq!(tower[1].1.interval, Some(1000));
+        assert_eq!(tower[2].1.gap_to_leader, Some(1500));
+        assert_eq!(tower[2].1.interval, Some(500));
+    }
+
+    #[test]
+    fn lapped_cars_report_laps() {
+        let mut tracker = tracker(5000);
+
+        place(&mut tracker, 0, 1, 11_000f32, 200f32);
+        place(&mut tracker, 1, 2, 10_500f32, 198f32);
+        place(&mut tracker, 2, 3, 4_000f32, 199f32);
+
+        let tower = tracker.compute();
+        let last = &tower[2].1;
+
+        assert_eq!(last.gap_to_leader, None);
+        assert_eq!(last.laps_to_leader, Some(1));
+        assert_eq!(last.interval, None);
+        assert_eq!(last.laps_to_ahead, Some(1));
+    }
+
+    #[test]
+    fn flashback_discards_samples_ahead() {
+        let mut tracker = tracker(5000);
+
+        place(&mut tracker, 0, 1, 1_000f32, 20f32);
+        place(&mut tracker, 0, 1, 400f32, 21f32);
+
+        assert_eq!(
+            tracker.cars[0].crossings.len(),
+            (400f32 / GAP_SAMPLE_DISTANCE) as usize + 1
+        );
+    }
+
+    #[test]
+    fn reset_forgets_previous_session() {
+        let mut tracker = tracker(5000);
+
+        place(&mut tracker, 0, 1, 1_000f32, 20f32);
+        tracker.reset();
+
+        assert!(tracker.cars[0].crossings.is_empty());
+        assert!(tracker.compute().is_empty());
+    }
+
+    #[test]
+    fn inactive_cars_are_ignored() {
+        let mut tracker = tracker(5000);
+
+        place(&mut tracker, 3, 1, 100f32, 10f32);
+        tracker.cars[3].active = false;
+
+        assert!(tracker.compute().is_empty());
+    }
+}
@@ -0,0 +1,296 @@
+use crate::{
+    config::Database,
+    error::{AppResult, IncidentError},
+    repositories::{ChampionshipRepository, IncidentRepository},
+    services::ResultService,
+    structs::{AmendmentKind, NewIncident, ResultAmendmentForm},
+};
+
+/// Defines the operations for recording and reviewing race incidents.
+pub trait IncidentServiceOperations {
+    /// Records an incident reported by the game during a session.
+    ///
+    /// # Arguments
+    ///
+    /// * `incident` - The incident data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's a database error.
+    async fn record(&self, incident: &NewIncident) -> AppResult<()>;
+
+    /// Adds a steward comment to an incident.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the championship.
+    /// * `user_id` - The ID of the steward commenting.
+    /// * `incident_id` - The ID of the incident.
+    /// * `body` - The comment text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user is not a steward or the incident is not found.
+    async fn comment(&self, id: i32, user_id: i32, incident_id: i32, body: &str) -> AppResult<()>;
+
+    /// Resolves an incident applying a post-race time penalty to the involved driver.
+    ///
+    /// The penalty is stored as an amendment of the session result.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the championship.
+    /// * `user_id` - The ID of the steward applying the penalty.
+    /// * `incident_id` - The ID of the incident.
+    /// * `seconds` - The penalty time in seconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user is not a steward, the incident is not found or
+    /// already resolved, or the driver is not classified in the session result.
+    async fn apply_penalty(
+        &self,
+        id: i32,
+        user_id: i32,
+        incident_id: i32,
+        seconds: i16,
+    ) -> AppResult<()>;
+
+    /// Resolves an incident without further action.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the championship.
+    /// * `user_id` - The ID of the steward dismissing the incident.
+    /// * `incident_id` - The ID of the incident.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user is not a steward or the incident is not found or
+    /// already resolved.
+    async fn dismiss(&self, id: i32, user_id: i32, incident_id: i32) -> AppResult<()>;
+}
+
+/// Implementation of the incident service.
+pub struct IncidentService {
+    db: &'static Database,
+    championship_repo: &'static ChampionshipRepository,
+    incident_repo: &'static IncidentRepository,
+}
+
+impl IncidentService {
+    /// Creates a new IncidentService instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database connection.
+    /// * `championship_repo` - The championship repository.
+    /// * `incident_repo` - The incident repository.
+    pub fn new(
+        db: &'static Database,
+        championship_repo: &'static ChampionshipRepository,
+        incident_repo: &'static IncidentRepository,
+    ) -> Self {
+        Self {
+            db,
+            championship_repo,
+            incident_repo,
+        }
+    }
+
+    /// Checks that the user is a steward and the incident belongs to the championship.
+    #[inline]
+    async fn ensure_reviewable(&self, id: i32, user_id: i32, incident_id: i32) -> AppResult<()> {
+        let (is_steward, incident) = tokio::try_join!(
+            self.championship_repo.is_steward(id, user_id),
+            self.incident_repo.find(id, incident_id)
+        )?;
+
+        if !is_steward {
+            Err(IncidentError::NotSteward)?
+        }
+
+        let Some(incident) = incident else {
+            Err(IncidentError::NotFound)?
+        };
+
+        if !incident.is_open() {
+            Err(IncidentError::AlreadyResolved)?
+        }
+
+        Ok(())
+    }
+
+    /// Internal method to record an incident.
+    #[inline]
+    async fn _record(&self, incident: &NewIncident) -> AppResult<()> {
+        let conn = self.db.pg.get().await?;
+
+        let record_incident_stmt = conn
+            .prepare_cached(
+                r#"
+                    INSERT INTO incidents (race_id, session_type, incident_type, lap, session_time,
+                        steam_name, other_steam_name, penalty_type, infringement_type, game_penalty_time)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                "#,
+            )
+            .await?;
+
+        conn.execute(
+            &record_incident_stmt,
+            &[
+                &incident.race_id,
+                &incident.session_type,
+                &incident.incident_type,
+                &incident.lap,
+                &incident.session_time,
+                &incident.steam_name,
+                &incident.other_steam_name,
+                &incident.penalty_type,
+                &incident.infringement_type,
+                &incident.game_penalty_time,
+            ],
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Internal method to comment an incident.
+    #[inline]
+    async fn _comment(&self, user_id: i32, incident_id: i32, body: &str) -> AppResult<()> {
+        let conn = self.db.pg.get().await?;
+
+        let comment_incident_stmt = conn
+            .prepare_cached(
+                r#"
+                    INSERT INTO incident_comments (incident_id, user_id, body)
+                    VALUES ($1, $2, $3)
+                "#,
+            )
+            .await?;
+
+        conn.execute(&comment_incident_stmt, &[&incident_id, &user_id, &body])
+            .await?;
+
+        Ok(())
+    }
+
+    /// Internal method to apply a penalty and rewrite the session classification.
+    #[inline]
+    async fn _apply_penalty(
+        &self,
+        id: i32,
+        user_id: i32,
+        incident_id: i32,
+        seconds: i16,
+    ) -> AppResult<()> {
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
+
+        let resolve_incident_stmt = tx
+            .prepare_cached(
+                r#"
+                    UPDATE incidents
+                    SET status = 'PenaltyApplied', penalty_seconds = $2, resolved_by = $3,
+                        resolved_at = CURRENT_TIMESTAMP
+                    WHERE id = $1 AND status = 'Open'
+                    RETURNING race_id, session_type, steam_name
+                "#,
+            )
+            .await?;
+
+        let Some(row) = tx
+            .query_opt(&resolve_incident_stmt, &[&incident_id, &seconds, &user_id])
+            .await?
+        else {
+            Err(IncidentError::AlreadyResolved)?
+        };
+
+        // The penalty is stored as an amendment so the game classification stays untouched
+        let form = ResultAmendmentForm {
+            amendment: AmendmentKind::TimePenalty {
+                steam_name: row.get(2),
+                seconds,
+            },
+            reason: None,
+        };
+
+        let result = ResultService::apply_amendment(
+            &tx,
+            id,
+            row.get(0),
+            row.get(1),
+            &form,
+            Some(incident_id),
+            user_id,
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        ResultService::invalidate_caches(self.db, id, &result);
+
+        Ok(())
+    }
+
+    /// Internal method to dismiss an incident.
+    #[inline]
+    async fn _dismiss(&self, user_id: i32, incident_id: i32) -> AppResult<()> {
+        let conn = self.db.pg.get().await?;
+
+        let dismiss_incident_stmt = conn
+            .prepare_cached(
+                r#"
+                    UPDATE incidents
+                    SET status = 'Dismissed', resolved_by = $2, resolved_at = CURRENT_TIMESTAMP
+                    WHERE id = $1 AND status = 'Open'
+                "#,
+            )
+            .await?;
+
+        if conn
+            .execute(&dismiss_incident_stmt, &[&incident_id, &user_id])
+            .await?
+            == 0
+        {
+            Err(IncidentError::AlreadyResolved)?
+        }
+
+        Ok(())
+    }
+}
+
+impl IncidentServiceOperations for IncidentService {
+    async fn record(&self, incident: &NewIncident) -> AppResult<()> {
+        self._record(incident).await
+    }
+
+    async fn comment(&self, id: i32, user_id: i32, incident_id: i32, body: &str) -> AppResult<()> {
+        if !self.championship_repo.is_steward(id, user_id).await? {
+            Err(IncidentError::NotSteward)?
+        }
+
+        if self.incident_repo.find(id, incident_id).await?.is_none() {
+            Err(IncidentError::NotFound)?
+        }
+
+        self._comment(user_id, incident_id, body).await
+    }
+
+    async fn apply_penalty(
+        &self,
+        id: i32,
+        user_id: i32,
+        incident_id: i32,
+        seconds: i16,
+    ) -> AppResult<()> {
+        self.ensure_reviewable(id, user_id, incident_id).await?;
+        self._apply_penalty(id, user_id, incident_id, seconds).await
+    }
+
+    async fn dismiss(&self, id: i32, user_id: i32, incident_id: i32) -> AppResult<()> {
+        self.ensure_reviewable(id, user_id, incident_id).await?;
+        self._dismiss(user_id, incident_id).await
+    }
+}
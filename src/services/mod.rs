@@ -1,13 +1,23 @@
+pub(crate) use api_key::*;
 pub(crate) use championship::*;
 pub(crate) use driver::*;
 pub(crate) use email::*;
 pub(crate) use f1::*;
+pub(crate) use incident::*;
+pub(crate) use oauth::*;
+pub(crate) use result::*;
 pub(crate) use token::*;
+pub(crate) use two_factor::*;
 pub(crate) use user::*;
 
+mod api_key;
 mod championship;
 mod driver;
 mod email;
 mod f1;
+mod incident;
+mod oauth;
+mod result;
 mod token;
+mod two_factor;
 mod user;
@@ -0,0 +1,180 @@
+use base64_simd::URL_SAFE_NO_PAD;
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::{
+    config::Database,
+    entity::{Provider, SharedUser},
+    error::{AppResult, CommonError, OAuthError, UserError},
+    repositories::{code_challenge, OAuthRepository, UserRepository},
+    structs::{OAuthCallback, OAuthState, UserRegistrationData},
+};
+
+use super::{UserService, UserServiceOperations};
+
+/// Result of a completed external authorization.
+pub enum OAuthOutcome {
+    /// The user signed in, creating the account on the first login.
+    SignedIn(SharedUser),
+    /// The provider was linked to the signed in user that started the authorization.
+    Linked,
+}
+
+/// Runs the login and account linking flows of the external providers.
+pub struct OAuthService {
+    db: &'static Database,
+    oauth_repo: &'static OAuthRepository,
+    user_repo: &'static UserRepository,
+    user_svc: &'static UserService,
+    rng: SystemRandom,
+}
+
+impl OAuthService {
+    /// Creates a new OAuthService instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database connection.
+    /// * `oauth_repo` - The external providers repository.
+    /// * `user_repo` - The user repository.
+    /// * `user_svc` - The user service.
+    pub fn new(
+        db: &'static Database,
+        oauth_repo: &'static OAuthRepository,
+        user_repo: &'static UserRepository,
+        user_svc: &'static UserService,
+    ) -> Self {
+        Self {
+            db,
+            oauth_repo,
+            user_repo,
+            user_svc,
+            rng: SystemRandom::new(),
+        }
+    }
+
+    /// Starts an authorization with a provider.
+    ///
+    /// A random `state` and PKCE verifier are kept server side until the
+    /// provider redirects back, so the callback can't be forged or replayed.
+    /// The state is returned along with the URL so it can be bound to the browser.
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - The provider to authorize with.
+    /// * `link_user_id` - The signed in user linking the provider, None to sign in.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the provider is disabled.
+    pub fn authorize_url(
+        &self,
+        provider: Provider,
+        link_user_id: Option<i32>,
+    ) -> AppResult<(String, String)> {
+        let state = self.random_token()?;
+        let code_verifier = self.random_token()?;
+
+        let authorize_url =
+            self.oauth_repo
+                .authorize_url(provider, &state, &code_challenge(&code_verifier))?;
+
+        self.db.cache.token.set_oauth_state(
+            state.clone(),
+            OAuthState {
+                provider,
+                code_verifier,
+                link_user_id,
+            },
+        );
+
+        Ok((authorize_url, state))
+    }
+
+    /// Completes an authorization when the provider redirects back.
+    ///
+    /// Users are found by their linked identity. Unknown identities create a new
+    /// account if the provider shares a verified email that's not in use.
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - The provider redirecting back.
+    /// * `callback` - Query of the redirect.
+    /// * `browser_state` - The state bound to the browser that started the authorization.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state is unknown, expired or not bound to the browser,
+    /// the provider rejects the authorization, or the identity can't be linked or
+    /// used to sign in.
+    pub async fn callback(
+        &self,
+        provider: Provider,
+        callback: &OAuthCallback,
+        browser_state: Option<&str>,
+    ) -> AppResult<OAuthOutcome> {
+        // A link completes for the user kept with the state, the cookie proves it's their browser
+        let Some(oauth_state) = self
+            .db
+            .cache
+            .token
+            .take_oauth_state(&callback.state, browser_state)
+            .filter(|oauth_state| oauth_state.provider == provider)
+        else {
+            Err(OAuthError::InvalidState)?
+        };
+
+        let account = self
+            .oauth_repo
+            .account(provider, callback, &oauth_state.code_verifier)
+            .await?;
+
+        if let Some(user_id) = oauth_state.link_user_id {
+            self.user_svc
+                .link_identity(user_id, provider, &account.provider_user_id)
+                .await?;
+
+            return Ok(OAuthOutcome::Linked);
+        }
+
+        if let Some(user) = self
+            .user_repo
+            .find_by_identity(provider, &account.provider_user_id)
+            .await?
+        {
+            return Ok(OAuthOutcome::SignedIn(user));
+        }
+
+        // Without an email the identity has to be linked from an existing account
+        let Some(email) = account.email.clone() else {
+            Err(OAuthError::AccountNotLinked)?
+        };
+
+        // TODO: Implement a redirect to website to show a ui error
+        if self.user_repo.user_exists(&email).await? {
+            Err(UserError::WrongProvider)?
+        }
+
+        let id = self
+            .user_svc
+            .create(UserRegistrationData::from_oauth_account(email, account))
+            .await?;
+
+        let Some(user) = self.user_repo.find(id).await? else {
+            Err(UserError::NotFound)?
+        };
+
+        Ok(OAuthOutcome::SignedIn(user))
+    }
+
+    /// Generates a random URL safe token, used for states and PKCE verifiers.
+    #[inline]
+    fn random_token(&self) -> AppResult<String> {
+        let mut bytes = [0u8; 32];
+
+        self.rng
+            .fill(&mut bytes)
+            .map_err(|_| CommonError::InternalServerError)?;
+
+        Ok(URL_SAFE_NO_PAD.encode_to_string(bytes))
+    }
+}
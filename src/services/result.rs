@@ -0,0 +1,260 @@
+use deadpool_postgres::Transaction;
+use prost::Message;
+use tracing::warn;
+
+use crate::{
+    config::Database,
+    entity::ResultAmendment,
+    error::{AppResult, ChampionshipError, ResultError},
+    repositories::ChampionshipRepository,
+    structs::{protos::SessionResult, AmendmentKind, ResultAmendmentForm},
+};
+
+/// Defines the operations for correcting stored race session results.
+pub trait ResultServiceOperations {
+    /// Amends the classification of a race session.
+    ///
+    /// The original result is kept untouched, the amendment is stored and applied on
+    /// top of the current effective classification.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the championship.
+    /// * `user_id` - The ID of the admin amending the result.
+    /// * `race_id` - The ID of the race.
+    /// * `session_type` - The session type of the result.
+    /// * `form` - The amendment to apply.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user is not an admin, the result is not found or the
+    /// amendment doesn't match the classification.
+    async fn amend(
+        &self,
+        id: i32,
+        user_id: i32,
+        race_id: i32,
+        session_type: i16,
+        form: &ResultAmendmentForm,
+    ) -> AppResult<()>;
+}
+
+/// Implementation of the result service.
+pub struct ResultService {
+    db: &'static Database,
+    championship_repo: &'static ChampionshipRepository,
+}
+
+impl ResultService {
+    /// Creates a new ResultService instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database connection.
+    /// * `championship_repo` - The championship repository.
+    pub fn new(db: &'static Database, championship_repo: &'static ChampionshipRepository) -> Self {
+        Self {
+            db,
+            championship_repo,
+        }
+    }
+
+    /// Stores an amendment and updates the effective classification of the session.
+    ///
+    /// # Returns
+    ///
+    /// The effective classification after the amendment.
+    pub async fn apply_amendment(
+        tx: &Transaction<'_>,
+        championship_id: i32,
+        race_id: i32,
+        session_type: i16,
+        form: &ResultAmendmentForm,
+        incident_id: Option<i32>,
+        user_id: i32,
+    ) -> AppResult<SessionResult> {
+        let session_result_stmt_fut = tx.prepare_cached(
+            r#"
+                SELECT COALESCE(r.amended_data, r.data) FROM results r
+                JOIN races ra ON ra.id = r.race_id
+                WHERE r.race_id = $1 AND r.session_type = $2 AND ra.championship_id = $3
+                FOR UPDATE OF r
+            "#,
+        );
+
+        let insert_amendment_stmt_fut = tx.prepare_cached(
+            r#"
+                INSERT INTO result_amendments (race_id, session_type, amendment_type, steam_name,
+                    seconds, classification_order, reason, incident_id, created_by)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        );
+
+        let update_result_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE results
+                SET amended_data = $3
+                WHERE race_id = $1 AND session_type = $2
+            "#,
+        );
+
+        let (session_result_stmt, insert_amendment_stmt, update_result_stmt) = tokio::try_join!(
+            session_result_stmt_fut,
+            insert_amendment_stmt_fut,
+            update_result_stmt_fut
+        )?;
+
+        let Some(row) = tx
+            .query_opt(
+                &session_result_stmt,
+                &[&race_id, &session_type, &championship_id],
+            )
+            .await?
+        else {
+            Err(ResultError::NotFound)?
+        };
+
+        let Ok(mut result) = SessionResult::decode(row.get::<_, &[u8]>(0)) else {
+            Err(ResultError::NotFound)?
+        };
+
+        let amendment = &form.amendment;
+        amendment.apply(&mut result)?;
+
+        tx.execute(
+            &insert_amendment_stmt,
+            &[
+                &race_id,
+                &session_type,
+                &amendment.amendment_type(),
+                &amendment.steam_name(),
+                &amendment.seconds(),
+                &amendment.order(),
+                &form.reason,
+                &incident_id,
+                &user_id,
+            ],
+        )
+        .await?;
+
+        tx.execute(
+            &update_result_stmt,
+            &[&race_id, &session_type, &result.encode_to_vec()],
+        )
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Replays the stored amendments of a session on top of a new game classification.
+    ///
+    /// Amendments that no longer match the classification are skipped.
+    ///
+    /// # Returns
+    ///
+    /// The effective classification.
+    pub async fn replay_amendments(
+        tx: &Transaction<'_>,
+        race_id: i32,
+        session_type: i16,
+        result: &SessionResult,
+    ) -> AppResult<SessionResult> {
+        let result_amendments_stmt_fut = tx.prepare_cached(
+            r#"
+                SELECT * FROM result_amendments
+                WHERE race_id = $1 AND session_type = $2
+                ORDER BY id
+            "#,
+        );
+
+        let update_result_stmt_fut = tx.prepare_cached(
+            r#"
+                UPDATE results
+                SET amended_data = $3
+                WHERE race_id = $1 AND session_type = $2
+            "#,
+        );
+
+        let (result_amendments_stmt, update_result_stmt) =
+            tokio::try_join!(result_amendments_stmt_fut, update_result_stmt_fut)?;
+
+        let rows = tx
+            .query(&result_amendments_stmt, &[&race_id, &session_type])
+            .await?;
+
+        let mut effective = result.clone();
+
+        if rows.is_empty() {
+            return Ok(effective);
+        }
+
+        for row in rows {
+            let amendment = ResultAmendment::from_row(&row);
+
+            let applied = AmendmentKind::try_from(&amendment)
+                .map_err(Into::into)
+                .and_then(|kind| kind.apply(&mut effective));
+
+            if let Err(e) = applied {
+                warn!("Skipping result amendment {}: {}", amendment.id, e);
+            }
+        }
+
+        tx.execute(
+            &update_result_stmt,
+            &[&race_id, &session_type, &effective.encode_to_vec()],
+        )
+        .await?;
+
+        Ok(effective)
+    }
+
+    /// Invalidates the standings and the career stats affected by a session result.
+    pub fn invalidate_caches(db: &Database, championship_id: i32, result: &SessionResult) {
+        db.cache.championship.delete_standings(&championship_id);
+
+        for steam_name in result.classification.keys() {
+            db.cache.driver.delete_stats(steam_name);
+        }
+    }
+
+    /// Internal method to amend a session result.
+    #[inline]
+    async fn _amend(
+        &self,
+        id: i32,
+        user_id: i32,
+        race_id: i32,
+        session_type: i16,
+        form: &ResultAmendmentForm,
+    ) -> AppResult<()> {
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
+
+        let result =
+            Self::apply_amendment(&tx, id, race_id, session_type, form, None, user_id).await?;
+
+        tx.commit().await?;
+
+        Self::invalidate_caches(self.db, id, &result);
+
+        Ok(())
+    }
+}
+
+impl ResultServiceOperations for ResultService {
+    async fn amend(
+        &self,
+        id: i32,
+        user_id: i32,
+        race_id: i32,
+        session_type: i16,
+        form: &ResultAmendmentForm,
+    ) -> AppResult<()> {
+        if !self.championship_repo.is_admin(id, user_id).await? {
+            Err(ChampionshipError::NotAdmin)?
+        }
+
+        self._amend(id, user_id, race_id, session_type, form).await
+    }
+}
@@ -1,51 +1,121 @@
 use crate::{
-    config::Database,
-    error::{AppResult, TokenError},
-    structs::{TokenPayload, TokenPurpose},
+    cache::EntityCache,
+    config::{constants::JWT_KEYS_RELOAD_INTERVAL, Database},
+    entity::User,
+    error::{AppResult, CommonError, OAuthError, TokenError, UserError},
+    repositories::{SessionRepository, UserRepository},
+    structs::{AuthTokens, ClientDevice, TokenPayload, TokenPurpose},
+    utils::JwtKeys,
 };
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, TokenData, Validation};
-use std::fs;
+use ahash::AHashMap;
+use chrono::{DateTime, Utc};
+use jsonwebtoken::{errors::ErrorKind, jwk::JwkSet, Algorithm, TokenData, Validation};
+use ring::{
+    digest::{digest, SHA256},
+    rand::{SecureRandom, SystemRandom},
+};
+use std::fmt::Write;
+use tracing::{error, warn};
 
 // TODO: Update this implementation
 
 /// Manages token lifecycle for authentication and authorization.
-#[derive(Clone)]
 pub struct TokenService {
-    header: Header,
-    validation: Validation,
-    encoding_key: EncodingKey,
-    decoding_key: DecodingKey,
+    keys: JwtKeys,
+    validations: AHashMap<TokenPurpose, Validation>,
+    rng: SystemRandom,
     db: &'static Database,
+    user_repo: &'static UserRepository,
+    session_repo: &'static SessionRepository,
 }
 
 impl TokenService {
     /// Creates a new `TokenService` instance.
-    pub fn new(db: &'static Database) -> Self {
-        Self {
+    ///
+    /// Fails if the JWT keys can't be loaded.
+    pub fn new(
+        db: &'static Database,
+        user_repo: &'static UserRepository,
+        session_repo: &'static SessionRepository,
+    ) -> AppResult<Self> {
+        Ok(Self {
             db,
-            header: Header::new(jsonwebtoken::Algorithm::RS256),
-            encoding_key: EncodingKey::from_rsa_pem(
-                &fs::read("certs/jsonwebtoken.key").expect("Unable to read key"),
-            )
-            .unwrap(),
-            validation: Validation::new(jsonwebtoken::Algorithm::RS256),
-            decoding_key: DecodingKey::from_rsa_pem(
-                &fs::read("certs/jsonwebtoken.crt").expect("Unable to read key"),
-            )
-            .unwrap(),
+            user_repo,
+            session_repo,
+            keys: JwtKeys::load()?,
+            rng: SystemRandom::new(),
+            validations: TokenPurpose::ALL
+                .into_iter()
+                .map(|purpose| {
+                    let mut validation = Validation::new(Algorithm::RS256);
+                    validation.set_audience(&[purpose.audience()]);
+                    validation.set_required_spec_claims(&["exp", "aud"]);
+                    (purpose, validation)
+                })
+                .collect(),
+        })
+    }
+
+    /// Public keys used to verify tokens, for the JWKS endpoint.
+    #[inline]
+    pub fn jwks(&self) -> JwkSet {
+        self.keys.jwks()
+    }
+
+    /// Reloads the JWT keys from disk without a restart.
+    #[inline]
+    pub fn reload_keys(&self) -> AppResult<()> {
+        self.keys.reload()
+    }
+
+    /// Periodically reloads the JWT keys so rotated keys are picked up.
+    pub async fn run_keys_reload_job(&self) {
+        let mut interval = tokio::time::interval(JWT_KEYS_RELOAD_INTERVAL);
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.reload_keys() {
+                error!("Failed to reload JWT keys: {}", e);
+            }
         }
     }
 
+    /// Validates a token of the given purpose and returns its subject ID.
     #[inline]
-    pub fn subject_id(&self, token: &str) -> AppResult<i32> {
-        let token_data = self.validate(token)?;
+    pub fn subject_id(&self, token: &str, purpose: TokenPurpose) -> AppResult<i32> {
+        let token_data = self.validate(token, purpose)?;
         Ok(token_data.claims.subject_id)
     }
 
-    /// Validates a token and returns the associated claims.
-    pub fn validate(&self, token: &str) -> AppResult<TokenData<TokenPayload>> {
-        decode::<TokenPayload>(token, &self.decoding_key, &self.validation)
-            .map_err(|_| TokenError::InvalidToken.into())
+    /// Validates a token of the given purpose and returns the associated claims.
+    ///
+    /// Tokens issued for any other purpose are rejected, both by audience and by claim.
+    pub fn validate(
+        &self,
+        token: &str,
+        purpose: TokenPurpose,
+    ) -> AppResult<TokenData<TokenPayload>> {
+        let token_data = self
+            .keys
+            .decode::<TokenPayload>(token, &self.validations[&purpose])
+            .map_err(|e| match e.kind() {
+                ErrorKind::InvalidAudience => TokenError::InvalidTokenPurpose,
+                _ => TokenError::InvalidToken,
+            })?;
+
+        if token_data.claims.purpose != purpose {
+            Err(TokenError::InvalidTokenPurpose)?
+        }
+
+        Ok(token_data)
+    }
+
+    /// Validates an invitation token and returns the invitation ID.
+    #[inline]
+    pub fn invitation_id(&self, token: &str) -> AppResult<i32> {
+        self.subject_id(token, TokenPurpose::ChampionshipInvitation)
     }
 
     /// Saves a reset password token to the cache.
@@ -65,57 +135,427 @@ impl TokenService {
             .set_token(token, TokenPurpose::EmailVerification);
     }
 
+    /// Saves a two-factor login token to the cache, so it can only complete one login.
+    #[inline]
+    pub fn save_two_factor_token(&self, token: String) {
+        self.db
+            .cache
+            .token
+            .set_token(token, TokenPurpose::TwoFactorPending);
+    }
+
+    /// Generates a one-time code the frontend exchanges for the tokens of an
+    /// external login, so no token travels in the redirect URL.
+    pub fn generate_exchange_code(&self, user_id: i32) -> AppResult<String> {
+        let mut bytes = [0u8; 32];
+
+        self.rng
+            .fill(&mut bytes)
+            .map_err(|_| CommonError::InternalServerError)?;
+
+        let code = Self::to_hex(&bytes);
+        self.db.cache.token.set_exchange_code(code.clone(), user_id);
+
+        Ok(code)
+    }
+
+    /// Redeems an exchange code, returning the user it was issued to.
+    #[inline]
+    pub fn redeem_exchange_code(&self, code: &str) -> AppResult<i32> {
+        let Some(user_id) = self.db.cache.token.take_exchange_code(code) else {
+            Err(OAuthError::InvalidExchangeCode)?
+        };
+
+        Ok(user_id)
+    }
+
     /// Generates a new token with specified subject and purpose.
+    #[inline]
     pub fn generate_token(&self, subject_id: i32, purpose: TokenPurpose) -> AppResult<String> {
+        self.encode_token(subject_id, purpose, None)
+    }
+
+    /// Generates an access token bound to the current token version of the user.
+    #[inline]
+    pub fn generate_access_token(&self, user: &User) -> AppResult<String> {
+        self.encode_token(
+            user.id,
+            TokenPurpose::Authentication,
+            Some(user.token_version),
+        )
+    }
+
+    fn encode_token(
+        &self,
+        subject_id: i32,
+        purpose: TokenPurpose,
+        ver: Option<i32>,
+    ) -> AppResult<String> {
+        let jti = match purpose {
+            TokenPurpose::RefreshAuthentication => Some(self.token_id()?),
+            _ => None,
+        };
+
         let token_claim = TokenPayload {
             subject_id,
             exp: purpose.expiration_timestamp(),
+            aud: purpose.audience().to_owned(),
             purpose,
+            jti,
+            ver,
         };
-        encode(&self.header, &token_claim, &self.encoding_key)
+        self.keys
+            .encode(&token_claim)
             .map_err(|_| TokenError::TokenCreationError.into())
     }
 
-    /// Removes a refresh token from the cache.
-    pub fn remove_refresh_token(&self, user_id: i32, fingerprint: String) {
+    /// Revokes every access token issued to a user by bumping its token version.
+    pub async fn revoke_access_tokens(&self, user_id: i32) -> AppResult<()> {
+        {
+            let conn = self.db.pg.get().await?;
+
+            let bump_version_stmt = conn
+                .prepare_cached(
+                    r#"
+                        UPDATE users
+                        SET token_version = token_version + 1
+                        WHERE id = $1
+                    "#,
+                )
+                .await?;
+
+            conn.execute(&bump_version_stmt, &[&user_id]).await?;
+        }
+
+        self.db.cache.user.delete(user_id);
+        Ok(())
+    }
+
+    /// Removes every session of a user, on all devices.
+    pub async fn revoke_sessions(&self, user_id: i32) -> AppResult<()> {
+        let rows = {
+            let conn = self.db.pg.get().await?;
+
+            let revoke_sessions_stmt = conn
+                .prepare_cached(
+                    r#"
+                        DELETE FROM user_sessions
+                        WHERE user_id = $1
+                        RETURNING fingerprint
+                    "#,
+                )
+                .await?;
+
+            conn.query(&revoke_sessions_stmt, &[&user_id]).await?
+        };
+
+        for row in rows {
+            self.db
+                .cache
+                .token
+                .remove_refresh_token(user_id, row.get(0));
+        }
+
+        Ok(())
+    }
+
+    /// Removes the session of a user on a device.
+    pub async fn remove_refresh_token(&self, user_id: i32, fingerprint: String) -> AppResult<()> {
+        {
+            let conn = self.db.pg.get().await?;
+
+            let delete_session_stmt = conn
+                .prepare_cached(
+                    r#"
+                        DELETE FROM user_sessions
+                        WHERE user_id = $1 AND fingerprint = $2
+                    "#,
+                )
+                .await?;
+
+            conn.execute(&delete_session_stmt, &[&user_id, &fingerprint])
+                .await?;
+        }
+
         self.db
             .cache
             .token
             .remove_refresh_token(user_id, fingerprint);
+
+        Ok(())
     }
 
-    /// Generates a new refresh token for a user.
-    pub fn generate_refresh_token(&self, user_id: i32, fingerprint: String) -> AppResult<String> {
-        let token = self.generate_token(user_id, TokenPurpose::RefreshAuthentication)?;
+    /// Revokes a session of a user by its ID.
+    pub async fn revoke_session(&self, user_id: i32, session_id: i32) -> AppResult<()> {
+        let row = {
+            let conn = self.db.pg.get().await?;
+
+            let revoke_session_stmt = conn
+                .prepare_cached(
+                    r#"
+                        DELETE FROM user_sessions
+                        WHERE id = $1 AND user_id = $2
+                        RETURNING fingerprint
+                    "#,
+                )
+                .await?;
+
+            conn.query_opt(&revoke_session_stmt, &[&session_id, &user_id])
+                .await?
+        };
+
+        let Some(row) = row else {
+            Err(TokenError::SessionNotFound)?
+        };
+
         self.db
             .cache
             .token
-            .set_refresh_token(user_id, fingerprint, token.clone());
+            .remove_refresh_token(user_id, row.get(0));
+
+        Ok(())
+    }
+
+    /// Issues the access and refresh token pair of a login on a device.
+    pub async fn generate_auth_tokens(
+        &self,
+        user: &User,
+        fingerprint: String,
+        device: &ClientDevice,
+    ) -> AppResult<AuthTokens> {
+        let access_token = self.generate_access_token(user)?;
+        let refresh_token = self
+            .generate_refresh_token(user.id, fingerprint, device)
+            .await?;
+
+        Ok(AuthTokens {
+            access_token,
+            refresh_token,
+        })
+    }
+
+    /// Generates a new refresh token for a user and stores the session of the device.
+    pub async fn generate_refresh_token(
+        &self,
+        user_id: i32,
+        fingerprint: String,
+        device: &ClientDevice,
+    ) -> AppResult<String> {
+        let token = self.generate_token(user_id, TokenPurpose::RefreshAuthentication)?;
+        let token_hash = Self::hash_token(&token);
+        let expires_at = TokenPurpose::RefreshAuthentication.expiration_date();
+
+        {
+            let mut conn = self.db.pg.get().await?;
+            let tx = conn.transaction().await?;
+
+            let (delete_expired_stmt, upsert_session_stmt, reset_family_stmt) = tokio::try_join!(
+                tx.prepare_cached(
+                    r#"
+                        DELETE FROM user_sessions
+                        WHERE user_id = $1 AND expires_at <= NOW()
+                    "#,
+                ),
+                tx.prepare_cached(
+                    r#"
+                        INSERT INTO user_sessions (user_id, fingerprint, token_hash, device, ip, expires_at)
+                        VALUES ($1, $2, $3, $4, $5, $6)
+                        ON CONFLICT (user_id, fingerprint) DO UPDATE SET
+                            token_hash = EXCLUDED.token_hash,
+                            device = EXCLUDED.device,
+                            ip = EXCLUDED.ip,
+                            created_at = NOW(),
+                            last_used_at = NOW(),
+                            expires_at = EXCLUDED.expires_at
+                        RETURNING id
+                    "#,
+                ),
+                tx.prepare_cached(
+                    r#"
+                        DELETE FROM user_session_rotated_tokens
+                        WHERE session_id = $1
+                    "#,
+                )
+            )?;
+
+            tx.execute(&delete_expired_stmt, &[&user_id]).await?;
+
+            let session_id: i32 = tx
+                .query_one(
+                    &upsert_session_stmt,
+                    &[
+                        &user_id,
+                        &fingerprint,
+                        &token_hash,
+                        &device.device,
+                        &device.ip,
+                        &expires_at,
+                    ],
+                )
+                .await?
+                .get(0);
+
+            // A new login starts a new token family on the device
+            tx.execute(&reset_family_stmt, &[&session_id]).await?;
+            tx.commit().await?;
+        }
+
+        if let Ok(ttl) = (expires_at - Utc::now()).to_std() {
+            self.db
+                .cache
+                .token
+                .set_refresh_token(user_id, fingerprint, token_hash, ttl);
+        }
+
         Ok(token)
     }
 
-    /// Refreshes an access token using a refresh token.
-    pub fn refresh_access_token(
+    /// Rotates a refresh token, returning a new access and refresh token pair.
+    ///
+    /// The presented refresh token is invalidated. Presenting an already rotated
+    /// token again revokes the whole token family of the device.
+    pub async fn rotate_refresh_token(
         &self,
         refresh_token: &str,
         fingerprint: String,
-    ) -> AppResult<String> {
-        let id = {
-            let token = self.validate(refresh_token)?;
-            if token.claims.purpose != TokenPurpose::RefreshAuthentication {
-                Err(TokenError::InvalidTokenPurpose)?
-            }
-            token.claims.subject_id
+    ) -> AppResult<AuthTokens> {
+        let id = self.subject_id(refresh_token, TokenPurpose::RefreshAuthentication)?;
+
+        let Some(user) = self.user_repo.find(id).await? else {
+            Err(UserError::NotFound)?
         };
 
-        let Some(db_token) = self.db.cache.token.get_refresh_token(id, fingerprint) else {
+        if !user.active {
+            Err(UserError::NotVerified)?
+        }
+
+        if self
+            .session_repo
+            .token_hash(id, &fingerprint)
+            .await?
+            .is_none()
+        {
             Err(TokenError::MissingToken)?
+        }
+
+        let old_hash = Self::hash_token(refresh_token);
+        let new_token = self.generate_token(id, TokenPurpose::RefreshAuthentication)?;
+        let new_hash = Self::hash_token(&new_token);
+
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
+
+        let (rotate_stmt, record_rotated_stmt) = tokio::try_join!(
+            tx.prepare_cached(
+                r#"
+                    UPDATE user_sessions
+                    SET token_hash = $4, last_used_at = NOW()
+                    WHERE user_id = $1 AND fingerprint = $2 AND token_hash = $3
+                        AND expires_at > NOW()
+                    RETURNING id, expires_at
+                "#,
+            ),
+            tx.prepare_cached(
+                r#"
+                    INSERT INTO user_session_rotated_tokens (token_hash, session_id)
+                    VALUES ($1, $2)
+                "#,
+            )
+        )?;
+
+        let rotated = tx
+            .query_opt(&rotate_stmt, &[&id, &fingerprint, &old_hash, &new_hash])
+            .await?;
+
+        let Some(row) = rotated else {
+            drop(tx);
+            return self.revoke_reused_family(id, fingerprint, &old_hash).await;
         };
 
-        if db_token != refresh_token {
+        let session_id: i32 = row.get(0);
+        let expires_at: DateTime<Utc> = row.get(1);
+
+        tx.execute(&record_rotated_stmt, &[&old_hash, &session_id])
+            .await?;
+        tx.commit().await?;
+
+        if let Ok(ttl) = (expires_at - Utc::now()).to_std() {
+            self.db
+                .cache
+                .token
+                .set_refresh_token(id, fingerprint, new_hash, ttl);
+        }
+
+        Ok(AuthTokens {
+            access_token: self.generate_access_token(&user)?,
+            refresh_token: new_token,
+        })
+    }
+
+    /// Revokes the token family of a device when a rotated token is presented again.
+    async fn revoke_reused_family(
+        &self,
+        user_id: i32,
+        fingerprint: String,
+        token_hash: &str,
+    ) -> AppResult<AuthTokens> {
+        let revoked = {
+            let conn = self.db.pg.get().await?;
+
+            let revoke_family_stmt = conn
+                .prepare_cached(
+                    r#"
+                        DELETE FROM user_sessions s
+                        USING user_session_rotated_tokens r
+                        WHERE r.session_id = s.id AND r.token_hash = $3
+                            AND s.user_id = $1 AND s.fingerprint = $2
+                    "#,
+                )
+                .await?;
+
+            conn.execute(&revoke_family_stmt, &[&user_id, &fingerprint, &token_hash])
+                .await?
+        };
+
+        if revoked == 0 {
             Err(TokenError::InvalidToken)?
         }
 
-        self.generate_token(id, TokenPurpose::Authentication)
+        warn!(
+            "Refresh token reuse detected for user {}, revoked session of device {}",
+            user_id, fingerprint
+        );
+
+        self.db
+            .cache
+            .token
+            .remove_refresh_token(user_id, fingerprint);
+
+        Err(TokenError::TokenReused)?
+    }
+
+    /// Generates a random token ID.
+    fn token_id(&self) -> AppResult<String> {
+        let mut bytes = [0u8; 16];
+
+        self.rng
+            .fill(&mut bytes)
+            .map_err(|_| CommonError::InternalServerError)?;
+
+        Ok(Self::to_hex(&bytes))
+    }
+
+    /// Hashes a refresh token, only the hash is ever stored.
+    fn hash_token(token: &str) -> String {
+        Self::to_hex(digest(&SHA256, token.as_bytes()).as_ref())
+    }
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes
+            .iter()
+            .fold(String::with_capacity(bytes.len() * 2), |mut hex, byte| {
+                let _ = write!(hex, "{byte:02x}");
+                hex
+            })
     }
 }
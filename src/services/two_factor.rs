@@ -0,0 +1,444 @@
+use chrono::Utc;
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::{
+    config::{
+        constants::{RECOVERY_CODES, TOTP_DIGITS, TOTP_SECRET_LEN},
+        Database,
+    },
+    entity::{Provider, User, UserTwoFactor},
+    error::{AppResult, CommonError, TokenError, TwoFactorError},
+    repositories::TwoFactorRepository,
+    structs::{TokenPurpose, TwoFactorSetup},
+    utils::{PasswordHasher, Totp},
+};
+
+use super::TokenService;
+
+const RECOVERY_CODE_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Defines the operations for managing the two-factor authentication of a user.
+pub trait TwoFactorServiceOperations {
+    /// Starts a TOTP enrolment, replacing any pending one.
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - The user enrolling.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user is not a local account or already has
+    /// two-factor authentication enabled.
+    async fn setup(&self, user: &User) -> AppResult<TwoFactorSetup>;
+
+    /// Confirms a pending enrolment with a code from the authenticator app.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The ID of the user.
+    /// * `code` - The current TOTP code.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's no pending enrolment or the code is invalid.
+    async fn enable(&self, user_id: i32, code: &str) -> AppResult<Vec<String>>;
+
+    /// Disables two-factor authentication, removing the secret and recovery codes.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The ID of the user.
+    /// * `code` - A TOTP code or an unused recovery code.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if two-factor authentication is not enabled or the code is invalid.
+    async fn disable(&self, user_id: i32, code: &str) -> AppResult<()>;
+
+    /// Completes a login with the challenge token and a second factor.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The two-factor token returned by the first login step.
+    /// * `code` - A TOTP code or an unused recovery code.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token is invalid or already used, or the code is invalid.
+    async fn login(&self, token: String, code: &str) -> AppResult<i32>;
+}
+
+/// Defines additional admin-level operations for two-factor authentication.
+pub trait TwoFactorAdminServiceOperations: TwoFactorServiceOperations {
+    /// Allows an admin to remove the two-factor authentication of a user that
+    /// lost both the authenticator and the recovery codes.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The ID of the user.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user has no two-factor authentication set up.
+    async fn admin_reset(&self, user_id: i32) -> AppResult<()>;
+}
+
+/// Implementation of the two-factor authentication service.
+pub struct TwoFactorService {
+    db: &'static Database,
+    two_factor_repo: &'static TwoFactorRepository,
+    token_svc: &'static TokenService,
+    password_hasher: PasswordHasher,
+    rng: SystemRandom,
+}
+
+impl TwoFactorService {
+    /// Creates a new TwoFactorService instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database connection.
+    /// * `two_factor_repo` - The two-factor repository.
+    /// * `token_svc` - The token service.
+    pub fn new(
+        db: &'static Database,
+        two_factor_repo: &'static TwoFactorRepository,
+        token_svc: &'static TokenService,
+    ) -> Self {
+        Self {
+            db,
+            two_factor_repo,
+            token_svc,
+            password_hasher: PasswordHasher::new(10),
+            rng: SystemRandom::new(),
+        }
+    }
+
+    /// Generates `N` random bytes.
+    #[inline]
+    fn random_bytes<const N: usize>(&self) -> AppResult<[u8; N]> {
+        let mut bytes = [0u8; N];
+
+        self.rng
+            .fill(&mut bytes)
+            .map_err(|_| CommonError::InternalServerError)?;
+
+        Ok(bytes)
+    }
+
+    /// Generates a recovery code in the `xxxxx-xxxxx` format.
+    #[inline]
+    fn recovery_code(&self) -> AppResult<String> {
+        let mut code = String::with_capacity(11);
+
+        for (i, byte) in self.random_bytes::<10>()?.into_iter().enumerate() {
+            if i == 5 {
+                code.push('-');
+            }
+
+            code.push(RECOVERY_CODE_ALPHABET[(byte & 31) as usize] as char);
+        }
+
+        Ok(code)
+    }
+
+    /// Checks if a code has the `xxxxx-xxxxx` format of the recovery codes.
+    #[inline]
+    fn is_recovery_code(code: &str) -> bool {
+        code.len() == 11
+            && code.bytes().enumerate().all(|(i, byte)| match i {
+                5 => byte == b'-',
+                _ => RECOVERY_CODE_ALPHABET.contains(&byte),
+            })
+    }
+
+    /// Checks a TOTP code and records its time step so it can't be used twice.
+    async fn verify_totp(&self, two_factor: &UserTwoFactor, code: &str) -> AppResult<bool> {
+        let Some(secret) = Totp::decode_secret(&two_factor.secret) else {
+            Err(CommonError::InternalServerError)?
+        };
+
+        let Some(step) = Totp::verify(
+            &secret,
+            code,
+            Utc::now().timestamp() as u64,
+            two_factor.last_step.map(|step| step as u64),
+        ) else {
+            return Ok(false);
+        };
+
+        let conn = self.db.pg.get().await?;
+
+        let claim_step_stmt = conn
+            .prepare_cached(
+                r#"
+                    UPDATE user_two_factor
+                    SET last_step = $2
+                    WHERE user_id = $1 AND (last_step IS NULL OR last_step < $2)
+                "#,
+            )
+            .await?;
+
+        let updated = conn
+            .execute(&claim_step_stmt, &[&two_factor.user_id, &(step as i64)])
+            .await?;
+
+        Ok(updated == 1)
+    }
+
+    /// Checks a recovery code against the unused ones and marks it as used.
+    async fn use_recovery_code(&self, user_id: i32, code: &str) -> AppResult<bool> {
+        let code = code.to_ascii_lowercase();
+
+        // Don't pay for the hashes on codes that can't have been issued
+        if !Self::is_recovery_code(&code) {
+            return Ok(false);
+        }
+
+        for recovery_code in self.two_factor_repo.unused_recovery_codes(user_id).await? {
+            if !self
+                .password_hasher
+                .verify_password(recovery_code.code_hash, code.clone())
+                .await?
+            {
+                continue;
+            }
+
+            let conn = self.db.pg.get().await?;
+
+            let use_code_stmt = conn
+                .prepare_cached(
+                    r#"
+                        UPDATE user_recovery_codes
+                        SET used_at = NOW()
+                        WHERE id = $1 AND used_at IS NULL
+                    "#,
+                )
+                .await?;
+
+            let updated = conn.execute(&use_code_stmt, &[&recovery_code.id]).await?;
+            return Ok(updated == 1);
+        }
+
+        Ok(false)
+    }
+
+    /// Internal method to verify a second factor of an enabled enrolment.
+    ///
+    /// Codes of `TOTP_DIGITS` length are checked as TOTP codes, anything else as
+    /// a recovery code. After `TWO_FACTOR_MAX_ATTEMPTS` wrong codes the user is
+    /// locked out until `TWO_FACTOR_LOCKOUT` passes.
+    #[inline]
+    async fn _verify(&self, user_id: i32, code: &str) -> AppResult<()> {
+        if self.db.cache.token.two_factor_locked(user_id) {
+            Err(TwoFactorError::TooManyAttempts)?
+        }
+
+        let Some(two_factor) = self
+            .two_factor_repo
+            .find(user_id)
+            .await?
+            .filter(UserTwoFactor::enabled)
+        else {
+            Err(TwoFactorError::NotEnabled)?
+        };
+
+        let valid = match code.len() == TOTP_DIGITS as usize {
+            true => self.verify_totp(&two_factor, code).await?,
+            false => self.use_recovery_code(user_id, code).await?,
+        };
+
+        if !valid {
+            self.db.cache.token.set_two_factor_failure(user_id);
+            Err(TwoFactorError::InvalidCode)?
+        }
+
+        self.db.cache.token.remove_two_factor_failures(user_id);
+        Ok(())
+    }
+
+    /// Internal method to start a TOTP enrolment.
+    #[inline]
+    async fn _setup(&self, user: &User) -> AppResult<TwoFactorSetup> {
+        let secret = Totp::encode_secret(&self.random_bytes::<TOTP_SECRET_LEN>()?);
+
+        let updated = {
+            let conn = self.db.pg.get().await?;
+
+            let setup_stmt = conn
+                .prepare_cached(
+                    r#"
+                        INSERT INTO user_two_factor (user_id, secret)
+                        VALUES ($1, $2)
+                        ON CONFLICT (user_id) DO UPDATE SET
+                            secret = EXCLUDED.secret,
+                            last_step = NULL,
+                            created_at = NOW()
+                        WHERE user_two_factor.enabled_at IS NULL
+                    "#,
+                )
+                .await?;
+
+            conn.execute(&setup_stmt, &[&user.id, &secret]).await?
+        };
+
+        if updated == 0 {
+            Err(TwoFactorError::AlreadyEnabled)?
+        }
+
+        Ok(TwoFactorSetup {
+            otpauth_uri: Totp::otpauth_uri(&secret, &user.email),
+            secret,
+        })
+    }
+
+    /// Internal method to enable two-factor authentication and issue recovery codes.
+    #[inline]
+    async fn _enable(&self, user_id: i32) -> AppResult<Vec<String>> {
+        let mut codes = Vec::with_capacity(RECOVERY_CODES);
+        let mut code_hashes = Vec::with_capacity(RECOVERY_CODES);
+
+        for _ in 0..RECOVERY_CODES {
+            let code = self.recovery_code()?;
+            code_hashes.push(self.password_hasher.hash_password(code.clone()).await?);
+            codes.push(code);
+        }
+
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
+
+        let (enable_stmt, delete_codes_stmt, insert_codes_stmt) = tokio::try_join!(
+            tx.prepare_cached(
+                r#"
+                    UPDATE user_two_factor
+                    SET enabled_at = NOW()
+                    WHERE user_id = $1 AND enabled_at IS NULL
+                "#,
+            ),
+            tx.prepare_cached(
+                r#"
+                    DELETE FROM user_recovery_codes
+                    WHERE user_id = $1
+                "#,
+            ),
+            tx.prepare_cached(
+                r#"
+                    INSERT INTO user_recovery_codes (user_id, code_hash)
+                    SELECT $1, UNNEST($2::VARCHAR[])
+                "#,
+            )
+        )?;
+
+        if tx.execute(&enable_stmt, &[&user_id]).await? == 0 {
+            Err(TwoFactorError::AlreadyEnabled)?
+        }
+
+        tx.execute(&delete_codes_stmt, &[&user_id]).await?;
+        tx.execute(&insert_codes_stmt, &[&user_id, &code_hashes])
+            .await?;
+        tx.commit().await?;
+
+        Ok(codes)
+    }
+
+    /// Internal method to remove the enrolment and recovery codes of a user.
+    ///
+    /// # Returns
+    /// Whether there was an enrolment to remove.
+    #[inline]
+    async fn _remove(&self, user_id: i32) -> AppResult<bool> {
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
+
+        let (delete_two_factor_stmt, delete_codes_stmt) = tokio::try_join!(
+            tx.prepare_cached(
+                r#"
+                    DELETE FROM user_two_factor
+                    WHERE user_id = $1
+                "#,
+            ),
+            tx.prepare_cached(
+                r#"
+                    DELETE FROM user_recovery_codes
+                    WHERE user_id = $1
+                "#,
+            )
+        )?;
+
+        let removed = tx.execute(&delete_two_factor_stmt, &[&user_id]).await?;
+        tx.execute(&delete_codes_stmt, &[&user_id]).await?;
+        tx.commit().await?;
+
+        Ok(removed == 1)
+    }
+}
+
+impl TwoFactorServiceOperations for TwoFactorService {
+    async fn setup(&self, user: &User) -> AppResult<TwoFactorSetup> {
+        if user.provider != Provider::Local {
+            Err(TwoFactorError::LocalOnly)?
+        }
+
+        self._setup(user).await
+    }
+
+    async fn enable(&self, user_id: i32, code: &str) -> AppResult<Vec<String>> {
+        let Some(two_factor) = self.two_factor_repo.find(user_id).await? else {
+            Err(TwoFactorError::NotSetUp)?
+        };
+
+        if two_factor.enabled() {
+            Err(TwoFactorError::AlreadyEnabled)?
+        }
+
+        if !self.verify_totp(&two_factor, code).await? {
+            Err(TwoFactorError::InvalidCode)?
+        }
+
+        self._enable(user_id).await
+    }
+
+    async fn disable(&self, user_id: i32, code: &str) -> AppResult<()> {
+        self._verify(user_id, code).await?;
+        self._remove(user_id).await?;
+        Ok(())
+    }
+
+    async fn login(&self, token: String, code: &str) -> AppResult<i32> {
+        if !self
+            .db
+            .cache
+            .token
+            .get_token(token.clone(), TokenPurpose::TwoFactorPending)
+        {
+            Err(TokenError::InvalidToken)?
+        }
+
+        let user_id = self
+            .token_svc
+            .subject_id(&token, TokenPurpose::TwoFactorPending)?;
+
+        let verified = self._verify(user_id, code).await;
+
+        // The pending token is spent once verified or out of attempts
+        if verified.is_ok() || self.db.cache.token.two_factor_locked(user_id) {
+            self.db
+                .cache
+                .token
+                .remove_token(token, TokenPurpose::TwoFactorPending);
+        }
+
+        verified?;
+        Ok(user_id)
+    }
+}
+
+impl TwoFactorAdminServiceOperations for TwoFactorService {
+    async fn admin_reset(&self, user_id: i32) -> AppResult<()> {
+        if !self._remove(user_id).await? {
+            Err(TwoFactorError::NotEnabled)?
+        }
+
+        Ok(())
+    }
+}
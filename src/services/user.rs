@@ -14,6 +14,16 @@ use crate::{
 
 use super::TokenService;
 
+/// Keeps `users.discord_id` in line with the linked Discord identity
+const SYNC_DISCORD_ID_QUERY: &str = r#"
+    UPDATE users
+    SET discord_id = (
+        SELECT provider_user_id::BIGINT FROM user_identities
+        WHERE user_id = $1 AND provider = 'Discord'
+    )
+    WHERE id = $1
+"#;
+
 /// Defines the core operations for managing users.
 pub trait UserServiceOperations {
     /// Creates a new user.
@@ -72,6 +82,38 @@ pub trait UserServiceOperations {
     ///
     /// Returns an error if the token is invalid or if there's a database error.
     async fn activate(&self, token: String) -> AppResult<i32>;
+
+    /// Links an external identity to a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the user.
+    /// * `provider` - The provider of the identity.
+    /// * `provider_user_id` - The ID of the account on the provider.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user already has an identity of the provider, or
+    /// the identity belongs to another user.
+    async fn link_identity(
+        &self,
+        id: i32,
+        provider: Provider,
+        provider_user_id: &str,
+    ) -> AppResult<()>;
+
+    /// Unlinks an external identity from a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - The user unlinking the identity.
+    /// * `provider` - The provider of the identity.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the provider is not linked or it's the last way the
+    /// user can sign in.
+    async fn unlink_identity(&self, user: SharedUser, provider: Provider) -> AppResult<()>;
 }
 
 /// Defines additional admin-level operations for managing users.
@@ -162,15 +204,32 @@ impl UserService {
         let provider = registration_data.provider.unwrap_or(Provider::Local);
         let active = provider != Provider::Local;
 
-        let conn = self.db.pg.get().await?;
-        let create_user_stmt = conn.prepare_cached(
-            r#"
-                INSERT INTO users (id, email, username, password, avatar, provider, discord_id, active)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-            "#,
-        ).await?;
+        // Only the account returned by the provider is trusted, never an ID sent by the client
+        let discord_id = registration_data
+            .provider_user_id
+            .as_deref()
+            .filter(|_| provider == Provider::Discord)
+            .and_then(|provider_user_id| provider_user_id.parse::<i64>().ok());
+
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
 
-        conn.execute_raw(
+        let (create_user_stmt, create_identity_stmt) = tokio::try_join!(
+            tx.prepare_cached(
+                r#"
+                    INSERT INTO users (id, email, username, password, avatar, provider, discord_id, active)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                "#,
+            ),
+            tx.prepare_cached(
+                r#"
+                    INSERT INTO user_identities (user_id, provider, provider_user_id)
+                    VALUES ($1, $2, $3)
+                "#,
+            )
+        )?;
+
+        tx.execute_raw(
             &create_user_stmt,
             slice_iter(&[
                 &id,
@@ -179,12 +238,18 @@ impl UserService {
                 &hashed_password,
                 &avatar,
                 &provider,
-                &registration_data.discord_id,
+                &discord_id,
                 &active,
             ]),
         )
         .await?;
 
+        if let Some(provider_user_id) = &registration_data.provider_user_id {
+            tx.execute(&create_identity_stmt, &[&id, &provider, provider_user_id])
+                .await?;
+        }
+
+        tx.commit().await?;
         Ok(id)
     }
 
@@ -283,7 +348,8 @@ impl UserService {
             .prepare_cached(
                 r#"
                     UPDATE users
-                    SET password = $1, updated_at = CURRENT_TIMESTAMP
+                    SET password = $1, updated_at = CURRENT_TIMESTAMP,
+                        token_version = token_version + 1
                     WHERE id = $2
                 "#,
             )
@@ -294,6 +360,7 @@ impl UserService {
             .await?;
 
         self.db.cache.user.delete(id);
+        self.token_svc.revoke_sessions(id).await?;
 
         info!("User password reseated with success: {}", id);
         Ok(())
@@ -322,6 +389,64 @@ impl UserService {
         Ok(())
     }
 
+    /// Internal method to link an external identity.
+    #[inline]
+    async fn _link_identity(
+        &self,
+        id: i32,
+        provider: Provider,
+        provider_user_id: &str,
+    ) -> AppResult<()> {
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
+
+        let (link_identity_stmt, sync_discord_stmt) = tokio::try_join!(
+            tx.prepare_cached(
+                r#"
+                    INSERT INTO user_identities (user_id, provider, provider_user_id)
+                    VALUES ($1, $2, $3)
+                "#,
+            ),
+            tx.prepare_cached(SYNC_DISCORD_ID_QUERY)
+        )?;
+
+        tx.execute(&link_identity_stmt, &[&id, &provider, &provider_user_id])
+            .await?;
+        tx.execute(&sync_discord_stmt, &[&id]).await?;
+        tx.commit().await?;
+
+        self.db.cache.user.delete(id);
+
+        info!("User {} linked {:?} account", id, provider);
+        Ok(())
+    }
+
+    /// Internal method to unlink an external identity.
+    #[inline]
+    async fn _unlink_identity(&self, id: i32, provider: Provider) -> AppResult<()> {
+        let mut conn = self.db.pg.get().await?;
+        let tx = conn.transaction().await?;
+
+        let (unlink_identity_stmt, sync_discord_stmt) = tokio::try_join!(
+            tx.prepare_cached(
+                r#"
+                    DELETE FROM user_identities
+                    WHERE user_id = $1 AND provider = $2
+                "#,
+            ),
+            tx.prepare_cached(SYNC_DISCORD_ID_QUERY)
+        )?;
+
+        tx.execute(&unlink_identity_stmt, &[&id, &provider]).await?;
+        tx.execute(&sync_discord_stmt, &[&id]).await?;
+        tx.commit().await?;
+
+        self.db.cache.user.delete(id);
+
+        info!("User {} unlinked {:?} account", id, provider);
+        Ok(())
+    }
+
     /// Internal method to deactivate a user's account.
     #[inline]
     async fn _deactivate(&self, id: i32) -> AppResult<()> {
@@ -330,7 +455,7 @@ impl UserService {
             .prepare_cached(
                 r#"
                     UPDATE users
-                    SET active = false
+                    SET active = false, token_version = token_version + 1
                     WHERE id = $1
                 "#,
             )
@@ -338,6 +463,7 @@ impl UserService {
 
         conn.execute_raw(&deactivate_user_stmt, &[&id]).await?;
         self.db.cache.user.delete(id);
+        self.token_svc.revoke_sessions(id).await?;
 
         info!("User activated with success: {}", id);
         Ok(())
@@ -373,7 +499,9 @@ impl UserServiceOperations for UserService {
             return Err(TokenError::InvalidToken)?;
         }
 
-        let user_id = self.token_svc.subject_id(&token)?;
+        let user_id = self
+            .token_svc
+            .subject_id(&token, TokenPurpose::PasswordReset)?;
 
         self._reset_password(user_id, password).await?;
 
@@ -395,7 +523,9 @@ impl UserServiceOperations for UserService {
             return Err(TokenError::InvalidToken)?;
         }
 
-        let user_id = self.token_svc.subject_id(&token)?;
+        let user_id = self
+            .token_svc
+            .subject_id(&token, TokenPurpose::EmailVerification)?;
 
         self._activate(user_id).await?;
 
@@ -406,6 +536,53 @@ impl UserServiceOperations for UserService {
 
         Ok(user_id)
     }
+
+    async fn link_identity(
+        &self,
+        id: i32,
+        provider: Provider,
+        provider_user_id: &str,
+    ) -> AppResult<()> {
+        if let Some(owner) = self
+            .user_repo
+            .find_by_identity(provider, provider_user_id)
+            .await?
+        {
+            match owner.id == id {
+                true => Err(UserError::IdentityAlreadyLinked)?,
+                false => Err(UserError::IdentityInUse)?,
+            }
+        }
+
+        let identities = self.user_repo.identities(id).await?;
+
+        if identities
+            .iter()
+            .any(|identity| identity.provider == provider)
+        {
+            Err(UserError::IdentityAlreadyLinked)?
+        }
+
+        self._link_identity(id, provider, provider_user_id).await
+    }
+
+    async fn unlink_identity(&self, user: SharedUser, provider: Provider) -> AppResult<()> {
+        let identities = self.user_repo.identities(user.id).await?;
+
+        if !identities
+            .iter()
+            .any(|identity| identity.provider == provider)
+        {
+            Err(UserError::IdentityNotLinked)?
+        }
+
+        // Another identity or a password has to remain to sign in with
+        if identities.len() == 1 && user.password.is_none() {
+            Err(UserError::LastLoginMethod)?
+        }
+
+        self._unlink_identity(user.id, provider).await
+    }
 }
 
 impl UserAdminServiceOperations for UserService {
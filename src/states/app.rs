@@ -2,12 +2,14 @@ use crate::{
     config::Database,
     error::AppResult,
     repositories::{
-        ChampionshipRepository, DiscordRepository, DriverRepository, ServerRepository,
-        UserRepository,
+        ApiKeyRepository, ChampionshipRepository, DriverRepository, IncidentRepository,
+        OAuthRepository, ResultRepository, ServerRepository, SessionRepository,
+        TwoFactorRepository, UserRepository,
     },
     services::{
-        ChampionshipService, DriverService, EmailService, F1ServiceHandler, FirewallService,
-        TokenService, UserService,
+        ApiKeyService, ChampionshipService, DriverService, EmailService, F1ServiceHandler,
+        FirewallService, IncidentService, OAuthService, ResultService, TokenService,
+        TwoFactorService, UserService,
     },
 };
 
@@ -16,15 +18,22 @@ pub struct AppState {
     pub user_svc: &'static UserService,
     pub user_repo: &'static UserRepository,
     pub token_svc: &'static TokenService,
+    pub session_repo: &'static SessionRepository,
     pub championship_svc: &'static ChampionshipService,
     pub championship_repo: &'static ChampionshipRepository,
-    #[allow(unused)]
     pub driver_repo: &'static DriverRepository,
-    #[allow(unused)]
     pub driver_svc: &'static DriverService,
+    pub incident_repo: &'static IncidentRepository,
+    pub incident_svc: &'static IncidentService,
+    pub result_repo: &'static ResultRepository,
+    pub result_svc: &'static ResultService,
+    pub api_key_repo: &'static ApiKeyRepository,
+    pub api_key_svc: &'static ApiKeyService,
+    pub two_factor_repo: &'static TwoFactorRepository,
+    pub two_factor_svc: &'static TwoFactorService,
     pub email_svc: EmailService,
     pub f1_svc: F1ServiceHandler,
-    pub discord_repo: &'static DiscordRepository,
+    pub oauth_svc: &'static OAuthService,
     pub server_repo: ServerRepository,
 }
 
@@ -34,6 +43,7 @@ pub struct F1State {
     pub driver_repo: &'static DriverRepository,
     pub championship_repo: &'static ChampionshipRepository,
     pub championship_svc: &'static ChampionshipService,
+    pub incident_svc: &'static IncidentService,
 }
 
 impl F1State {
@@ -42,6 +52,7 @@ impl F1State {
         driver_repo: &'static DriverRepository,
         championship_repo: &'static ChampionshipRepository,
         championship_svc: &'static ChampionshipService,
+        incident_svc: &'static IncidentService,
     ) -> Self {
         let firewall = Box::leak(Box::new(FirewallService::new()));
 
@@ -51,6 +62,7 @@ impl F1State {
             driver_repo,
             championship_repo,
             championship_svc,
+            incident_svc,
         }
     }
 }
@@ -59,17 +71,47 @@ impl AppState {
     pub async fn new(db: &'static Database) -> AppResult<Self> {
         // Repositories
         let user_repo = Box::leak(Box::new(UserRepository::new(db)));
-        let discord_repo = Box::leak(Box::new(DiscordRepository::new()));
+        let oauth_repo = Box::leak(Box::new(OAuthRepository::new()));
         let championship_repo = Box::leak(Box::new(ChampionshipRepository::new(db)));
         let driver_repo = Box::leak(Box::new(DriverRepository::new(db)));
+        let incident_repo = Box::leak(Box::new(IncidentRepository::new(db)));
+        let result_repo = Box::leak(Box::new(ResultRepository::new(db)));
+        let session_repo = Box::leak(Box::new(SessionRepository::new(db)));
+        let api_key_repo = Box::leak(Box::new(ApiKeyRepository::new(db)));
+        let two_factor_repo = Box::leak(Box::new(TwoFactorRepository::new(db)));
 
         // Services
-        let token_svc = Box::leak(Box::from(TokenService::new(db)));
-        let driver_svc = Box::leak(Box::new(DriverService::new(db, driver_repo).await));
+        let token_svc = Box::leak(Box::from(TokenService::new(db, user_repo, session_repo)?));
+        let driver_svc = Box::leak(Box::new(
+            DriverService::new(db, driver_repo, championship_repo).await,
+        ));
         let user_svc = Box::leak(Box::from(UserService::new(db, user_repo, token_svc).await));
         let championship_svc = Box::leak(Box::from(
             ChampionshipService::new(db, user_repo, championship_repo).await?,
         ));
+        let incident_svc = Box::leak(Box::new(IncidentService::new(
+            db,
+            championship_repo,
+            incident_repo,
+        )));
+        let result_svc = Box::leak(Box::new(ResultService::new(db, championship_repo)));
+        let api_key_svc = Box::leak(Box::new(ApiKeyService::new(
+            db,
+            championship_repo,
+            api_key_repo,
+        )));
+        let two_factor_svc = Box::leak(Box::new(TwoFactorService::new(
+            db,
+            two_factor_repo,
+            token_svc,
+        )));
+        let oauth_svc = Box::leak(Box::new(OAuthService::new(
+            db, oauth_repo, user_repo, user_svc,
+        )));
+
+        // Background jobs
+        ntex::rt::spawn(championship_svc.run_purge_job());
+        ntex::rt::spawn(token_svc.run_keys_reload_job());
 
         // Inner states
         let f1_state = Box::leak(Box::new(F1State::new(
@@ -77,6 +119,7 @@ impl AppState {
             driver_repo,
             championship_repo,
             championship_svc,
+            incident_svc,
         )));
 
         Ok(Self {
@@ -84,12 +127,21 @@ impl AppState {
             f1_svc: F1ServiceHandler::new(f1_state),
             user_repo,
             token_svc,
+            session_repo,
             championship_svc,
             championship_repo,
             driver_repo,
             driver_svc,
+            incident_repo,
+            incident_svc,
+            result_repo,
+            result_svc,
+            api_key_repo,
+            api_key_svc,
+            two_factor_repo,
+            two_factor_svc,
             email_svc: EmailService::new(),
-            discord_repo,
+            oauth_svc,
             server_repo: ServerRepository::new(db),
         })
     }
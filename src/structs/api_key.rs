@@ -0,0 +1,28 @@
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use serde_trim::string_trim;
+
+use crate::entity::{ApiKey, ApiKeyScope};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ApiKeyCreationForm {
+    #[serde(deserialize_with = "string_trim")]
+    #[garde(length(min = 1, max = 50))]
+    pub name: String,
+    #[garde(range(min = 700000000, max = 799999999))]
+    pub championship_id: i32,
+    #[garde(length(min = 1, max = 3))]
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+/// A newly created key, the secret is only ever returned here
+#[derive(Debug, Serialize)]
+pub struct CreatedApiKey {
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+    pub key: String,
+}
+
+// Path Parameters
+#[derive(Debug, Deserialize, Validate)]
+pub struct ApiKeyId(#[garde(range(min = 1))] pub i32);
@@ -1,4 +1,7 @@
+use std::collections::HashMap;
+
 use garde::Validate;
+use ntex::web::HttpRequest;
 use serde::{Deserialize, Serialize};
 use serde_trim::{option_string_trim, string_trim};
 
@@ -21,11 +24,6 @@ pub struct AuthTokens {
     pub refresh_token: String,
 }
 
-#[derive(Serialize)]
-pub struct NewAccessToken {
-    pub access_token: String,
-}
-
 // User Registration and Management
 #[derive(Deserialize, Debug, Validate)]
 pub struct UserRegistrationData {
@@ -40,21 +38,25 @@ pub struct UserRegistrationData {
     pub password: Option<String>,
     #[garde(inner(length(min = 10, max = 100)))]
     pub avatar: Option<String>,
+    /// Only set by external logins, clients always register local accounts
+    #[serde(skip)]
     #[garde(skip)]
     pub provider: Option<Provider>,
+    /// Account on the provider, linked as the first identity of the user
+    #[serde(skip)]
     #[garde(skip)]
-    pub discord_id: Option<i64>,
+    pub provider_user_id: Option<String>,
 }
 
 impl UserRegistrationData {
-    pub fn from_discord_user_info(discord_info: DiscordUserInfo) -> Self {
+    pub fn from_oauth_account(email: String, account: OAuthAccount) -> Self {
         UserRegistrationData {
-            avatar: discord_info.avatar_url(),
-            username: discord_info.username,
-            email: discord_info.email,
-            provider: Some(Provider::Discord),
+            avatar: account.avatar,
+            username: account.username.chars().take(20).collect(),
+            email,
+            provider: Some(account.provider),
             password: None,
-            discord_id: Some(discord_info.id),
+            provider_user_id: Some(account.provider_user_id),
         }
     }
 }
@@ -91,28 +93,94 @@ pub struct ClientFingerprint {
     pub fingerprint: String,
 }
 
-#[derive(Deserialize)]
-pub struct OauthAuthorizationCode {
+/// Device details stored with the session of a refresh token
+#[derive(Debug)]
+pub struct ClientDevice {
+    pub device: Option<String>,
+    pub ip: Option<String>,
+}
+
+impl ClientDevice {
+    pub fn from_request(req: &HttpRequest) -> Self {
+        let header = |name: &str| {
+            req.headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+
+        let device = header("User-Agent").map(|agent| agent.chars().take(100).collect());
+        let ip = header("CF-Connecting-IP")
+            .map(|ip| ip.chars().take(45).collect())
+            .or_else(|| req.peer_addr().map(|addr| addr.ip().to_string()));
+
+        ClientDevice { device, ip }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SessionId(#[garde(range(min = 1))] pub i32);
+
+/// Query of the redirect back from a provider, Steam sends its OpenID
+/// assertion instead of a code
+#[derive(Debug, Deserialize)]
+pub struct OAuthCallback {
+    pub state: String,
+    pub code: Option<String>,
+    #[serde(flatten)]
+    pub params: HashMap<String, String>,
+}
+
+/// Pending authorization, kept server side under its `state`
+#[derive(Debug, Clone)]
+pub struct OAuthState {
+    pub provider: Provider,
+    pub code_verifier: String,
+    /// Set when a signed in user links the provider instead of signing in
+    pub link_user_id: Option<i32>,
+}
+
+/// Account of a user on an external provider
+#[derive(Debug)]
+pub struct OAuthAccount {
+    pub provider: Provider,
+    pub provider_user_id: String,
+    /// Only set when the provider verified it
+    pub email: Option<String>,
+    pub username: String,
+    pub avatar: Option<String>,
+}
+
+/// One-time code from the redirect of an external login
+#[derive(Debug, Deserialize, Validate)]
+pub struct OAuthCodeExchange {
+    #[garde(length(min = 1, max = 64))]
     pub code: String,
 }
 
+/// Where to send the user to link a provider
+#[derive(Debug, Serialize)]
+pub struct AccountLinkRedirect {
+    pub authorize_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProviderPath(pub Provider);
+
 #[derive(Debug, Serialize)]
-pub struct DiscordExchangeRequest<'a> {
+pub struct OAuthTokenRequest<'a> {
     pub client_id: &'a str,
     pub client_secret: &'a str,
     pub grant_type: &'a str,
     pub code: &'a str,
     pub redirect_uri: &'a str,
+    pub code_verifier: &'a str,
 }
 
-#[allow(dead_code)]
 #[derive(Debug, Deserialize)]
-pub struct DiscordAuth {
+pub struct OAuthTokenResponse {
     pub access_token: String,
-    pub expires_in: u32,
-    pub refresh_token: String,
-    pub scope: String,
-    pub token_type: String,
 }
 
 #[derive(Deserialize, Debug)]
@@ -120,7 +188,9 @@ pub struct DiscordUserInfo {
     #[serde(deserialize_with = "deserialize_i64_from_string")]
     pub id: i64,
     pub username: String,
-    pub email: String,
+    pub email: Option<String>,
+    #[serde(default)]
+    pub verified: bool,
     pub avatar: Option<String>,
 }
 
@@ -134,3 +204,13 @@ impl DiscordUserInfo {
         })
     }
 }
+
+#[derive(Deserialize, Debug)]
+pub struct GoogleUserInfo {
+    pub sub: String,
+    pub name: Option<String>,
+    pub email: Option<String>,
+    #[serde(default)]
+    pub email_verified: bool,
+    pub picture: Option<String>,
+}
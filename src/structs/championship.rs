@@ -2,7 +2,7 @@ use garde::Validate;
 use serde::{Deserialize, Serialize};
 use serde_trim::{option_string_trim, string_trim};
 
-use crate::entity::{Category, ChampionshipRole, SharedChampionship, SharedRace};
+use crate::entity::{Category, ChampionshipRole, SharedChampionship, SharedRace, Visibility};
 
 use super::TeamIds;
 
@@ -14,6 +14,9 @@ pub struct ChampionshipCreationData {
     pub name: String,
     #[garde(skip)]
     pub category: Category,
+    #[serde(default)]
+    #[garde(skip)]
+    pub visibility: Visibility,
 }
 
 #[derive(Deserialize, Validate)]
@@ -28,6 +31,20 @@ pub struct ChampionshipUserAddForm {
     pub team_id: Option<TeamIds>,
 }
 
+#[derive(Deserialize, Validate)]
+pub struct ChampionshipUserUpdateForm {
+    #[garde(skip)]
+    pub role: Option<ChampionshipRole>,
+    #[garde(length(min = 1, max = 10))]
+    pub team_ids: Option<Vec<TeamIds>>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct ChampionshipOwnerForm {
+    #[garde(range(min = 600000000, max = 699999999))]
+    pub user_id: i32,
+}
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct ChampionshipUpdateData {
     #[garde(ascii, length(min = 3, max = 20))]
@@ -35,6 +52,76 @@ pub struct ChampionshipUpdateData {
     pub name: Option<String>,
     #[garde(skip)]
     pub category: Option<Category>,
+    #[garde(skip)]
+    pub visibility: Option<Visibility>,
+}
+
+// Championship Discovery
+#[derive(Debug, Deserialize, Validate)]
+pub struct ChampionshipSearchQuery {
+    #[serde(default, deserialize_with = "option_string_trim")]
+    #[garde(inner(length(min = 1, max = 20)))]
+    pub name: Option<String>,
+    #[garde(skip)]
+    pub category: Option<Category>,
+    #[serde(default = "default_page")]
+    #[garde(range(min = 1, max = 10000))]
+    pub page: i64,
+    #[serde(default = "default_per_page")]
+    #[garde(range(min = 1, max = 50))]
+    pub per_page: i64,
+}
+
+// Championship Export
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    #[default]
+    Json,
+}
+
+impl ExportFormat {
+    #[inline]
+    pub const fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Json => "application/json",
+        }
+    }
+
+    #[inline]
+    pub const fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ChampionshipExportQuery {
+    #[serde(default)]
+    #[garde(skip)]
+    pub format: ExportFormat,
+}
+
+#[inline]
+const fn default_page() -> i64 {
+    1
+}
+
+#[inline]
+const fn default_per_page() -> i64 {
+    20
+}
+
+#[derive(Serialize)]
+pub struct ChampionshipSearchData {
+    pub championships: Vec<SharedChampionship>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
 }
 
 // Service Status
@@ -49,14 +136,45 @@ pub struct ServiceStatus {
 #[derive(Deserialize, Validate)]
 pub struct ChampionshipId(#[garde(range(min = 700000000, max = 799999999))] pub i32);
 
+#[derive(Deserialize, Validate)]
+pub struct ChampionshipAndRequestId {
+    #[serde(rename = "id")]
+    #[garde(range(min = 700000000, max = 799999999))]
+    pub championship_id: i32,
+    #[garde(range(min = 1))]
+    pub request_id: i32,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct InvitationId(#[garde(range(min = 1))] pub i32);
+
 #[derive(Deserialize, Validate)]
 pub struct ChampionshipAndUserId {
+    #[serde(rename = "id")]
     #[garde(range(min = 700000000, max = 799999999))]
     pub championship_id: i32,
     #[garde(range(min = 600000000, max = 699999999))]
     pub user_id: i32,
 }
 
+#[derive(Deserialize, Validate)]
+pub struct ChampionshipAndTransferId {
+    #[serde(rename = "id")]
+    #[garde(range(min = 700000000, max = 799999999))]
+    pub championship_id: i32,
+    #[garde(range(min = 1))]
+    pub transfer_id: i32,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct ChampionshipAndClaimId {
+    #[serde(rename = "id")]
+    #[garde(range(min = 700000000, max = 799999999))]
+    pub championship_id: i32,
+    #[garde(range(min = 1))]
+    pub claim_id: i32,
+}
+
 #[derive(Serialize)]
 pub struct ChampionshipData {
     pub championship: SharedChampionship,
@@ -0,0 +1,52 @@
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use serde_trim::string_trim;
+
+use crate::entity::{SharedChampionship, SharedDriver, SharedDriverStats, SharedUser};
+
+// Driver Profile
+#[derive(Debug, Serialize)]
+pub struct LinkedUser {
+    pub id: i32,
+    pub username: String,
+    pub avatar: String,
+}
+
+impl From<SharedUser> for LinkedUser {
+    #[inline]
+    fn from(user: SharedUser) -> Self {
+        LinkedUser {
+            id: user.id,
+            username: user.username.clone(),
+            avatar: user.avatar.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DriverProfileData {
+    pub driver: SharedDriver,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<LinkedUser>,
+    pub championships: Vec<SharedChampionship>,
+    pub stats: SharedDriverStats,
+}
+
+// Driver Management
+#[derive(Debug, Deserialize, Validate)]
+pub struct DriverRenameForm {
+    #[serde(deserialize_with = "string_trim")]
+    #[garde(length(min = 1, max = 100))]
+    pub steam_name: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct DriverMergeForm {
+    #[serde(deserialize_with = "string_trim")]
+    #[garde(length(min = 1, max = 100))]
+    pub target: String,
+}
+
+// Path Parameters
+#[derive(Debug, Deserialize, Validate)]
+pub struct DriverSteamName(#[garde(length(min = 1, max = 100))] pub String);
@@ -8,6 +8,7 @@ use super::game::*;
 pub enum F1PacketData<'a> {
     Motion(&'a PacketMotionData),
     Session(&'a PacketSessionData),
+    LapData(&'a PacketLapData),
     Event(&'a PacketEventData),
     Participants(&'a PacketParticipantsData),
     FinalClassification(&'a PacketFinalClassificationData),
@@ -26,6 +27,7 @@ impl F1PacketData<'_> {
             PacketIds::Event => cast::<PacketEventData>(data).map(F1PacketData::Event),
             PacketIds::Motion => cast::<PacketMotionData>(data).map(F1PacketData::Motion),
             PacketIds::Session => cast::<PacketSessionData>(data).map(F1PacketData::Session),
+            PacketIds::LapData => cast::<PacketLapData>(data).map(F1PacketData::LapData),
             PacketIds::CarDamage => cast::<PacketCarDamageData>(data).map(F1PacketData::CarDamage),
             PacketIds::CarStatus => cast::<PacketCarStatusData>(data).map(F1PacketData::CarStatus),
             PacketIds::CarTelemetry => {
@@ -17,6 +17,153 @@ use crate::{
 
 include!(concat!(env!("OUT_DIR"), "/f1telemetry.rs"));
 
+impl SessionResult {
+    /// Adds a post-race time penalty to a driver and recomputes the classification.
+    ///
+    /// # Arguments
+    ///
+    /// * `steam_name` - The driver receiving the penalty.
+    /// * `seconds` - The penalty time in seconds.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the driver isn't classified in the session.
+    pub fn apply_time_penalty(&mut self, steam_name: &str, seconds: u32) -> bool {
+        let Some(classification) = self.classification.get_mut(steam_name) else {
+            return false;
+        };
+
+        classification.penalties_time =
+            Some(classification.penalties_time.unwrap_or_default() + seconds);
+        classification.num_penalties = Some(classification.num_penalties.unwrap_or_default() + 1);
+
+        let points = self.finisher_points();
+        self.reclassify(points);
+        true
+    }
+
+    /// Disqualifies a driver, moving it to the back of the classification without points.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the driver isn't classified in the session.
+    pub fn disqualify(&mut self, steam_name: &str) -> bool {
+        let points = self.finisher_points();
+
+        let Some(classification) = self.classification.get_mut(steam_name) else {
+            return false;
+        };
+
+        classification.result_status = Some(5);
+        self.reclassify(points);
+
+        if let Some(classification) = self.classification.get_mut(steam_name) {
+            classification.points = Some(0);
+        }
+
+        true
+    }
+
+    /// Sets the classification to the given order, the points of the finishers
+    /// move with it. Later reclassifications keep this order.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the order doesn't contain every classified driver exactly once.
+    pub fn reorder(&mut self, order: &[String]) -> bool {
+        if order.len() != self.classification.len()
+            || order.iter().enumerate().any(|(i, steam_name)| {
+                order[..i].contains(steam_name) || !self.classification.contains_key(steam_name)
+            })
+        {
+            return false;
+        }
+
+        let points = self.finisher_points();
+
+        for (i, steam_name) in order.iter().enumerate() {
+            if let Some(classification) = self.classification.get_mut(steam_name) {
+                classification.position = Some(i as u32 + 1);
+            }
+        }
+
+        self.manual_order = true;
+        self.award_points(points);
+        true
+    }
+
+    /// Sorts the finishers by completed laps and race time including penalties.
+    ///
+    /// Drivers that didn't finish keep their order behind the finishers, followed by the
+    /// disqualified ones. A manual order is kept, only the disqualified drivers move back.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - Points of the finishers before the change, handed out in the new order.
+    fn reclassify(&mut self, points: Vec<Option<u32>>) {
+        let manual_order = self.manual_order;
+        let mut entries = self.entries_by_position();
+
+        let total_time = |c: &FinalClassificationData| {
+            c.race_time.unwrap_or_default() + c.penalties_time.unwrap_or_default() as f64
+        };
+
+        // 3 = finished, 5 = disqualified
+        let rank = |c: &FinalClassificationData| match c.result_status {
+            Some(5) => 2,
+            _ if manual_order => 0,
+            Some(3) => 0,
+            _ => 1,
+        };
+
+        entries.sort_by(|a, b| {
+            rank(a).cmp(&rank(b)).then_with(|| {
+                if rank(a) == 0 && !manual_order {
+                    b.laps
+                        .cmp(&a.laps)
+                        .then(total_time(a).total_cmp(&total_time(b)))
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            })
+        });
+
+        for (i, classification) in entries.into_iter().enumerate() {
+            classification.position = Some(i as u32 + 1);
+        }
+
+        self.award_points(points);
+    }
+
+    /// Hands out the points of the finishers following the classification,
+    /// drivers that didn't finish keep their own.
+    fn award_points(&mut self, points: Vec<Option<u32>>) {
+        let mut points = points.into_iter();
+
+        for classification in self.entries_by_position() {
+            if classification.result_status == Some(3) {
+                classification.points = points.next().flatten().or(Some(0));
+            }
+        }
+    }
+
+    /// Classification entries sorted by their current position.
+    fn entries_by_position(&mut self) -> Vec<&mut FinalClassificationData> {
+        let mut entries: Vec<_> = self.classification.values_mut().collect();
+        entries.sort_by_key(|c| c.position.filter(|&p| p > 0).unwrap_or(u32::MAX));
+        entries
+    }
+
+    /// Points currently awarded to the finishers, in classification order.
+    fn finisher_points(&mut self) -> Vec<Option<u32>> {
+        self.entries_by_position()
+            .into_iter()
+            .filter(|c| c.result_status == Some(3))
+            .map(|c| c.points)
+            .collect()
+    }
+}
+
 /// Events that are not sent to avoid unnecessary data transmission.
 const NOT_SEND_EVENTS: [EventCode; 9] = [
     EventCode::ButtonStatus,
@@ -700,6 +847,11 @@ impl F1GeneralInfo {
                     player_changed = true;
                 }
 
+                if cur_player.timing != last_player.timing {
+                    diff_player.timing = cur_player.timing;
+                    player_changed = true;
+                }
+
                 if cur_player.final_classification != last_player.final_classification {
                     diff_player.final_classification = cur_player.final_classification.clone();
                     player_changed = true;
@@ -889,3 +1041,134 @@ impl EventData {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finisher(position: u32, points: u32, race_time: f64) -> FinalClassificationData {
+        FinalClassificationData {
+            position: Some(position),
+            laps: Some(10),
+            points: Some(points),
+            result_status: Some(3),
+            race_time: Some(race_time),
+            ..Default::default()
+        }
+    }
+
+    fn session() -> SessionResult {
+        let mut result = SessionResult::default();
+        let classification = &mut result.classification;
+
+        classification.insert("a".into(), finisher(1, 25, 600.0));
+        classification.insert("b".into(), finisher(2, 18, 603.0));
+        classification.insert("c".into(), finisher(3, 15, 610.0));
+        classification.insert(
+            "d".into(),
+            FinalClassificationData {
+                position: Some(4),
+                laps: Some(3),
+                points: Some(0),
+                result_status: Some(4),
+                ..Default::default()
+            },
+        );
+
+        result
+    }
+
+    #[test]
+    fn time_penalty_moves_positions_and_points() {
+        let mut result = session();
+
+        assert!(result.apply_time_penalty("a", 5));
+
+        let a = &result.classification["a"];
+        let b = &result.classification["b"];
+
+        assert_eq!(b.position, Some(1));
+        assert_eq!(b.points, Some(25));
+        assert_eq!(a.position, Some(2));
+        assert_eq!(a.points, Some(18));
+        assert_eq!(a.penalties_time, Some(5));
+        assert_eq!(a.num_penalties, Some(1));
+        assert_eq!(result.classification["c"].position, Some(3));
+        assert_eq!(result.classification["d"].position, Some(4));
+    }
+
+    #[test]
+    fn time_penalty_keeps_non_finishers_behind() {
+        let mut result = session();
+
+        assert!(result.apply_time_penalty("c", 60));
+        assert_eq!(result.classification["c"].position, Some(3));
+        assert_eq!(result.classification["d"].position, Some(4));
+        assert!(!result.apply_time_penalty("e", 5));
+    }
+
+    #[test]
+    fn disqualified_driver_loses_points() {
+        let mut result = session();
+
+        assert!(result.disqualify("a"));
+
+        let a = &result.classification["a"];
+
+        assert_eq!(a.position, Some(4));
+        assert_eq!(a.points, Some(0));
+        assert_eq!(result.classification["b"].points, Some(25));
+        assert_eq!(result.classification["c"].points, Some(18));
+        assert_eq!(result.classification["d"].position, Some(3));
+        assert_eq!(result.classification["d"].points, Some(0));
+    }
+
+    #[test]
+    fn reorder_requires_every_driver_once() {
+        let mut result = session();
+        let order = |names: &[&str]| {
+            names
+                .iter()
+                .map(|&name| name.to_owned())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(!result.reorder(&order(&["c", "b", "a"])));
+        assert!(!result.reorder(&order(&["c", "b", "a", "a"])));
+        assert!(result.reorder(&order(&["c", "b", "a", "d"])));
+        assert_eq!(result.classification["c"].position, Some(1));
+        assert_eq!(result.classification["c"].points, Some(25));
+        assert_eq!(result.classification["a"].points, Some(15));
+        assert_eq!(result.classification["d"].points, Some(0));
+    }
+
+    #[test]
+    fn reorder_only_awards_points_to_finishers() {
+        let mut result = session();
+        let order = ["d", "c", "b", "a"].map(str::to_owned);
+
+        assert!(result.reorder(&order));
+        assert_eq!(result.classification["d"].position, Some(1));
+        assert_eq!(result.classification["d"].points, Some(0));
+        assert_eq!(result.classification["c"].points, Some(25));
+        assert_eq!(result.classification["a"].points, Some(15));
+    }
+
+    #[test]
+    fn time_penalty_keeps_manual_order() {
+        let mut result = session();
+        let order = ["c", "b", "a", "d"].map(str::to_owned);
+
+        assert!(result.reorder(&order));
+        assert!(result.apply_time_penalty("c", 30));
+        assert_eq!(result.classification["c"].position, Some(1));
+        assert_eq!(result.classification["c"].points, Some(25));
+
+        assert!(result.disqualify("c"));
+        assert_eq!(result.classification["b"].position, Some(1));
+        assert_eq!(result.classification["b"].points, Some(25));
+        assert_eq!(result.classification["a"].points, Some(18));
+        assert_eq!(result.classification["c"].position, Some(4));
+        assert_eq!(result.classification["c"].points, Some(0));
+    }
+}
@@ -0,0 +1,157 @@
+use chrono::{DateTime, Utc};
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use serde_trim::string_trim;
+
+use crate::structs::protos::{FinalClassificationData, SessionResult};
+
+// Season Import
+/// Historical season imported into a championship.
+///
+/// Drivers are registered in the championship, or updated if they already are.
+/// Every race is created with the classification of each of its sessions.
+#[derive(Debug, Deserialize, Validate)]
+pub struct ChampionshipImportForm {
+    #[serde(default)]
+    #[garde(length(max = 100))]
+    pub drivers: Vec<ImportDriver>,
+    #[garde(length(min = 1, max = 50))]
+    pub races: Vec<ImportRace>,
+}
+
+/// Driver registration, `nationality` follows the game ids.
+#[derive(Debug, Deserialize, Validate)]
+pub struct ImportDriver {
+    #[serde(deserialize_with = "string_trim")]
+    #[garde(length(min = 1, max = 100))]
+    pub steam_name: String,
+    #[serde(default)]
+    #[garde(range(min = 0, max = 255))]
+    pub nationality: i16,
+    #[garde(range(min = 0, max = 255))]
+    pub team_id: i16,
+    #[garde(range(min = 1, max = 99))]
+    pub number: i16,
+}
+
+/// Race held on `track_id` (game ids) with the classification of its sessions.
+#[derive(Debug, Deserialize, Validate)]
+pub struct ImportRace {
+    #[garde(range(min = 0, max = 255))]
+    pub track_id: i16,
+    #[garde(skip)]
+    pub date: DateTime<Utc>,
+    #[garde(length(min = 1, max = 18))]
+    pub sessions: Vec<ImportSession>,
+}
+
+/// Classification of a session, `session_type` follows the game ids (15 = race).
+#[derive(Debug, Deserialize, Validate)]
+pub struct ImportSession {
+    #[garde(range(min = 1, max = 18))]
+    pub session_type: i16,
+    #[garde(length(min = 1, max = 22))]
+    pub classification: Vec<ImportClassification>,
+}
+
+/// Final classification of a driver, times in milliseconds except `race_time` in seconds.
+#[derive(Debug, Deserialize, Validate)]
+pub struct ImportClassification {
+    #[serde(deserialize_with = "string_trim")]
+    #[garde(length(min = 1, max = 100))]
+    pub steam_name: String,
+    #[garde(range(min = 1, max = 22))]
+    pub position: u32,
+    #[garde(inner(range(min = 1, max = 22)))]
+    pub grid_position: Option<u32>,
+    #[garde(inner(range(max = 200)))]
+    pub laps: Option<u32>,
+    #[garde(inner(range(max = 100)))]
+    pub points: Option<u32>,
+    #[garde(inner(range(max = 50)))]
+    pub pit_stops: Option<u32>,
+    /// Defaults to 3 (finished)
+    #[garde(inner(range(min = 2, max = 7)))]
+    pub result_status: Option<u32>,
+    #[garde(skip)]
+    pub best_lap_time: Option<u32>,
+    #[garde(inner(range(min = 0.0)))]
+    pub race_time: Option<f64>,
+    #[garde(inner(range(max = 3600)))]
+    pub penalties_time: Option<u32>,
+    #[garde(inner(range(max = 100)))]
+    pub num_penalties: Option<u32>,
+}
+
+impl From<&ImportSession> for SessionResult {
+    fn from(session: &ImportSession) -> Self {
+        let classification = session
+            .classification
+            .iter()
+            .map(|row| {
+                let data = FinalClassificationData {
+                    position: Some(row.position),
+                    laps: row.laps,
+                    grid_position: row.grid_position,
+                    points: row.points,
+                    pit_stops: row.pit_stops,
+                    result_status: Some(row.result_status.unwrap_or(3)),
+                    best_lap_time: row.best_lap_time,
+                    race_time: row.race_time,
+                    penalties_time: row.penalties_time,
+                    num_penalties: row.num_penalties,
+                    ..Default::default()
+                };
+
+                (row.steam_name.clone(), data)
+            })
+            .collect();
+
+        SessionResult {
+            classification,
+            ..Default::default()
+        }
+    }
+}
+
+/// Outcome of an import, nothing is written if any row has errors.
+#[derive(Debug, Default, Serialize)]
+pub struct ImportReport {
+    pub races: usize,
+    pub drivers: usize,
+    pub results: usize,
+    pub errors: Vec<ImportRowError>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportRowError {
+    pub row: String,
+    pub message: String,
+}
+
+impl ImportReport {
+    /// Validates a row, recording every failing field under `row`.
+    pub fn validate_row<T: Validate<Context = ()>>(&mut self, row: String, value: &T) {
+        if let Err(report) = value.validate() {
+            for (path, error) in report.iter() {
+                self.errors.push(ImportRowError {
+                    row: row.clone(),
+                    message: format!("{}: {}", path, error),
+                });
+            }
+        }
+    }
+
+    #[inline]
+    pub fn push_error(&mut self, row: String, message: impl Into<String>) {
+        self.errors.push(ImportRowError {
+            row,
+            message: message.into(),
+        });
+    }
+
+    #[inline]
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+}
@@ -0,0 +1,50 @@
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use serde_trim::string_trim;
+
+use crate::entity::{Incident, IncidentComment, IncidentType};
+
+// Live Recording
+#[derive(Debug)]
+pub struct NewIncident {
+    pub race_id: i32,
+    pub session_type: i16,
+    pub incident_type: IncidentType,
+    pub lap: i16,
+    pub session_time: f32,
+    pub steam_name: String,
+    pub other_steam_name: Option<String>,
+    pub penalty_type: Option<i16>,
+    pub infringement_type: Option<i16>,
+    pub game_penalty_time: Option<i16>,
+}
+
+// Steward Review
+#[derive(Debug, Serialize)]
+pub struct IncidentData {
+    pub incident: Incident,
+    pub comments: Vec<IncidentComment>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct IncidentCommentForm {
+    #[serde(deserialize_with = "string_trim")]
+    #[garde(length(min = 1, max = 1000))]
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct IncidentPenaltyForm {
+    #[garde(range(min = 1, max = 600))]
+    pub seconds: i16,
+}
+
+// Path Parameters
+#[derive(Deserialize, Validate)]
+pub struct ChampionshipAndIncidentId {
+    #[serde(rename = "id")]
+    #[garde(range(min = 700000000, max = 799999999))]
+    pub championship_id: i32,
+    #[garde(range(min = 1))]
+    pub incident_id: i32,
+}
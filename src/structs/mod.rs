@@ -1,15 +1,27 @@
+pub(crate) use api_key::*;
 pub(crate) use auth::*;
 pub(crate) use championship::*;
+pub(crate) use driver::*;
 pub(crate) use f1::*;
+pub(crate) use import::*;
+pub(crate) use incident::*;
+pub(crate) use result::*;
 pub(crate) use server::*;
 pub(crate) use templates::*;
 pub(crate) use token::*;
+pub(crate) use two_factor::*;
 pub(crate) use user::*;
 
+mod api_key;
 mod auth;
 mod championship;
+mod driver;
 mod f1;
+mod import;
+mod incident;
+mod result;
 mod server;
 mod templates;
 mod token;
+mod two_factor;
 mod user;
@@ -0,0 +1,158 @@
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use serde_trim::{option_string_trim, string_trim};
+
+use crate::{
+    entity::{AmendmentType, ResultAmendment},
+    error::{AppResult, ResultError},
+    structs::protos::SessionResult,
+};
+
+// Amendments
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(tag = "type")]
+pub enum AmendmentKind {
+    TimePenalty {
+        #[serde(deserialize_with = "string_trim")]
+        #[garde(length(min = 1, max = 100))]
+        steam_name: String,
+        #[garde(range(min = 1, max = 600))]
+        seconds: i16,
+    },
+    Disqualification {
+        #[serde(deserialize_with = "string_trim")]
+        #[garde(length(min = 1, max = 100))]
+        steam_name: String,
+    },
+    Reorder {
+        #[garde(length(min = 1, max = 22), inner(length(min = 1, max = 100)))]
+        order: Vec<String>,
+    },
+}
+
+impl AmendmentKind {
+    /// Applies the amendment on top of a session classification.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the driver isn't classified or the order doesn't match
+    /// the classified drivers.
+    pub fn apply(&self, result: &mut SessionResult) -> AppResult<()> {
+        let applied = match self {
+            AmendmentKind::TimePenalty {
+                steam_name,
+                seconds,
+            } => result.apply_time_penalty(steam_name, *seconds as u32),
+            AmendmentKind::Disqualification { steam_name } => result.disqualify(steam_name),
+            AmendmentKind::Reorder { order } => {
+                if !result.reorder(order) {
+                    Err(ResultError::InvalidOrder)?
+                }
+
+                true
+            }
+        };
+
+        if !applied {
+            Err(ResultError::DriverNotClassified)?
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub const fn amendment_type(&self) -> AmendmentType {
+        match self {
+            AmendmentKind::TimePenalty { .. } => AmendmentType::TimePenalty,
+            AmendmentKind::Disqualification { .. } => AmendmentType::Disqualification,
+            AmendmentKind::Reorder { .. } => AmendmentType::Reorder,
+        }
+    }
+
+    #[inline]
+    pub fn steam_name(&self) -> Option<&str> {
+        match self {
+            AmendmentKind::TimePenalty { steam_name, .. }
+            | AmendmentKind::Disqualification { steam_name } => Some(steam_name),
+            AmendmentKind::Reorder { .. } => None,
+        }
+    }
+
+    #[inline]
+    pub fn seconds(&self) -> Option<i16> {
+        match self {
+            AmendmentKind::TimePenalty { seconds, .. } => Some(*seconds),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn order(&self) -> Option<&[String]> {
+        match self {
+            AmendmentKind::Reorder { order } => Some(order),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<&ResultAmendment> for AmendmentKind {
+    type Error = ResultError;
+
+    fn try_from(amendment: &ResultAmendment) -> Result<Self, Self::Error> {
+        let kind = match amendment.amendment_type {
+            AmendmentType::TimePenalty => AmendmentKind::TimePenalty {
+                steam_name: amendment.steam_name.clone().ok_or(ResultError::NotFound)?,
+                seconds: amendment.seconds.unwrap_or_default(),
+            },
+            AmendmentType::Disqualification => AmendmentKind::Disqualification {
+                steam_name: amendment.steam_name.clone().ok_or(ResultError::NotFound)?,
+            },
+            AmendmentType::Reorder => AmendmentKind::Reorder {
+                order: amendment
+                    .classification_order
+                    .clone()
+                    .ok_or(ResultError::NotFound)?,
+            },
+        };
+
+        Ok(kind)
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ResultAmendmentForm {
+    #[serde(flatten)]
+    #[garde(dive)]
+    pub amendment: AmendmentKind,
+    #[serde(default, deserialize_with = "option_string_trim")]
+    #[garde(length(min = 1, max = 500))]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RaceResultData {
+    pub race_id: i32,
+    pub session_type: i16,
+    pub result: SessionResult,
+    pub amendments: Vec<ResultAmendment>,
+}
+
+/// Effective classification of a race session as streamed by the exports
+#[derive(Debug, Serialize)]
+pub struct SessionResultEntry {
+    pub race_id: i32,
+    pub session_type: i16,
+    pub result: SessionResult,
+}
+
+// Path Parameters
+#[derive(Deserialize, Validate)]
+pub struct RaceResultPath {
+    #[serde(rename = "id")]
+    #[garde(range(min = 700000000, max = 799999999))]
+    pub championship_id: i32,
+    #[garde(range(min = 1))]
+    pub race_id: i32,
+    #[garde(range(min = 0, max = 18))]
+    pub session_type: i16,
+}
@@ -20,3 +20,11 @@ pub struct PasswordResetTemplate {
 #[derive(TemplateSimple)]
 #[template(path = "password_changed.stpl")]
 pub struct PasswordChangeConfirmationTemplate {}
+
+#[derive(TemplateSimple)]
+#[template(path = "championship_invitation.stpl")]
+pub struct ChampionshipInvitationTemplate {
+    pub championship_name: String,
+    pub invited_by: String,
+    pub invitation_link: String,
+}
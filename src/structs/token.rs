@@ -1,4 +1,4 @@
-use chrono::{Duration, Local, TimeDelta};
+use chrono::{DateTime, Duration, Local, TimeDelta, Utc};
 use serde::{Deserialize, Serialize};
 use tokio::time::Instant;
 
@@ -9,17 +9,47 @@ pub enum TokenPurpose {
     EmailVerification,
     PasswordReset,
     RefreshAuthentication,
+    ChampionshipInvitation,
+    TwoFactorPending,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TokenPayload {
     pub exp: usize,
+    pub aud: String,
     pub subject_id: i32,
     pub purpose: TokenPurpose,
+    /// Unique ID, set on refresh tokens so every rotation yields a new token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
+    /// Token version of the user, set on access tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ver: Option<i32>,
 }
 
 // Token Purpose Implementation
 impl TokenPurpose {
+    pub const ALL: [TokenPurpose; 6] = [
+        TokenPurpose::Authentication,
+        TokenPurpose::EmailVerification,
+        TokenPurpose::PasswordReset,
+        TokenPurpose::RefreshAuthentication,
+        TokenPurpose::ChampionshipInvitation,
+        TokenPurpose::TwoFactorPending,
+    ];
+
+    /// Audience claim of the purpose, a token is only accepted by its own audience
+    pub const fn audience(&self) -> &'static str {
+        match self {
+            TokenPurpose::Authentication => "intelli:auth",
+            TokenPurpose::EmailVerification => "intelli:email-verification",
+            TokenPurpose::PasswordReset => "intelli:password-reset",
+            TokenPurpose::RefreshAuthentication => "intelli:refresh",
+            TokenPurpose::ChampionshipInvitation => "intelli:championship-invitation",
+            TokenPurpose::TwoFactorPending => "intelli:two-factor",
+        }
+    }
+
     pub fn expiry_instant(&self) -> Instant {
         Instant::now() + self.validity_duration().unwrap().to_std().unwrap()
     }
@@ -33,10 +63,16 @@ impl TokenPurpose {
             .timestamp() as usize
     }
 
+    pub fn expiration_date(&self) -> DateTime<Utc> {
+        Utc::now() + self.validity_duration().unwrap()
+    }
+
     const fn validity_duration(&self) -> Option<TimeDelta> {
         match self {
             TokenPurpose::RefreshAuthentication => Duration::try_days(17),
             TokenPurpose::Authentication => Duration::try_days(1),
+            TokenPurpose::ChampionshipInvitation => Duration::try_days(7),
+            TokenPurpose::TwoFactorPending => Duration::try_minutes(5),
             _ => Duration::try_minutes(15),
         }
     }
@@ -0,0 +1,39 @@
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+use serde_trim::string_trim;
+
+/// Secret of a pending TOTP enrolment, to be added to an authenticator app
+#[derive(Debug, Serialize)]
+pub struct TwoFactorSetup {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+/// A TOTP code, or a recovery code where accepted
+#[derive(Debug, Deserialize, Validate)]
+pub struct TwoFactorCode {
+    #[serde(deserialize_with = "string_trim")]
+    #[garde(length(min = 6, max = 11))]
+    pub code: String,
+}
+
+/// Recovery codes issued when two-factor authentication is enabled, only returned once
+#[derive(Debug, Serialize)]
+pub struct RecoveryCodes {
+    pub recovery_codes: Vec<String>,
+}
+
+/// Returned by the login of users with two-factor authentication enabled
+#[derive(Debug, Serialize)]
+pub struct TwoFactorChallenge {
+    pub two_factor_token: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct TwoFactorLogin {
+    #[garde(length(min = 1))]
+    pub token: String,
+    #[serde(deserialize_with = "string_trim")]
+    #[garde(length(min = 6, max = 11))]
+    pub code: String,
+}
@@ -1,8 +1,8 @@
 use garde::Validate;
 use serde::{Deserialize, Serialize};
-use serde_trim::option_string_trim;
+use serde_trim::{option_string_trim, string_trim};
 
-use crate::entity::{SharedChampionship, SharedUser};
+use crate::entity::{SharedChampionship, SharedDriver, SharedUser};
 
 // User Management
 #[derive(Debug, Deserialize, Validate)]
@@ -18,9 +18,43 @@ pub struct UserUpdateData {
 #[derive(Debug, Serialize)]
 pub struct UserProfileData {
     pub user: SharedUser,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver: Option<SharedDriver>,
     pub championships: Vec<SharedChampionship>,
 }
 
+// Driver Claims
+#[derive(Debug, Deserialize, Validate)]
+pub struct DriverClaimForm {
+    #[serde(deserialize_with = "string_trim")]
+    #[garde(length(min = 1, max = 100))]
+    pub steam_name: String,
+    /// Championship whose admins approve the claim
+    #[garde(range(min = 700000000, max = 799999999))]
+    pub championship_id: i32,
+}
+
 // Path Parameters
 #[derive(Debug, Deserialize, Validate)]
 pub struct UserId(#[garde(range(min = 600000000, max = 699999999))] pub i32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn claim_needs_a_championship_to_approve_it() {
+        let claim_form = serde_json::from_str::<DriverClaimForm>(
+            r#"{"steam_name":" Driver ","championship_id":700000000}"#,
+        )
+        .unwrap();
+        assert_eq!(claim_form.steam_name, "Driver");
+        assert!(claim_form.validate().is_ok());
+
+        assert!(serde_json::from_str::<DriverClaimForm>(r#"{"steam_name":"Driver"}"#).is_err());
+        assert!(serde_json::from_str::<DriverClaimForm>(
+            r#"{"steam_name":"Driver","championship_id":null}"#
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,340 @@
+use std::{borrow::Cow, fmt::Write};
+
+use ntex::util::Bytes;
+use serde::Serialize;
+
+use crate::{
+    entity::{Championship, ChampionshipDriver, SharedRace, Standing},
+    error::AppResult,
+    structs::{ExportFormat, SessionResultEntry},
+};
+
+/// Championship data that fits in memory, written before the streamed results.
+#[derive(Serialize)]
+pub struct ChampionshipExportHead<'a> {
+    pub championship: &'a Championship,
+    pub drivers: &'a [ChampionshipDriver],
+    pub standings: &'a [Standing],
+    pub races: &'a [SharedRace],
+}
+
+/// Encodes a championship export chunk by chunk so it can be streamed.
+///
+/// JSON exports are a single document with the results as the last array,
+/// CSV exports are split in sections, each one with its own header row.
+pub struct ChampionshipExporter {
+    format: ExportFormat,
+    first_result: bool,
+}
+
+impl ChampionshipExporter {
+    pub fn new(format: ExportFormat) -> Self {
+        Self {
+            format,
+            first_result: true,
+        }
+    }
+
+    /// Encodes everything that goes before the results.
+    pub fn head(&self, head: &ChampionshipExportHead) -> AppResult<Bytes> {
+        match self.format {
+            ExportFormat::Json => {
+                let mut buf = serde_json::to_vec(head)?;
+
+                // Reopen the object to append the streamed results
+                buf.pop();
+                buf.extend_from_slice(br#","results":["#);
+
+                Ok(Bytes::from(buf))
+            }
+
+            ExportFormat::Csv => {
+                let mut buf = String::new();
+                let championship = head.championship;
+
+                let _ = writeln!(buf, "# championship\nid,name,category,created_at");
+                let _ = writeln!(
+                    buf,
+                    "{},{},{:?},{}\n",
+                    championship.id,
+                    csv_field(&championship.name),
+                    championship.category,
+                    championship.created_at.to_rfc3339()
+                );
+
+                let _ = writeln!(buf, "# drivers\nsteam_name,team_id,number");
+                for driver in head.drivers {
+                    let _ = writeln!(
+                        buf,
+                        "{},{},{}",
+                        csv_field(&driver.steam_name),
+                        driver.team_id,
+                        driver.number
+                    );
+                }
+
+                let _ = writeln!(
+                    buf,
+                    "\n# standings\nposition,steam_name,points,races,wins,podiums"
+                );
+                for (i, standing) in head.standings.iter().enumerate() {
+                    let _ = writeln!(
+                        buf,
+                        "{},{},{},{},{},{}",
+                        i + 1,
+                        csv_field(&standing.steam_name),
+                        standing.points,
+                        standing.races,
+                        standing.wins,
+                        standing.podiums
+                    );
+                }
+
+                let _ = writeln!(buf, "\n# races\nrace_id,track_id,date");
+                for race in head.races {
+                    let _ = writeln!(
+                        buf,
+                        "{},{},{}",
+                        race.id,
+                        race.track_id,
+                        race.date.to_rfc3339()
+                    );
+                }
+
+                let _ = writeln!(
+                    buf,
+                    "\n# results\nrace_id,session_type,position,steam_name,grid_position,laps,points,\
+                     pit_stops,result_status,best_lap_time_ms,race_time,penalties_time,num_penalties,\
+                     tyre_stints,lap_times_ms"
+                );
+
+                Ok(Bytes::from(buf))
+            }
+        }
+    }
+
+    /// Encodes the classification of a race session.
+    pub fn result(&mut self, entry: &SessionResultEntry) -> AppResult<Bytes> {
+        match self.format {
+            ExportFormat::Json => {
+                let mut buf = Vec::new();
+
+                if !std::mem::take(&mut self.first_result) {
+                    buf.push(b',');
+                }
+
+                serde_json::to_writer(&mut buf, entry)?;
+                Ok(Bytes::from(buf))
+            }
+
+            ExportFormat::Csv => {
+                let mut buf = String::new();
+                let mut classification: Vec<_> = entry.result.classification.iter().collect();
+                classification.sort_by_key(|(_, c)| c.position.unwrap_or(u32::MAX));
+
+                for (steam_name, c) in classification {
+                    // Visual compound and last lap of each stint, e.g. `16:12|17:30`
+                    let tyre_stints = c
+                        .tyre_stints_visual
+                        .iter()
+                        .zip(&c.tyre_stints_end_laps)
+                        .map(|(compound, end_lap)| format!("{}:{}", compound, end_lap))
+                        .collect::<Vec<_>>()
+                        .join("|");
+
+                    // Lap times in order, e.g. `92345|90871`
+                    let lap_times = entry
+                        .result
+                        .lap_history
+                        .get(steam_name)
+                        .map(|history| {
+                            history
+                                .lap_history_data
+                                .iter()
+                                .map(|lap| lap.lap_time.unwrap_or_default().to_string())
+                                .collect::<Vec<_>>()
+                                .join("|")
+                        })
+                        .unwrap_or_default();
+
+                    let _ = writeln!(
+                        buf,
+                        "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                        entry.race_id,
+                        entry.session_type,
+                        c.position.unwrap_or_default(),
+                        csv_field(steam_name),
+                        c.grid_position.unwrap_or_default(),
+                        c.laps.unwrap_or_default(),
+                        c.points.unwrap_or_default(),
+                        c.pit_stops.unwrap_or_default(),
+                        c.result_status.unwrap_or_default(),
+                        c.best_lap_time.unwrap_or_default(),
+                        c.race_time.unwrap_or_default(),
+                        c.penalties_time.unwrap_or_default(),
+                        c.num_penalties.unwrap_or_default(),
+                        tyre_stints,
+                        lap_times
+                    );
+                }
+
+                Ok(Bytes::from(buf))
+            }
+        }
+    }
+
+    /// Encodes everything that goes after the results.
+    pub fn tail(&self) -> Bytes {
+        match self.format {
+            ExportFormat::Json => Bytes::from_static(b"]}"),
+            ExportFormat::Csv => Bytes::new(),
+        }
+    }
+
+    /// Ends an export that failed halfway, in place of the tail.
+    ///
+    /// JSON exports stay a valid document with an `error` field after the
+    /// results, CSV exports end with an `error` section.
+    pub fn interrupted(&self) -> Bytes {
+        match self.format {
+            ExportFormat::Json => Bytes::from_static(br#"],"error":"Export interrupted"}"#),
+            ExportFormat::Csv => Bytes::from_static(b"\n# error\nExport interrupted\n"),
+        }
+    }
+}
+
+/// Quotes a CSV field when it contains a separator, quote or line break.
+fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::{
+        entity::{Category, Visibility},
+        structs::protos::{FinalClassificationData, HistoryData, LapHistoryData, SessionResult},
+    };
+
+    fn championship() -> Championship {
+        Championship {
+            id: 700000001,
+            port: 27700,
+            name: String::from("Sunday, League"),
+            owner_id: 600000001,
+            category: Category::F1,
+            created_at: Utc::now(),
+            updated_at: None,
+            visibility: Visibility::Public,
+            deleted_at: None,
+        }
+    }
+
+    fn entry(race_id: i32) -> SessionResultEntry {
+        let mut result = SessionResult::default();
+        result.classification.insert(
+            String::from("driver"),
+            FinalClassificationData {
+                position: Some(1),
+                tyre_stints_visual: vec![16, 17],
+                tyre_stints_end_laps: vec![12, 30],
+                ..Default::default()
+            },
+        );
+        result.lap_history.insert(
+            String::from("driver"),
+            HistoryData {
+                num_laps: Some(2),
+                lap_history_data: vec![
+                    LapHistoryData {
+                        lap_time: Some(92345),
+                        ..Default::default()
+                    },
+                    LapHistoryData {
+                        lap_time: Some(90871),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+        );
+
+        SessionResultEntry {
+            race_id,
+            session_type: 15,
+            result,
+        }
+    }
+
+    fn export(format: ExportFormat) -> String {
+        let championship = championship();
+        let mut exporter = ChampionshipExporter::new(format);
+        let mut buf = Vec::new();
+
+        let head = ChampionshipExportHead {
+            championship: &championship,
+            drivers: &[],
+            standings: &[],
+            races: &[],
+        };
+
+        buf.extend_from_slice(&exporter.head(&head).unwrap());
+        buf.extend_from_slice(&exporter.result(&entry(1)).unwrap());
+        buf.extend_from_slice(&exporter.result(&entry(2)).unwrap());
+        buf.extend_from_slice(&exporter.tail());
+
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn json_export_is_a_single_document() {
+        let export: serde_json::Value = serde_json::from_str(&export(ExportFormat::Json)).unwrap();
+
+        assert_eq!(export["championship"]["name"], "Sunday, League");
+        assert_eq!(export["results"].as_array().unwrap().len(), 2);
+        assert_eq!(export["results"][1]["race_id"], 2);
+        assert_eq!(
+            export["results"][0]["result"]["lap_history"]["driver"]["lap_history_data"][1]
+                ["lap_time"],
+            90871
+        );
+    }
+
+    #[test]
+    fn interrupted_json_export_is_valid() {
+        let championship = championship();
+        let mut exporter = ChampionshipExporter::new(ExportFormat::Json);
+        let mut buf = Vec::new();
+
+        let head = ChampionshipExportHead {
+            championship: &championship,
+            drivers: &[],
+            standings: &[],
+            races: &[],
+        };
+
+        buf.extend_from_slice(&exporter.head(&head).unwrap());
+        buf.extend_from_slice(&exporter.result(&entry(1)).unwrap());
+        buf.extend_from_slice(&exporter.interrupted());
+
+        let export: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+
+        assert_eq!(export["error"], "Export interrupted");
+        assert_eq!(export["results"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn csv_export_quotes_fields() {
+        let export = export(ExportFormat::Csv);
+
+        assert!(export.contains("700000001,\"Sunday, League\",F1,"));
+        assert!(export.contains("\n2,15,1,driver,0,0,0,0,0,0,0,0,0,16:12|17:30,92345|90871\n"));
+        assert_eq!(csv_field("a\"b"), "\"a\"\"b\"");
+    }
+}
@@ -0,0 +1,342 @@
+use std::{fs, path::Path, sync::Arc};
+
+use ahash::AHashMap;
+use base64_simd::URL_SAFE_NO_PAD;
+use jsonwebtoken::{
+    decode, decode_header, encode,
+    errors::{Error as JwtError, ErrorKind},
+    jwk::{
+        AlgorithmParameters, CommonParameters, Jwk, JwkSet, KeyAlgorithm, PublicKeyUse,
+        RSAKeyParameters, RSAKeyType,
+    },
+    Algorithm, DecodingKey, EncodingKey, Header, TokenData, Validation,
+};
+use openssl::{
+    error::ErrorStack,
+    pkey::{PKey, Public},
+    rsa::Rsa,
+    x509::X509,
+};
+use parking_lot::RwLock;
+use serde::{de::DeserializeOwned, Serialize};
+use tracing::{error, info};
+
+use crate::{
+    config::constants::{JWT_ACTIVE_KID_FILE, JWT_KEYS_DIR, JWT_LEGACY_KEY, JWT_LEGACY_KID},
+    error::{AppResult, CommonError},
+};
+
+/// Signing and verification keys of the JWTs, identified by `kid`.
+///
+/// Keys are read from `JWT_KEYS_DIR`, every `<kid>.crt` public key is used for
+/// verification and the `<kid>.key` of the kid named in the active file signs new tokens.
+/// Without that directory the legacy `certs/jsonwebtoken` pair is loaded instead.
+pub struct JwtKeys {
+    inner: RwLock<Arc<KeySet>>,
+}
+
+struct KeySet {
+    active_kid: String,
+    encoding_key: EncodingKey,
+    decoding_keys: AHashMap<String, DecodingKey>,
+    jwks: JwkSet,
+}
+
+impl JwtKeys {
+    pub fn load() -> AppResult<Self> {
+        let key_set = Self::read_key_set()?;
+
+        Ok(Self {
+            inner: RwLock::new(Arc::new(key_set)),
+        })
+    }
+
+    /// Reloads the keys from disk, the current keys are kept if anything fails.
+    pub fn reload(&self) -> AppResult<()> {
+        let key_set = Self::read_key_set()?;
+        let mut inner = self.inner.write();
+
+        if inner.active_kid != key_set.active_kid {
+            info!("JWT signing key rotated to {}", key_set.active_kid);
+        }
+
+        *inner = Arc::new(key_set);
+        Ok(())
+    }
+
+    /// Public keys of every loaded kid, in JWKS format.
+    #[inline]
+    pub fn jwks(&self) -> JwkSet {
+        self.inner.read().jwks.clone()
+    }
+
+    /// Signs the claims with the active key, setting its kid in the header.
+    pub fn encode<T: Serialize>(&self, claims: &T) -> Result<String, JwtError> {
+        let key_set = self.inner.read().clone();
+
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some(key_set.active_kid.clone());
+
+        encode(&header, claims, &key_set.encoding_key)
+    }
+
+    /// Verifies a token with the key named by its kid.
+    ///
+    /// Tokens without a kid were signed before keys were rotated and are checked
+    /// against the active key.
+    pub fn decode<T: DeserializeOwned>(
+        &self,
+        token: &str,
+        validation: &Validation,
+    ) -> Result<TokenData<T>, JwtError> {
+        let header = decode_header(token)?;
+        let key_set = self.inner.read().clone();
+
+        let kid = header.kid.as_deref().unwrap_or(&key_set.active_kid);
+        let Some(decoding_key) = key_set.decoding_keys.get(kid) else {
+            Err(ErrorKind::InvalidToken)?
+        };
+
+        decode(token, decoding_key, validation)
+    }
+
+    fn read_key_set() -> AppResult<KeySet> {
+        let key_set = match Path::new(JWT_KEYS_DIR).is_dir() {
+            true => Self::read_keys_dir(Path::new(JWT_KEYS_DIR)),
+            false => Self::read_legacy_pair(),
+        };
+
+        key_set.map_err(|e| {
+            error!("Failed to load JWT keys: {}", e);
+            CommonError::InternalServerError.into()
+        })
+    }
+
+    fn read_keys_dir(dir: &Path) -> Result<KeySet, String> {
+        let active_kid = fs::read_to_string(dir.join(JWT_ACTIVE_KID_FILE))
+            .map_err(|e| format!("unable to read active kid: {e}"))?
+            .trim()
+            .to_owned();
+
+        let mut public_keys = Vec::new();
+        let entries =
+            fs::read_dir(dir).map_err(|e| format!("unable to read {JWT_KEYS_DIR}: {e}"))?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            if path.extension().is_some_and(|ext| ext == "crt") {
+                if let Some(kid) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    let pem = fs::read(&path).map_err(|e| format!("unable to read {kid}: {e}"))?;
+                    public_keys.push((kid.to_owned(), pem));
+                }
+            }
+        }
+
+        let private_key = fs::read(dir.join(format!("{active_kid}.key")))
+            .map_err(|e| format!("unable to read signing key {active_kid}: {e}"))?;
+
+        Self::build_key_set(active_kid, &private_key, public_keys)
+    }
+
+    fn read_legacy_pair() -> Result<KeySet, String> {
+        let private_key = fs::read(format!("{JWT_LEGACY_KEY}.key"))
+            .map_err(|e| format!("unable to read signing key: {e}"))?;
+        let public_key = fs::read(format!("{JWT_LEGACY_KEY}.crt"))
+            .map_err(|e| format!("unable to read public key: {e}"))?;
+
+        Self::build_key_set(
+            JWT_LEGACY_KID.to_owned(),
+            &private_key,
+            vec![(JWT_LEGACY_KID.to_owned(), public_key)],
+        )
+    }
+
+    fn build_key_set(
+        active_kid: String,
+        private_key: &[u8],
+        public_keys: Vec<(String, Vec<u8>)>,
+    ) -> Result<KeySet, String> {
+        let encoding_key = EncodingKey::from_rsa_pem(private_key)
+            .map_err(|e| format!("invalid signing key {active_kid}: {e}"))?;
+
+        let mut decoding_keys = AHashMap::with_capacity(public_keys.len());
+        let mut jwks = JwkSet {
+            keys: Vec::with_capacity(public_keys.len()),
+        };
+
+        for (kid, pem) in public_keys {
+            let rsa =
+                Self::rsa_public_key(&pem).map_err(|e| format!("invalid public key {kid}: {e}"))?;
+
+            let n = URL_SAFE_NO_PAD.encode_to_string(rsa.n().to_vec());
+            let e = URL_SAFE_NO_PAD.encode_to_string(rsa.e().to_vec());
+
+            let decoding_key = DecodingKey::from_rsa_components(&n, &e)
+                .map_err(|e| format!("invalid public key {kid}: {e}"))?;
+
+            jwks.keys.push(Jwk {
+                common: CommonParameters {
+                    public_key_use: Some(PublicKeyUse::Signature),
+                    key_algorithm: Some(KeyAlgorithm::RS256),
+                    key_id: Some(kid.clone()),
+                    ..Default::default()
+                },
+                algorithm: AlgorithmParameters::RSA(RSAKeyParameters {
+                    key_type: RSAKeyType::RSA,
+                    n,
+                    e,
+                }),
+            });
+
+            decoding_keys.insert(kid, decoding_key);
+        }
+
+        if !decoding_keys.contains_key(&active_kid) {
+            Err(format!("missing public key of active kid {active_kid}"))?
+        }
+
+        Ok(KeySet {
+            active_kid,
+            encoding_key,
+            decoding_keys,
+            jwks,
+        })
+    }
+
+    /// Reads an RSA public key from a `PUBLIC KEY`, `RSA PUBLIC KEY` or
+    /// `CERTIFICATE` PEM, the formats the legacy key pair was accepted in.
+    fn rsa_public_key(pem: &[u8]) -> Result<Rsa<Public>, ErrorStack> {
+        PKey::public_key_from_pem(pem)
+            .and_then(|key| key.rsa())
+            .or_else(|_| Rsa::public_key_from_pem_pkcs1(pem))
+            .or_else(|_| X509::from_pem(pem)?.public_key()?.rsa())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use openssl::{asn1::Asn1Time, hash::MessageDigest, x509::X509Name};
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct Claims {
+        exp: usize,
+        sub: String,
+    }
+
+    fn key_pair() -> (Vec<u8>, Vec<u8>) {
+        let rsa = Rsa::generate(2048).unwrap();
+        (
+            rsa.private_key_to_pem().unwrap(),
+            rsa.public_key_to_pem().unwrap(),
+        )
+    }
+
+    #[test]
+    fn rotated_keys_verify_old_tokens() {
+        let (old_private, old_public) = key_pair();
+        let (new_private, new_public) = key_pair();
+
+        let claims = Claims {
+            exp: usize::MAX / 2,
+            sub: "600000000".to_owned(),
+        };
+
+        let old_set = JwtKeys::build_key_set(
+            "old".into(),
+            &old_private,
+            vec![("old".into(), old_public.clone())],
+        )
+        .unwrap();
+        let keys = JwtKeys {
+            inner: RwLock::new(Arc::new(old_set)),
+        };
+        let old_token = keys.encode(&claims).unwrap();
+
+        let new_set = JwtKeys::build_key_set(
+            "new".into(),
+            &new_private,
+            vec![("old".into(), old_public), ("new".into(), new_public)],
+        )
+        .unwrap();
+        *keys.inner.write() = Arc::new(new_set);
+
+        let new_token = keys.encode(&claims).unwrap();
+        assert_eq!(
+            decode_header(&new_token).unwrap().kid.as_deref(),
+            Some("new")
+        );
+
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.required_spec_claims.clear();
+
+        for token in [&old_token, &new_token] {
+            let data = keys.decode::<Claims>(token, &validation).unwrap();
+            assert_eq!(data.claims.sub, "600000000");
+        }
+
+        let jwks = keys.jwks();
+        assert!(jwks.find("old").is_some() && jwks.find("new").is_some());
+    }
+
+    #[test]
+    fn certificate_pem_is_accepted() {
+        let rsa = Rsa::generate(2048).unwrap();
+        let private_key = rsa.private_key_to_pem().unwrap();
+        let pkey = PKey::from_rsa(rsa).unwrap();
+
+        let mut name = X509Name::builder().unwrap();
+        name.append_entry_by_text("CN", "intelli-api").unwrap();
+        let name = name.build();
+
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(&pkey).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(365).unwrap())
+            .unwrap();
+        builder.sign(&pkey, MessageDigest::sha256()).unwrap();
+        let certificate = builder.build().to_pem().unwrap();
+
+        let key_set = JwtKeys::build_key_set(
+            JWT_LEGACY_KID.into(),
+            &private_key,
+            vec![(JWT_LEGACY_KID.into(), certificate)],
+        )
+        .unwrap();
+        let keys = JwtKeys {
+            inner: RwLock::new(Arc::new(key_set)),
+        };
+
+        let claims = Claims {
+            exp: usize::MAX / 2,
+            sub: "600000000".to_owned(),
+        };
+        let token = keys.encode(&claims).unwrap();
+
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.required_spec_claims.clear();
+
+        assert!(keys.decode::<Claims>(&token, &validation).is_ok());
+    }
+
+    #[test]
+    fn active_key_needs_a_public_key() {
+        let (private_key, public_key) = key_pair();
+        let key_set = JwtKeys::build_key_set(
+            "active".into(),
+            &private_key,
+            vec![("other".into(), public_key)],
+        );
+
+        assert!(key_set.is_err());
+    }
+}
@@ -2,17 +2,23 @@ use crate::{
     error::{AppResult, F1ServiceError},
     structs::PacketHeader,
 };
+pub(crate) use export::*;
 pub(crate) use ids_generator::IdsGenerator;
+pub(crate) use jwt_keys::JwtKeys;
 pub(crate) use password_hash::*;
 pub(crate) use ports::MachinePorts;
+pub(crate) use totp::Totp;
 
 use postgres_types::ToSql;
 use serde::{Deserialize, Deserializer};
 use std::mem;
 
+mod export;
 mod ids_generator;
+mod jwt_keys;
 mod password_hash;
 mod ports;
+mod totp;
 
 pub fn deserialize_i64_from_string<'de, D>(deserializer: D) -> Result<i64, D::Error>
 where
@@ -0,0 +1,164 @@
+use ring::hmac;
+
+use crate::config::constants::{TOTP_DIGITS, TOTP_ISSUER, TOTP_PERIOD, TOTP_SKEW};
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Time based one-time passwords (RFC 6238) with HMAC-SHA1, as expected by
+/// authenticator apps.
+pub struct Totp;
+
+impl Totp {
+    /// Encodes a secret in base32 without padding, the format of otpauth URIs.
+    pub fn encode_secret(bytes: &[u8]) -> String {
+        let mut encoded = String::with_capacity(bytes.len().div_ceil(5) * 8);
+        let (mut buffer, mut bits) = (0u32, 0u8);
+
+        for &byte in bytes {
+            buffer = (buffer << 8) | byte as u32;
+            bits += 8;
+
+            while bits >= 5 {
+                bits -= 5;
+                encoded.push(BASE32_ALPHABET[((buffer >> bits) & 31) as usize] as char);
+            }
+        }
+
+        if bits > 0 {
+            encoded.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+        }
+
+        encoded
+    }
+
+    /// Decodes a base32 secret, returns None on invalid characters.
+    pub fn decode_secret(secret: &str) -> Option<Vec<u8>> {
+        let mut decoded = Vec::with_capacity(secret.len() * 5 / 8);
+        let (mut buffer, mut bits) = (0u32, 0u8);
+
+        for char in secret.bytes().filter(|&char| char != b'=') {
+            let value = BASE32_ALPHABET
+                .iter()
+                .position(|&c| c == char.to_ascii_uppercase())?;
+
+            buffer = (buffer << 5) | value as u32;
+            bits += 5;
+
+            if bits >= 8 {
+                bits -= 8;
+                decoded.push((buffer >> bits) as u8);
+            }
+        }
+
+        Some(decoded)
+    }
+
+    /// Builds the otpauth URI shown as a QR code during enrolment.
+    pub fn otpauth_uri(secret: &str, account: &str) -> String {
+        format!(
+            "otpauth://totp/{issuer}:{account}?secret={secret}&issuer={issuer}&algorithm=SHA1&digits={TOTP_DIGITS}&period={TOTP_PERIOD}",
+            issuer = Self::percent_encode(TOTP_ISSUER),
+            account = Self::percent_encode(account),
+        )
+    }
+
+    /// Computes the code of a time step.
+    pub fn code(secret: &[u8], step: u64) -> u32 {
+        let key = hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, secret);
+        let tag = hmac::sign(&key, &step.to_be_bytes());
+        let hash = tag.as_ref();
+
+        let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+        let binary = u32::from_be_bytes([
+            hash[offset] & 0x7f,
+            hash[offset + 1],
+            hash[offset + 2],
+            hash[offset + 3],
+        ]);
+
+        binary % 10u32.pow(TOTP_DIGITS)
+    }
+
+    /// Verifies a code around the current time step, allowing some clock skew.
+    ///
+    /// Steps at or before `last_step` are rejected so a code can't be replayed.
+    ///
+    /// # Returns
+    /// The matched time step, to be stored as the new `last_step`.
+    pub fn verify(
+        secret: &[u8],
+        code: &str,
+        unix_time: u64,
+        last_step: Option<u64>,
+    ) -> Option<u64> {
+        if code.len() != TOTP_DIGITS as usize || !code.bytes().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        let code: u32 = code.parse().ok()?;
+        let current = unix_time / TOTP_PERIOD;
+
+        (current.saturating_sub(TOTP_SKEW)..=current + TOTP_SKEW)
+            .filter(|&step| last_step.is_none_or(|last| step > last))
+            .find(|&step| Self::code(secret, step) == code)
+    }
+
+    fn percent_encode(value: &str) -> String {
+        value
+            .bytes()
+            .fold(String::with_capacity(value.len()), |mut encoded, byte| {
+                match byte {
+                    b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'@' => {
+                        encoded.push(byte as char)
+                    }
+                    _ => encoded.push_str(&format!("%{byte:02X}")),
+                }
+
+                encoded
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 6238 appendix B, SHA1 secret
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn codes_match_rfc_vectors() {
+        let vectors = [
+            (59, 94287082),
+            (1111111109, 7081804),
+            (1111111111, 14050471),
+            (1234567890, 89005924),
+            (2000000000, 69279037),
+        ];
+
+        for (time, code) in vectors {
+            assert_eq!(Totp::code(RFC_SECRET, time / TOTP_PERIOD), code % 1_000_000);
+        }
+    }
+
+    #[test]
+    fn verify_rejects_replayed_steps() {
+        let time = 1111111109;
+        let code = format!("{:06}", Totp::code(RFC_SECRET, time / TOTP_PERIOD));
+
+        let step = Totp::verify(RFC_SECRET, &code, time, None).unwrap();
+        assert_eq!(step, time / TOTP_PERIOD);
+        assert_eq!(Totp::verify(RFC_SECRET, &code, time, Some(step)), None);
+        assert_eq!(Totp::verify(RFC_SECRET, "12345", time, None), None);
+    }
+
+    #[test]
+    fn secrets_round_trip_base32() {
+        let encoded = Totp::encode_secret(RFC_SECRET);
+
+        assert_eq!(encoded, "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ");
+        assert_eq!(Totp::decode_secret(&encoded).unwrap(), RFC_SECRET);
+        assert!(Totp::otpauth_uri(&encoded, "user@example.com")
+            .starts_with("otpauth://totp/Intelli%20Telemetry:user@example.com?secret="));
+    }
+}